@@ -0,0 +1,67 @@
+use shared::NetworkConditions;
+use std::path::PathBuf;
+use std::time::Duration;
+
+#[derive(clap::Parser)]
+#[clap(about = "Sphere Wars game client")]
+pub struct Cli {
+    #[clap(
+        long,
+        value_name = "FILE",
+        help = "Watch a recorded replay instead of joining a server"
+    )]
+    pub replay: Option<PathBuf>,
+    #[clap(
+        long,
+        conflicts_with = "replay",
+        help = "Watch the match live without playing"
+    )]
+    pub spectate: bool,
+    #[clap(
+        long,
+        default_value = "0",
+        help = "Simulated one-way latency in milliseconds"
+    )]
+    pub sim_latency: u64,
+    #[clap(
+        long,
+        default_value = "0",
+        help = "Simulated random extra latency of up to this many milliseconds"
+    )]
+    pub sim_jitter: u64,
+    #[clap(
+        long,
+        default_value = "0",
+        help = "Simulated chance of losing a packet, from 0 to 1"
+    )]
+    pub sim_loss: f32,
+    #[clap(
+        long,
+        default_value = "0",
+        help = "Simulated chance of delivering a packet twice, from 0 to 1"
+    )]
+    pub sim_duplication: f32,
+    #[clap(
+        long,
+        default_value = "0",
+        help = "Simulated chance of delivering a packet out of order, from 0 to 1"
+    )]
+    pub sim_reordering: f32,
+}
+
+impl Cli {
+    pub fn validate(&self) -> Result<(), String> {
+        self.network_conditions().validate()
+    }
+
+    // Bad network to simulate on every packet the client sends and receives
+    pub fn network_conditions(&self) -> NetworkConditions {
+        NetworkConditions {
+            latency: Duration::from_millis(self.sim_latency),
+            jitter: Duration::from_millis(self.sim_jitter),
+            loss: self.sim_loss,
+            duplication: self.sim_duplication,
+            reordering: self.sim_reordering,
+        }
+    }
+}
@@ -1,5 +1,8 @@
 use bevy::prelude::*;
-use shared::{GameState, Player};
+use shared::{
+    AmmoStatus, ControlZone, FlagState, GameMode, GameState, PickupState, Player, ProjectileState,
+    Team, ZoneOwner,
+};
 use std::collections::HashMap;
 
 #[derive(Component)]
@@ -14,6 +17,44 @@ pub struct GameData {
     pub state: Option<GameState>,
     pub game_start_time: Option<f64>,
     pub player_entities: HashMap<String, Entity>,
+    pub state_time_remaining: Option<f32>, // Counts down locally between server updates
+    pub score_limit: Option<u32>,          // Frags, or captures in Capture the Flag
+    pub mode: Option<GameMode>,
+    pub team_scores: HashMap<Team, u32>,
+    pub flags: HashMap<Team, FlagState>,
+    pub zone: Option<ControlZone>,
+    pub round: Option<u32>,       // Current elimination round
+    pub ammo: Option<AmmoStatus>, // Our weapon's ammo, reload counts down locally
+    pub projectiles: HashMap<u32, ProjectileState>,
+    pub pickups: HashMap<u32, PickupState>,
+    pub radar_remaining: Option<f32>, // Seconds our radar pulse keeps revealing enemies
+    pub spectating: bool,             // Watching with no player of our own
+    pub spectators: Vec<String>,
+}
+
+impl GameData {
+    pub fn my_player(&self) -> Option<&Player> {
+        self.my_id.as_ref().and_then(|id| self.players.get(id))
+    }
+
+    pub fn zone_owner_name(&self, owner: &ZoneOwner) -> String {
+        match owner {
+            ZoneOwner::Team(team) => format!("{} team", team.name()),
+            ZoneOwner::Player(id) => self
+                .players
+                .get(id)
+                .map(|p| p.name.clone())
+                .unwrap_or_else(|| "Unknown".to_string()),
+        }
+    }
+
+    pub fn zone_owner_color(&self, owner: &ZoneOwner) -> Color {
+        let [r, g, b] = match owner {
+            ZoneOwner::Team(team) => team.color(),
+            ZoneOwner::Player(id) => self.players.get(id).map_or([1.0; 3], |p| p.color),
+        };
+        Color::srgb(r, g, b)
+    }
 }
 
 // Component to mark the local player
@@ -2,24 +2,21 @@
 
 use bevy::prelude::*;
 
+// A server projectile drawn locally, extrapolated between snapshots
 #[derive(Component)]
-pub struct Projectile;
+pub struct Projectile {
+    pub id: u32,
+    pub velocity: Vec3,
+    pub gravity: f32,
+    pub synced_position: Vec3, // Last position the server reported
+}
 
-#[derive(Component)]
+// Local trigger state; weapon stats come from the shared weapon registry
+#[derive(Component, Default)]
 pub struct Weapon {
-    pub fire_rate: f32, // shots per second
     pub last_shot_time: f32,
 }
 
-impl Default for Weapon {
-    fn default() -> Self {
-        Self {
-            fire_rate: 4.0, // 2 shots per second
-            last_shot_time: 0.0,
-        }
-    }
-}
-
 #[derive(Component)]
 pub struct Health {
     pub current: f32,
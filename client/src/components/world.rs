@@ -1,5 +1,5 @@
 use bevy::prelude::*;
-use shared::{MazeGrid, SpawnPoint};
+use shared::{MazeGrid, Team};
 
 #[derive(Component)]
 pub struct Collidable;
@@ -19,10 +19,48 @@ pub struct MinimapInitialized;
 #[derive(Component)]
 pub struct PlayerDot;
 
+// Minimap marker for another player we are allowed to see (e.g. a teammate)
+#[derive(Component)]
+pub struct RemotePlayerDot {
+    pub id: String,
+}
+
+// Capture the Flag flag, follows its carrier when taken
+#[derive(Component)]
+pub struct FlagMarker {
+    pub team: Team,
+}
+
+// Floor pad marking where a team's flag lives
+#[derive(Component)]
+pub struct FlagBaseMarker {
+    pub team: Team,
+}
+
+// Minimap marker for a team's flag
+#[derive(Component)]
+pub struct MinimapFlag {
+    pub team: Team,
+}
+
+// A pickup spawner's rotating mesh, hidden while waiting to respawn
+#[derive(Component)]
+pub struct PickupMarker {
+    pub id: u32,
+}
+
+// King of the Hill control zone drawn on the floor
+#[derive(Component)]
+pub struct ZoneMarker;
+
+// Minimap outline of the control zone
+#[derive(Component)]
+pub struct MinimapZone;
+
 #[derive(Resource)]
 pub struct SharedMaze {
+    pub seed: u64,
     pub grid: MazeGrid,
-    pub spawn_points: Vec<SpawnPoint>,
 }
 
 #[derive(Component)]
@@ -1,17 +1,65 @@
+// Bevy systems routinely take many parameters and nested query filters
+#![allow(clippy::too_many_arguments, clippy::type_complexity)]
+
 use bevy::prelude::*;
+use clap::Parser;
+use shared::{SimulatedTransport, Transport, UdpTransport};
 
+use cli::Cli;
 use net::{ConnectionInfo, NetworkClient};
-use plugins::{NetworkPlugin, PlayerPlugin, UIPlugin, WorldPlugin};
+use plugins::{NetworkPlugin, PlayerPlugin, ReplayPlugin, SpectatorPlugin, UIPlugin, WorldPlugin};
+use systems::replay::playback::ReplayPlayback;
 use systems::utils::{
     get_init_plugins, handle_app_exit, handle_shutdown_signal, setup_signal_handlers,
 };
 
+mod cli;
 mod components;
 mod net;
 mod plugins;
 mod systems;
 
 fn main() {
+    let cli = Cli::parse();
+    if let Err(error) = cli.validate() {
+        eprintln!("Error: {}", error);
+        std::process::exit(1);
+    }
+
+    // Open the replay or connect before the window appears, so errors show in the terminal
+    let mut app = App::new();
+    if let Some(path) = &cli.replay {
+        // Watching a recording needs no server
+        let (playback, network) = match ReplayPlayback::open(path) {
+            Ok(replay) => replay,
+            Err(e) => {
+                eprintln!("❌ Failed to open replay {}: {}", path.display(), e);
+                std::process::exit(1);
+            }
+        };
+        app.add_plugins(ReplayPlugin)
+            .insert_resource(playback)
+            .insert_resource(network);
+    } else if cli.spectate {
+        let network = connect(&cli);
+        network.join_as_spectator();
+        app.add_plugins(SpectatorPlugin).insert_resource(network);
+    } else {
+        let network = connect(&cli);
+        network.join_game();
+        app.add_plugins(PlayerPlugin).insert_resource(network);
+    }
+    app.add_plugins((get_init_plugins(), WorldPlugin, NetworkPlugin, UIPlugin))
+        .add_systems(Update, (handle_shutdown_signal, handle_app_exit));
+
+    // Set up signal handlers for graceful shutdown
+    setup_signal_handlers();
+
+    println!("🎮 Starting Sphere Wars...");
+    app.run();
+}
+
+fn connect(cli: &Cli) -> NetworkClient {
     // Get connection info through interactive prompts
     let connection_info = match ConnectionInfo::prompt_user() {
         Ok(info) => info,
@@ -23,27 +71,27 @@ fn main() {
     };
 
     println!("\n🚀 Connecting to game server...");
-    let network = NetworkClient::new(
-        connection_info.host,
-        connection_info.port,
-        connection_info.username,
-    );
-
-    network.join_game();
-
-    // Set up signal handlers for graceful shutdown
-    setup_signal_handlers();
-
-    println!("🎮 Starting Sphere Wars...");
-    App::new()
-        .add_plugins((
-            get_init_plugins(),
-            WorldPlugin,
-            PlayerPlugin,
-            NetworkPlugin,
-            UIPlugin,
-        ))
-        .insert_resource(network)
-        .add_systems(Update, (handle_shutdown_signal, handle_app_exit))
-        .run();
+    let server_addr = match format!("{}:{}", connection_info.host, connection_info.port).parse() {
+        Ok(addr) => addr,
+        Err(e) => {
+            eprintln!("\n❌ Invalid server address: {}", e);
+            std::process::exit(1);
+        }
+    };
+    // Bind to a random local port
+    let udp = match UdpTransport::bind("0.0.0.0:0") {
+        Ok(udp) => udp,
+        Err(e) => {
+            eprintln!("\n❌ Failed to create socket: {}", e);
+            std::process::exit(1);
+        }
+    };
+    let conditions = cli.network_conditions();
+    let transport: Box<dyn Transport> = if conditions.is_perfect() {
+        Box::new(udp)
+    } else {
+        println!("🐌 Simulating a bad network: {:?}", conditions);
+        Box::new(SimulatedTransport::new(udp, conditions))
+    };
+    NetworkClient::new(transport, server_addr, connection_info.username)
 }
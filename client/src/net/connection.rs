@@ -110,7 +110,7 @@ fn test_server_health(host: &str, port: u16) -> bool {
         Ok((len, _)) => {
             let response = String::from_utf8_lossy(&buf[..len]);
             bincode::serde::decode_from_slice::<ServerMessage, _>(
-                &response.as_bytes(),
+                response.as_bytes(),
                 bincode::config::standard(),
             )
             .is_ok()
@@ -130,11 +130,12 @@ fn test_username_availability(host: &str, port: u16, username: &str) -> Username
     }
 
     let server_addr = format!("{}:{}", host, port);
-    let join_msg = ClientMessage::JoinGame {
-        player_name: username.to_string(),
+    // Ask without joining, so the probe never shows up in the game
+    let check_msg = ClientMessage::CheckName {
+        name: username.to_string(),
     };
 
-    let serialized = match bincode::serde::encode_to_vec(&join_msg, bincode::config::standard()) {
+    let serialized = match bincode::serde::encode_to_vec(&check_msg, bincode::config::standard()) {
         Ok(s) => s,
         Err(e) => return UsernameStatus::Error(format!("Failed to serialize message: {}", e)),
     };
@@ -148,20 +149,10 @@ fn test_username_availability(host: &str, port: u16, username: &str) -> Username
         Ok((len, _)) => {
             let response = String::from_utf8_lossy(&buf[..len]);
             match bincode::serde::decode_from_slice::<ServerMessage, _>(
-                &response.as_bytes(),
+                response.as_bytes(),
                 bincode::config::standard(),
             ) {
                 Ok((ServerMessage::NameAlreadyTaken, _)) => UsernameStatus::Taken,
-                Ok((ServerMessage::GameJoined { .. }, _)) => {
-                    // Send leave message to clean up the test connection
-                    let leave_msg = ClientMessage::LeaveGame;
-                    if let Ok(leave_serialized) =
-                        bincode::serde::encode_to_vec(&leave_msg, bincode::config::standard())
-                    {
-                        let _ = socket.send_to(&leave_serialized, &server_addr);
-                    }
-                    UsernameStatus::Available
-                }
                 Ok(_) => UsernameStatus::Available, // Other responses mean we can connect
                 Err(e) => UsernameStatus::Error(format!("Invalid server response: {}", e)),
             }
@@ -1,24 +1,23 @@
 use bevy::prelude::*;
-use shared::{ClientMessage, ServerMessage};
-use std::net::{SocketAddr, UdpSocket};
+use shared::{ChatChannel, ClientMessage, ServerMessage, Transport, WeaponKind};
+use std::net::SocketAddr;
 
 #[derive(Resource)]
 pub struct NetworkClient {
-    socket: UdpSocket,
+    transport: Box<dyn Transport>,
     server_addr: SocketAddr,
     player_name: String,
 }
 
 #[allow(dead_code)]
 impl NetworkClient {
-    pub fn new(host: String, port: u16, player_name: String) -> Self {
-        // Bind to a random local port
-        let socket = UdpSocket::bind("0.0.0.0:0").unwrap();
-        socket.set_nonblocking(true).unwrap();
-        let server_addr: SocketAddr = format!("{}:{}", host, port).parse().unwrap();
-
+    pub fn new(
+        transport: Box<dyn Transport>,
+        server_addr: SocketAddr,
+        player_name: String,
+    ) -> Self {
         Self {
-            socket,
+            transport,
             server_addr,
             player_name,
         }
@@ -30,25 +29,35 @@ impl NetworkClient {
         };
 
         if let Ok(encoded) = bincode::serde::encode_to_vec(&join_msg, bincode::config::standard()) {
-            let _ = self.socket.send_to(&encoded, self.server_addr);
+            let _ = self.transport.send_to(&encoded, self.server_addr);
+        }
+    }
+
+    pub fn join_as_spectator(&self) {
+        let join_msg = ClientMessage::JoinAsSpectator {
+            spectator_name: self.player_name.clone(),
+        };
+
+        if let Ok(encoded) = bincode::serde::encode_to_vec(&join_msg, bincode::config::standard()) {
+            let _ = self.transport.send_to(&encoded, self.server_addr);
         }
     }
 
     pub fn try_recv(&self) -> Option<ServerMessage> {
-        let mut buf = [0; 65536]; // Increased buffer size for binary data
-        match self.socket.recv_from(&mut buf) {
-            Ok((n, _)) => bincode::serde::decode_from_slice(&buf[..n], bincode::config::standard())
-                .ok()
-                .map(|(msg, _)| msg),
-            Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => None,
-            Err(_) => None,
+        match self.transport.try_recv_from() {
+            Ok(Some((packet, _))) => {
+                bincode::serde::decode_from_slice(&packet, bincode::config::standard())
+                    .ok()
+                    .map(|(msg, _)| msg)
+            }
+            Ok(None) | Err(_) => None,
         }
     }
 
     pub fn send_move(&self, position: Vec3, rotation: Quat) {
         let move_msg = ClientMessage::PlayerMove { position, rotation };
         if let Ok(encoded) = bincode::serde::encode_to_vec(&move_msg, bincode::config::standard()) {
-            let _ = self.socket.send_to(&encoded, self.server_addr);
+            let _ = self.transport.send_to(&encoded, self.server_addr);
         }
     }
 
@@ -56,7 +65,23 @@ impl NetworkClient {
         let shoot_msg = ClientMessage::PlayerShoot { origin, direction };
         if let Ok(encoded) = bincode::serde::encode_to_vec(&shoot_msg, bincode::config::standard())
         {
-            let _ = self.socket.send_to(&encoded, self.server_addr);
+            let _ = self.transport.send_to(&encoded, self.server_addr);
+        }
+    }
+
+    pub fn send_switch_weapon(&self, weapon: WeaponKind) {
+        let switch_msg = ClientMessage::SwitchWeapon { weapon };
+        if let Ok(encoded) = bincode::serde::encode_to_vec(&switch_msg, bincode::config::standard())
+        {
+            let _ = self.transport.send_to(&encoded, self.server_addr);
+        }
+    }
+
+    pub fn send_reload(&self) {
+        let reload_msg = ClientMessage::Reload;
+        if let Ok(encoded) = bincode::serde::encode_to_vec(&reload_msg, bincode::config::standard())
+        {
+            let _ = self.transport.send_to(&encoded, self.server_addr);
         }
     }
 
@@ -65,7 +90,21 @@ impl NetworkClient {
         if let Ok(encoded) =
             bincode::serde::encode_to_vec(&respawn_msg, bincode::config::standard())
         {
-            let _ = self.socket.send_to(&encoded, self.server_addr);
+            let _ = self.transport.send_to(&encoded, self.server_addr);
+        }
+    }
+
+    pub fn send_pong(&self, seq: u32) {
+        let pong_msg = ClientMessage::Pong { seq };
+        if let Ok(encoded) = bincode::serde::encode_to_vec(&pong_msg, bincode::config::standard()) {
+            let _ = self.transport.send_to(&encoded, self.server_addr);
+        }
+    }
+
+    pub fn send_chat(&self, channel: ChatChannel, text: String) {
+        let chat_msg = ClientMessage::Chat { channel, text };
+        if let Ok(encoded) = bincode::serde::encode_to_vec(&chat_msg, bincode::config::standard()) {
+            let _ = self.transport.send_to(&encoded, self.server_addr);
         }
     }
 
@@ -73,7 +112,7 @@ impl NetworkClient {
         let leave_msg = ClientMessage::LeaveGame;
         if let Ok(encoded) = bincode::serde::encode_to_vec(&leave_msg, bincode::config::standard())
         {
-            let _ = self.socket.send_to(&encoded, self.server_addr);
+            let _ = self.transport.send_to(&encoded, self.server_addr);
         }
     }
 
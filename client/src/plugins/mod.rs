@@ -1,9 +1,13 @@
 pub mod network;
 pub mod player;
+pub mod replay;
+pub mod spectator;
 pub mod ui;
 pub mod world;
 
 pub use network::NetworkPlugin;
 pub use player::PlayerPlugin;
+pub use replay::ReplayPlugin;
+pub use spectator::SpectatorPlugin;
 pub use ui::UIPlugin;
 pub use world::WorldPlugin;
@@ -8,29 +8,30 @@ use crate::{
     },
     net::NetworkClient,
     plugins::ui::show_message,
+    systems::ui::chat::{ChatHistory, show_chat},
+    systems::ui::damage_indicators::DamageIndicators,
     systems::ui::death_screen::DamageOverlayState,
+    systems::ui::event_log::EventLog,
+    systems::ui::kill_feed::{KillFeedContainer, show_kill, weapon_label},
+    systems::ui::killcam::{KillcamClip, KillcamState},
 };
 use bevy::prelude::*;
-use shared::{MazeConfig, Player, ServerMessage, generate_maze_from_config};
+use shared::{
+    FlagEvent, GameState, MazeConfig, Player, PowerUp, ProjectileDef, ServerMessage, StreakKind,
+    StreakReward, generate_maze_from_config, kill_streak_name, multi_kill_name, weapons,
+};
 use std::collections::HashMap;
 
+const INVISIBLE_ALPHA: f32 = 0.08; // How much of an invisible player still shows
+
 pub struct NetworkPlugin;
 
-#[derive(Resource)]
+#[derive(Resource, Default)]
 pub struct LocalPlayerResource {
     pub player: Option<Player>,
     pub entity: Option<Entity>,
 }
 
-impl Default for LocalPlayerResource {
-    fn default() -> Self {
-        Self {
-            player: None,
-            entity: None,
-        }
-    }
-}
-
 impl Plugin for NetworkPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<GameData>()
@@ -57,14 +58,22 @@ fn handle_network_messages(
     mut player_transforms: Query<&mut Transform, (With<LocalPlayer>, Without<RemotePlayer>)>,
     mut remote_transforms: Query<&mut Transform, (With<RemotePlayer>, Without<LocalPlayer>)>,
     mut damage_overlay: ResMut<DamageOverlayState>,
+    mut damage_indicators: ResMut<DamageIndicators>,
+    mut killcam: ResMut<KillcamState>,
+    mut event_log: ResMut<EventLog>,
     message_container: Query<Entity, With<MessageContainer>>,
+    kill_feed: Query<Entity, With<KillFeedContainer>>,
+    chat_history: Query<Entity, With<ChatHistory>>,
     network: Res<NetworkClient>,
     maze: Option<Res<SharedMaze>>,
 ) {
     while let Some(message) = network.try_recv() {
         // Debug: Log all received messages
         match &message {
-            ServerMessage::PlayerMoved { .. } => {} // Skip logging frequent movement messages
+            ServerMessage::PlayerMoved { .. }
+            | ServerMessage::ProjectileStates { .. }
+            | ServerMessage::Ping { .. }
+            | ServerMessage::Pings { .. } => {} // Skip logging frequent messages
             _ => println!("Received message: {:?}", &message),
         }
 
@@ -103,15 +112,55 @@ fn handle_network_messages(
                     game_data.player_entities.insert(player_id, entity);
                 }
             }
+            ServerMessage::SpectatorJoined => {
+                game_data.spectating = true;
+                show_message(
+                    &mut commands,
+                    "Spectating - click to follow a player".to_string(),
+                    3.0,
+                    &message_container,
+                );
+            }
+            ServerMessage::Spectators { names } => {
+                game_data.spectators = names;
+            }
+            ServerMessage::Chat {
+                sender_id,
+                sender_name,
+                channel,
+                text,
+            } => {
+                show_chat(
+                    &mut commands,
+                    &chat_history,
+                    &game_data,
+                    sender_id.as_deref(),
+                    &sender_name,
+                    channel,
+                    &text,
+                );
+            }
+            ServerMessage::Ping { seq } => {
+                network.send_pong(seq);
+            }
+            ServerMessage::Pings { pings } => {
+                for (player_id, ping_ms) in pings {
+                    if let Some(player) = game_data.players.get_mut(&player_id) {
+                        player.ping_ms = Some(ping_ms);
+                    }
+                }
+            }
             ServerMessage::GameState {
                 players,
                 state,
                 game_start_time,
+                team_scores,
             } => {
                 // First update game data and players
                 game_data.state = Some(state);
                 game_data.game_start_time = game_start_time;
                 game_data.players = players.clone();
+                game_data.team_scores = team_scores;
 
                 println!("GameState received: {} players total", players.len());
                 println!("My ID: {:?}", game_data.my_id);
@@ -161,10 +210,10 @@ fn handle_network_messages(
             }
             ServerMessage::PlayerUpdate { player } => {
                 // Update local player if it's us
-                if let Some(my_player) = &mut local_player.player {
-                    if my_player.id == player.id {
-                        *my_player = player.clone();
-                    }
+                if let Some(my_player) = &mut local_player.player
+                    && my_player.id == player.id
+                {
+                    *my_player = player.clone();
                 }
                 // Update game data
                 if let Some(existing) = game_data.players.get_mut(&player.id) {
@@ -183,6 +232,7 @@ fn handle_network_messages(
                 }
             }
             ServerMessage::PlayerJoined { player } => {
+                event_log.push(format!("{} joined", player.name));
                 game_data.players.insert(player.id.clone(), player.clone());
                 // Spawn remote player entity if it's not us
                 if Some(player.id.as_str()) != game_data.my_id.as_deref() {
@@ -210,15 +260,218 @@ fn handle_network_messages(
                 }
             }
             ServerMessage::PlayerLeft { player_id } => {
-                game_data.players.remove(&player_id);
+                if let Some(player) = game_data.players.remove(&player_id) {
+                    event_log.push(format!("{} left", player.name));
+                }
                 // Only remove and despawn if it's not the local player
-                if Some(player_id.as_str()) != game_data.my_id.as_deref() {
-                    if let Some(entity) = game_data.player_entities.remove(&player_id) {
-                        if commands.get_entity(entity).is_ok() {
-                            commands.entity(entity).despawn();
+                if Some(player_id.as_str()) != game_data.my_id.as_deref()
+                    && let Some(entity) = game_data.player_entities.remove(&player_id)
+                    && commands.get_entity(entity).is_ok()
+                {
+                    commands.entity(entity).despawn();
+                }
+            }
+            ServerMessage::ZoneUpdate { zone, moved } => {
+                let previous_owner = game_data.zone.as_ref().and_then(|z| z.owner.clone());
+                if moved && game_data.state == Some(GameState::GameStarted) {
+                    show_message(
+                        &mut commands,
+                        "The hill has moved!".to_string(),
+                        3.0,
+                        &message_container,
+                    );
+                } else if let Some(owner) = &zone.owner
+                    && previous_owner.as_ref() != Some(owner)
+                {
+                    let announcement =
+                        format!("{} controls the hill", game_data.zone_owner_name(owner));
+                    show_message(&mut commands, announcement, 3.0, &message_container);
+                }
+
+                game_data.zone = Some(zone);
+            }
+            ServerMessage::PickupStates { pickups } => {
+                game_data.pickups = pickups.into_iter().map(|p| (p.id, p)).collect();
+            }
+            ServerMessage::PickupUpdate {
+                pickup,
+                collected_by,
+            } => {
+                if collected_by.is_some() && collected_by == game_data.my_id {
+                    show_message(
+                        &mut commands,
+                        format!("Picked up {}", pickup.kind.name()),
+                        2.0,
+                        &message_container,
+                    );
+                }
+                game_data.pickups.insert(pickup.id, pickup);
+            }
+            ServerMessage::Streak {
+                player_id,
+                kind,
+                count,
+                reward,
+            } => {
+                match &kind {
+                    StreakKind::KillStreak => {
+                        if let Some(player) = game_data.players.get_mut(&player_id) {
+                            player.streak = count;
+                        }
+                    }
+                    StreakKind::Ended { .. } => {
+                        if let Some(player) = game_data.players.get_mut(&player_id) {
+                            player.streak = 0;
                         }
                     }
+                    StreakKind::MultiKill => {}
                 }
+
+                let is_me = Some(&player_id) == game_data.my_id.as_ref();
+                let name_of = |id: &str| {
+                    if game_data.my_id.as_deref() == Some(id) {
+                        "You".to_string()
+                    } else {
+                        game_data
+                            .players
+                            .get(id)
+                            .map_or("Unknown".to_string(), |p| p.name.clone())
+                    }
+                };
+                let who = name_of(&player_id);
+
+                let announcement = match &kind {
+                    StreakKind::MultiKill => Some(format!("{}: {}!", who, multi_kill_name(count))),
+                    StreakKind::KillStreak => kill_streak_name(count)
+                        .map(|name| format!("{}: {}! ({} kills in a row)", who, name, count)),
+                    StreakKind::Ended { ended_by } => {
+                        let ended_by = ended_by.as_deref().map_or("Fate".to_string(), name_of);
+                        let whose = if is_me {
+                            "your".to_string()
+                        } else {
+                            format!("{}'s", who)
+                        };
+                        Some(format!(
+                            "{} ended {} {} kill streak",
+                            ended_by, whose, count
+                        ))
+                    }
+                };
+                if let Some(announcement) = announcement {
+                    event_log.push(announcement.clone());
+                    show_message(&mut commands, announcement, 3.0, &message_container);
+                }
+
+                if is_me && let Some(StreakReward::RadarPulse { secs }) = reward {
+                    game_data.radar_remaining = Some(secs);
+                    show_message(
+                        &mut commands,
+                        "RADAR PULSE - enemies revealed on the minimap".to_string(),
+                        3.0,
+                        &message_container,
+                    );
+                }
+            }
+            ServerMessage::PlayerStatus {
+                player_id,
+                health,
+                armor,
+                power_ups,
+            } => {
+                if let Some(player) = game_data.players.get_mut(&player_id) {
+                    player.health = health;
+                    player.armor = armor;
+                    player.power_ups = power_ups;
+                }
+            }
+            ServerMessage::ProjectileStates { projectiles } => {
+                game_data.projectiles = projectiles.into_iter().map(|p| (p.id, p)).collect();
+            }
+            ServerMessage::ProjectileExploded {
+                id,
+                weapon,
+                position,
+            } => {
+                game_data.projectiles.remove(&id);
+                if let Some(projectile) = &weapons().get(weapon).projectile {
+                    spawn_explosion_effect(
+                        &mut commands,
+                        &mut meshes,
+                        &mut materials,
+                        position,
+                        projectile,
+                    );
+                }
+            }
+            ServerMessage::AmmoUpdate { status } => {
+                game_data.ammo = Some(status);
+            }
+            ServerMessage::PlayerScored { player_id, score } => {
+                if let Some(player) = game_data.players.get_mut(&player_id) {
+                    player.score = score;
+                }
+            }
+            ServerMessage::WeaponSwitched { player_id, weapon } => {
+                if let Some(player) = game_data.players.get_mut(&player_id) {
+                    player.weapon = weapon;
+                }
+                if Some(&player_id) == game_data.my_id.as_ref() {
+                    if let Some(player) = local_player.player.as_mut() {
+                        player.weapon = weapon;
+                    }
+                    show_message(
+                        &mut commands,
+                        format!("Switched to {}", weapons().get(weapon).name),
+                        1.5,
+                        &message_container,
+                    );
+                }
+            }
+            ServerMessage::PlayerInfected {
+                player_id,
+                infected_by,
+            } => {
+                if let Some(player) = game_data.players.get_mut(&player_id) {
+                    player.infect();
+                }
+                if Some(&player_id) == game_data.my_id.as_ref()
+                    && let Some(player) = local_player.player.as_mut()
+                {
+                    player.infect();
+                }
+
+                let name = |id: &str| {
+                    game_data
+                        .players
+                        .get(id)
+                        .map(|p| p.name.clone())
+                        .unwrap_or_else(|| "Someone".to_string())
+                };
+                let announcement = if Some(&player_id) == game_data.my_id.as_ref() {
+                    "You have been infected - spread it!".to_string()
+                } else {
+                    match infected_by {
+                        Some(by) => format!("{} was infected by {}", name(&player_id), name(&by)),
+                        None => format!("{} is patient zero!", name(&player_id)),
+                    }
+                };
+                show_message(&mut commands, announcement, 3.0, &message_container);
+            }
+            ServerMessage::RoundStarted { round } => {
+                show_message(
+                    &mut commands,
+                    format!("Round {} - last one standing wins!", round),
+                    3.0,
+                    &message_container,
+                );
+                game_data.round = Some(round);
+            }
+            ServerMessage::RoundOver { round, winner_id } => {
+                let announcement = match winner_id.and_then(|id| game_data.players.get(&id)) {
+                    Some(winner) => format!("{} wins round {}!", winner.name, round),
+                    None => format!("Round {} is a draw - nobody survived", round),
+                };
+                show_message(&mut commands, announcement, 4.0, &message_container);
             }
             ServerMessage::GameStarted {
                 seed,
@@ -226,6 +479,11 @@ fn handle_network_messages(
                 height,
                 difficulty,
             } => {
+                // A new seed means a new round; the same seed is just a repeat
+                if maze.as_ref().is_some_and(|maze| maze.seed == seed) {
+                    continue;
+                }
+
                 let config = MazeConfig {
                     seed,
                     width,
@@ -234,10 +492,114 @@ fn handle_network_messages(
                 };
                 let maze_data = generate_maze_from_config(&config);
                 commands.insert_resource(SharedMaze {
+                    seed,
                     grid: maze_data.grid,
-                    spawn_points: maze_data.spawn_points,
                 });
             }
+            ServerMessage::TeamScores { team_scores } => {
+                game_data.team_scores = team_scores;
+            }
+            ServerMessage::FlagStates { flags } => {
+                game_data.flags = flags.into_iter().map(|flag| (flag.team, flag)).collect();
+            }
+            ServerMessage::FlagUpdate {
+                flag,
+                event,
+                player_id,
+            } => {
+                let player_name = player_id
+                    .and_then(|id| game_data.players.get(&id))
+                    .map(|p| p.name.clone())
+                    .unwrap_or_else(|| "the timer".to_string());
+                let announcement = match event {
+                    FlagEvent::PickedUp => {
+                        format!("{} flag taken by {}", flag.team.name(), player_name)
+                    }
+                    FlagEvent::Dropped => format!("{} flag dropped", flag.team.name()),
+                    FlagEvent::Returned => {
+                        format!("{} flag returned by {}", flag.team.name(), player_name)
+                    }
+                    FlagEvent::Captured => {
+                        format!("{} captured the {} flag!", player_name, flag.team.name())
+                    }
+                };
+                show_message(&mut commands, announcement, 3.0, &message_container);
+
+                game_data.flags.insert(flag.team, flag);
+            }
+            ServerMessage::MatchStateChanged {
+                state,
+                mode,
+                time_remaining,
+                score_limit,
+            } => {
+                let announcement = match state {
+                    GameState::WaitingForPlayers => "Warmup - waiting for players".to_string(),
+                    GameState::Countdown => format!(
+                        "Match starting in {:.0} seconds",
+                        time_remaining.unwrap_or_default()
+                    ),
+                    GameState::GameStarted => format!("{} started - fight!", mode.name()),
+                    GameState::GameOver => "Match over".to_string(),
+                };
+                show_message(&mut commands, announcement, 3.0, &message_container);
+
+                game_data.state = Some(state);
+                game_data.mode = Some(mode);
+                game_data.state_time_remaining = time_remaining;
+                game_data.score_limit = score_limit;
+            }
+            ServerMessage::MatchResults {
+                winner_id,
+                winning_team,
+                reason,
+                players,
+            } => {
+                let winner_name = match winning_team {
+                    Some(team) => format!("Team {}", team.name()),
+                    None if game_data.mode.is_some_and(|mode| mode.is_team_mode()) => {
+                        "Nobody (draw)".to_string()
+                    }
+                    None => winner_id
+                        .and_then(|id| players.get(&id))
+                        .map(|p| p.name.clone())
+                        .unwrap_or_else(|| "Nobody".to_string()),
+                };
+
+                let mut standings: Vec<&Player> = players.values().collect();
+                standings.sort_by(|a, b| {
+                    (b.score, b.kills)
+                        .cmp(&(a.score, a.kills))
+                        .then(a.deaths.cmp(&b.deaths))
+                });
+                let show_points = game_data.mode.is_some_and(|mode| mode.has_control_zone());
+                let mut summary = format!("{} - {} wins!", reason, winner_name);
+                for (rank, player) in standings.iter().take(3).enumerate() {
+                    let points = if show_points {
+                        format!("{} pts  ", player.score)
+                    } else {
+                        String::new()
+                    };
+                    summary.push_str(&format!(
+                        "\n{}. {}  {}{} / {} / {}",
+                        rank + 1,
+                        player.name,
+                        points,
+                        player.kills,
+                        player.deaths,
+                        player.assists
+                    ));
+                }
+                if !game_data.spectators.is_empty() {
+                    summary.push_str(&format!(
+                        "\nSpectating: {}",
+                        game_data.spectators.join(", ")
+                    ));
+                }
+                show_message(&mut commands, summary, 8.0, &message_container);
+
+                game_data.players = players;
+            }
             ServerMessage::NameAlreadyTaken => {
                 println!(
                     "WARNING: Received NameAlreadyTaken message - this should only happen during join!"
@@ -270,31 +632,87 @@ fn handle_network_messages(
                 }
                 println!("Player {} fired shot. Hit: {}", player_id, hit_result.hit);
             }
+            ServerMessage::Killcam {
+                killer_name,
+                weapon,
+                killer_health,
+                killer_frames,
+                victim_frames,
+                shots,
+                ..
+            } => {
+                killcam.play(KillcamClip {
+                    killer_name,
+                    weapon,
+                    killer_health,
+                    killer_frames,
+                    victim_frames,
+                    shots,
+                });
+            }
             ServerMessage::PlayerDamaged {
                 player_id,
                 damage,
                 health,
                 damage_by,
+                source_position,
             } => {
                 // Update player health
                 if let Some(player) = game_data.players.get_mut(&player_id) {
                     player.health = health;
                 }
 
-                // Trigger damage overlay if this is the local player
-                if Some(player_id.as_str()) == game_data.my_id.as_deref() {
+                // Trigger damage overlay if this is the local player, pointing at whoever did it
+                let my_id = game_data.my_id.as_deref();
+                if Some(player_id.as_str()) == my_id {
                     damage_overlay.trigger_damage_flash();
+                    if let Some(attacker_id) =
+                        damage_by.as_ref().filter(|id| Some(id.as_str()) != my_id)
+                        && let Some(position) = source_position
+                    {
+                        damage_indicators.damaged_by(attacker_id.clone(), position);
+                    }
+                } else if damage_by.as_deref() == my_id && my_id.is_some() {
+                    damage_indicators.hit(health <= 0.0);
                 }
 
                 println!(
-                    "Player {} took {} damage from {}. Health: {}",
+                    "Player {} took {} damage from {:?}. Health: {}",
                     player_id, damage, damage_by, health
                 );
             }
             ServerMessage::PlayerDied {
                 player_id,
                 killer_id,
+                assist_ids,
+                weapon,
+                kind,
             } => {
+                show_kill(
+                    &mut commands,
+                    &kill_feed,
+                    &game_data,
+                    killer_id.as_deref(),
+                    &player_id,
+                    weapon,
+                    &kind,
+                );
+                let name_of = |id: &str| {
+                    game_data
+                        .players
+                        .get(id)
+                        .map_or("Unknown".to_string(), |p| p.name.clone())
+                };
+                event_log.push(match &killer_id {
+                    Some(killer_id) => format!(
+                        "{} {} {}",
+                        name_of(killer_id),
+                        weapon_label(weapon, &kind),
+                        name_of(&player_id)
+                    ),
+                    None => format!("{} died", name_of(&player_id)),
+                });
+
                 if let Some(player) = game_data.players.get_mut(&player_id) {
                     player.is_alive = false;
                     player.health = 0.0;
@@ -305,9 +723,9 @@ fn handle_network_messages(
                 if let Some(entity) = game_data.player_entities.get(&player_id) {
                     // Get the current transform before despawning
                     let current_transform = if Some(&player_id) != game_data.my_id.as_ref() {
-                        remote_transforms.get(*entity).ok().map(|t| t.clone())
+                        remote_transforms.get(*entity).ok().copied()
                     } else {
-                        player_transforms.get(*entity).ok().map(|t| t.clone())
+                        player_transforms.get(*entity).ok().copied()
                     };
 
                     // Despawn the old entity
@@ -364,62 +782,60 @@ fn handle_network_messages(
                     }
                 }
 
-                // Update killer stats
+                // Update killer and assist stats, leaving team kills uncredited like the server
                 if let Some(killer_id) = killer_id {
-                    if let Some(killer) = game_data.players.get_mut(&killer_id) {
+                    let team_kill = game_data
+                        .players
+                        .get(&killer_id)
+                        .zip(game_data.players.get(&player_id))
+                        .is_some_and(|(killer, victim)| killer.is_teammate(victim));
+                    if !team_kill && let Some(killer) = game_data.players.get_mut(&killer_id) {
                         killer.kills += 1;
                     }
-                    println!("Player {} was killed by {}", player_id, killer_id);
+                    println!(
+                        "Player {} was killed by {} ({:?}, {:?})",
+                        player_id, killer_id, weapon, kind
+                    );
                 } else {
                     println!("Player {} died", player_id);
                 }
+                for assist_id in &assist_ids {
+                    if let Some(assister) = game_data.players.get_mut(assist_id) {
+                        assister.assists += 1;
+                    }
+                }
+                if game_data
+                    .my_id
+                    .as_ref()
+                    .is_some_and(|my_id| assist_ids.contains(my_id))
+                {
+                    let victim_name = game_data
+                        .players
+                        .get(&player_id)
+                        .map_or("Unknown".to_string(), |p| p.name.clone());
+                    show_message(
+                        &mut commands,
+                        format!("Assist on {}", victim_name),
+                        2.0,
+                        &message_container,
+                    );
+                }
             }
             ServerMessage::PlayerRespawned {
                 player_id,
                 position,
             } => {
-                // Store the final position and rotation for entity update
-                let mut final_position = position;
-                let mut final_rotation = Quat::IDENTITY;
-
-                // Calculate spawn index before mutable borrow
-                let spawn_index = if let Some(maze) = maze.as_ref() {
-                    if !maze.spawn_points.is_empty() {
-                        Some((player_id.len() + game_data.players.len()) % maze.spawn_points.len())
-                    } else {
-                        None
-                    }
-                } else {
-                    None
-                };
+                // The server picks the spawn, e.g. on the player's own side in team modes
+                let final_position = position;
+                let final_rotation = Quat::IDENTITY;
 
                 // Update player state
                 if let Some(player) = game_data.players.get_mut(&player_id) {
                     player.is_alive = true;
                     player.health = player.max_health;
-
-                    // Use client-side spawn point selection if we have maze data
-                    if let Some(maze) = maze.as_ref() {
-                        // Select a spawn point from the maze
-                        let spawn_points = &maze.spawn_points;
-                        if !spawn_points.is_empty() {
-                            if let Some(index) = spawn_index {
-                                let spawn_point = &spawn_points[index];
-                                player.position = spawn_point.position;
-                                player.rotation = spawn_point.rotation;
-                                final_position = spawn_point.position;
-                                final_rotation = spawn_point.rotation;
-                            }
-                        } else {
-                            // Fallback to server position if no spawn points
-                            player.position = position;
-                            final_position = position;
-                        }
-                    } else {
-                        // Use server position if no maze data
-                        player.position = position;
-                        final_position = position;
-                    }
+                    player.armor = 0.0;
+                    player.power_ups.clear();
+                    player.position = position;
                 }
 
                 // Recreate entity if it doesn't exist (was despawned on death)
@@ -486,14 +902,13 @@ fn handle_network_messages(
                 // Update entity position if it exists
                 if let Some(entity) = game_data.player_entities.get(&player_id) {
                     // Check if it's the local player
-                    if let Some(local_player_entity) = local_player.entity {
-                        if *entity == local_player_entity {
-                            if let Ok(mut transform) = player_transforms.get_mut(*entity) {
-                                transform.translation = final_position;
-                                transform.rotation = final_rotation;
-                                transform.scale = Vec3::ONE; // Restore normal scale on respawn
-                            }
-                        }
+                    if let Some(local_player_entity) = local_player.entity
+                        && *entity == local_player_entity
+                        && let Ok(mut transform) = player_transforms.get_mut(*entity)
+                    {
+                        transform.translation = final_position;
+                        transform.rotation = final_rotation;
+                        transform.scale = Vec3::ONE; // Restore normal scale on respawn
                     }
                     // Check if it's a remote player
                     if let Ok(mut transform) = remote_transforms.get_mut(*entity) {
@@ -536,18 +951,42 @@ fn sync_player_transforms(
 fn sync_remote_players(
     mut game_data: ResMut<GameData>,
     mut commands: Commands,
-    mut query: Query<(Entity, &RemotePlayer, &mut Transform)>,
+    mut query: Query<(
+        Entity,
+        &RemotePlayer,
+        &mut Transform,
+        &MeshMaterial3d<StandardMaterial>,
+    )>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
 ) {
     let mut existing_players: HashMap<String, Entity> = HashMap::new();
 
     // Update existing remote players
-    for (entity, remote_player, mut transform) in query.iter_mut() {
+    for (entity, remote_player, mut transform, material) in query.iter_mut() {
         if let Some(player) = game_data.players.get(&remote_player.id) {
             transform.translation = player.position;
             transform.rotation = player.rotation;
             existing_players.insert(remote_player.id.clone(), entity);
+
+            // Recolor players that switched teams, e.g. when infected, and
+            // fade out anyone who is invisible to a faint shimmer
+            let alpha = if player.has_power_up(PowerUp::Invisibility) {
+                INVISIBLE_ALPHA
+            } else {
+                1.0
+            };
+            let color = Color::srgba(player.color[0], player.color[1], player.color[2], alpha);
+            if let Some(material) = materials.get_mut(&material.0)
+                && material.base_color != color
+            {
+                material.base_color = color;
+                material.alpha_mode = if alpha < 1.0 {
+                    AlphaMode::Blend
+                } else {
+                    AlphaMode::Opaque
+                };
+            }
         }
     }
 
@@ -614,6 +1053,32 @@ fn spawn_shot_effect(
     ));
 }
 
+fn spawn_explosion_effect(
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    materials: &mut ResMut<Assets<StandardMaterial>>,
+    position: Vec3,
+    projectile: &ProjectileDef,
+) {
+    let [r, g, b] = projectile.color;
+
+    // A translucent flash the size of the blast
+    commands.spawn((
+        Mesh3d(meshes.add(Sphere::new(projectile.splash_radius))),
+        MeshMaterial3d(materials.add(StandardMaterial {
+            base_color: Color::srgba(r, g, b, 0.35),
+            emissive: LinearRgba::new(r * 4.0, g * 4.0, b * 4.0, 1.0),
+            alpha_mode: AlphaMode::Blend,
+            unlit: true,
+            ..default()
+        })),
+        Transform::from_translation(position),
+        HitEffect {
+            timer: Timer::from_seconds(0.3, TimerMode::Once),
+        },
+    ));
+}
+
 fn cleanup_hit_effects(
     mut commands: Commands,
     mut query: Query<(Entity, &mut HitEffect)>,
@@ -621,10 +1086,8 @@ fn cleanup_hit_effects(
 ) {
     for (entity, mut hit_effect) in query.iter_mut() {
         hit_effect.timer.tick(time.delta());
-        if hit_effect.timer.finished() {
-            if commands.get_entity(entity).is_ok() {
-                commands.entity(entity).despawn();
-            }
+        if hit_effect.timer.finished() && commands.get_entity(entity).is_ok() {
+            commands.entity(entity).despawn();
         }
     }
 }
@@ -1,6 +1,7 @@
 use bevy::prelude::*;
 
 use crate::systems::player::{camera::*, physics::*, setup::*, shooting::*};
+use crate::systems::ui::chat::chat_closed;
 use crate::systems::ui::death_screen::*;
 
 pub struct PlayerPlugin;
@@ -13,17 +14,23 @@ impl Plugin for PlayerPlugin {
                 Update,
                 (
                     track_scene_instances,
-                    move_player,
-                    handle_jumping,
+                    // Typing in the chat box doesn't move or shoot
+                    (
+                        move_player,
+                        handle_jumping,
+                        hitscan_shooting,
+                        switch_weapon,
+                        reload_weapon,
+                        handle_manual_respawn,
+                    )
+                        .run_if(chat_closed),
                     apply_gravity,
                     follow_camera_system,
                     camera_look_sys,
                     grab_mouse,
                     handle_collisions,
-                    hitscan_shooting,
                     update_death_state,
                     handle_death_screen,
-                    handle_manual_respawn,
                     disable_movement_when_dead,
                 ),
             );
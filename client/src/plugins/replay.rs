@@ -0,0 +1,18 @@
+use bevy::prelude::*;
+
+use super::spectator::SpectatorCameraPlugin;
+use crate::systems::replay::{hud::*, playback::*};
+
+// Watching a recorded match, used instead of the PlayerPlugin
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(SpectatorCameraPlugin)
+            .add_systems(Startup, (start_replay, setup_replay_hud))
+            .add_systems(
+                Update,
+                ((replay_controls, advance_replay).chain(), update_replay_hud),
+            );
+    }
+}
@@ -0,0 +1,32 @@
+use bevy::prelude::*;
+
+use crate::systems::player::setup::grab_mouse;
+use crate::systems::spectator::{camera::*, hud::*};
+use crate::systems::ui::chat::chat_closed;
+
+// Free-fly and follow cameras for watching instead of playing
+pub struct SpectatorCameraPlugin;
+
+impl Plugin for SpectatorCameraPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SpectatorCamera>().add_systems(
+            Update,
+            (
+                (switch_camera_view, free_camera).run_if(chat_closed),
+                follow_watched_player,
+                grab_mouse,
+            ),
+        );
+    }
+}
+
+// Watching a live match, used instead of the PlayerPlugin
+pub struct SpectatorPlugin;
+
+impl Plugin for SpectatorPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(SpectatorCameraPlugin)
+            .add_systems(Startup, setup_spectator_hud)
+            .add_systems(Update, update_spectator_hud);
+    }
+}
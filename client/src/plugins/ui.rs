@@ -1,7 +1,20 @@
 use crate::components::ui::{MessageContainer, MessageDisplay};
+use crate::systems::replay::playback::ReplayPlayback;
+use crate::systems::ui::ammo::{setup_ammo_display, update_ammo_display};
+use crate::systems::ui::chat::{ChatInput, chat_input, setup_chat, update_chat};
+use crate::systems::ui::damage_indicators::{
+    DamageIndicators, setup_damage_indicators, update_damage_indicators,
+};
 use crate::systems::ui::death_screen::{
     DamageOverlayState, DeathState, handle_damage_overlay, handle_death_screen, setup_death_screen,
 };
+use crate::systems::ui::event_log::{EventLog, setup_event_log, update_event_log};
+use crate::systems::ui::hud::{setup_hud, update_hud};
+use crate::systems::ui::kill_feed::{setup_kill_feed, update_kill_feed};
+use crate::systems::ui::killcam::{KillcamGizmos, KillcamState, play_killcam, setup_killcam};
+use crate::systems::ui::match_status::{setup_match_status, update_match_status};
+use crate::systems::ui::scoreboard::{setup_scoreboard, update_scoreboard};
+use crate::systems::ui::zone_status::{setup_zone_status, update_zone_status};
 use bevy::prelude::*;
 
 pub struct UIPlugin;
@@ -10,7 +23,28 @@ impl Plugin for UIPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<DeathState>()
             .init_resource::<DamageOverlayState>()
-            .add_systems(Startup, (setup_ui, setup_death_screen))
+            .init_resource::<DamageIndicators>()
+            .init_resource::<KillcamState>()
+            .init_resource::<EventLog>()
+            .init_resource::<ChatInput>()
+            .init_gizmo_group::<KillcamGizmos>()
+            .add_systems(
+                Startup,
+                (
+                    setup_ui,
+                    setup_death_screen,
+                    setup_damage_indicators,
+                    setup_killcam,
+                    setup_scoreboard,
+                    setup_kill_feed,
+                    setup_event_log,
+                    setup_chat,
+                    setup_match_status,
+                    setup_zone_status,
+                    setup_ammo_display,
+                    setup_hud,
+                ),
+            )
             .add_systems(
                 Update,
                 (
@@ -18,6 +52,18 @@ impl Plugin for UIPlugin {
                     cleanup_expired_messages,
                     handle_death_screen,
                     handle_damage_overlay,
+                    update_damage_indicators,
+                    play_killcam,
+                    update_scoreboard,
+                    update_kill_feed,
+                    update_event_log,
+                    // Nobody to talk to in a replay
+                    chat_input.run_if(not(resource_exists::<ReplayPlayback>)),
+                    update_chat,
+                    update_match_status,
+                    update_zone_status,
+                    update_ammo_display,
+                    update_hud,
                 ),
             );
     }
@@ -3,9 +3,13 @@ use bevy::prelude::*;
 use crate::{
     components::world::SharedMaze,
     systems::world::{
+        flags::update_flags,
         maze::{position_player_in_maze, setup_maze, setup_maze_materials},
+        pickups::{tick_power_ups, update_pickups},
+        projectiles::update_projectiles,
         setup::setup_world,
         ui::{crosshairs::*, fps::*, minimap::*},
+        zone::update_zone,
     },
 };
 
@@ -30,11 +34,24 @@ impl Plugin for WorldPlugin {
                 (
                     (setup_maze, position_player_in_maze)
                         .chain()
-                        .run_if(resource_added::<SharedMaze>),
+                        .run_if(resource_changed::<SharedMaze>),
                     update_fps_counter,
-                    update_minimap,
+                    (
+                        reset_minimap.run_if(resource_changed::<SharedMaze>),
+                        update_minimap,
+                    )
+                        .chain(),
                     update_player_position_on_minimap,
                     update_player_dot_colors,
+                    update_remote_player_dots,
+                    tick_radar_pulse,
+                    update_minimap_flags,
+                    update_flags,
+                    update_minimap_zone,
+                    update_zone,
+                    update_projectiles,
+                    update_pickups,
+                    tick_power_ups,
                 )
                     .run_if(resource_exists::<SharedMaze>),
             );
@@ -1,4 +1,6 @@
 pub mod player;
+pub mod replay;
+pub mod spectator;
 pub mod ui;
 pub mod utils;
 pub mod world;
@@ -6,15 +6,15 @@ pub fn follow_camera_system(
     player_query: Query<&Transform, (With<Player>, Without<FollowCamera>)>,
     mut camera_query: Query<&mut Transform, (With<FollowCamera>, Without<Player>)>,
 ) {
-    if let Ok(player_transform) = player_query.single() {
-        if let Ok(mut camera_transform) = camera_query.single_mut() {
-            let player_pos = player_transform.translation;
-            // Position camera higher and slightly forward to avoid seeing tank parts
-            // Use the player's forward direction to offset the camera properly
-            let forward = player_transform.forward();
-            let camera_offset = Vec3::new(0.0, 1.0, 0.0) + forward * 1.5; // Lower camera position
-            camera_transform.translation = player_pos + camera_offset;
-        }
+    if let Ok(player_transform) = player_query.single()
+        && let Ok(mut camera_transform) = camera_query.single_mut()
+    {
+        let player_pos = player_transform.translation;
+        // Position camera higher and slightly forward to avoid seeing tank parts
+        // Use the player's forward direction to offset the camera properly
+        let forward = player_transform.forward();
+        let camera_offset = Vec3::new(0.0, 1.0, 0.0) + forward * 1.5; // Lower camera position
+        camera_transform.translation = player_pos + camera_offset;
     }
 }
 
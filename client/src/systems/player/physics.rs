@@ -1,6 +1,7 @@
 use bevy::prelude::*;
 
 use crate::components::{
+    network::GameData,
     player::{FollowCamera, Grounded, Player, Velocity},
     world::Collidable,
 };
@@ -21,11 +22,13 @@ pub fn move_player(
     time: Res<Time>,
     network: Res<NetworkClient>,
     death_state: Res<DeathState>,
+    game_data: Res<GameData>,
 ) {
     // Don't allow movement when dead
     if death_state.is_dead {
         return;
     }
+    let speed = PLAYER_SPEED * game_data.my_player().map_or(1.0, |p| p.speed_multiplier());
     let camera_transform = if let Ok(transform) = camera_q.single() {
         transform
     } else {
@@ -55,7 +58,7 @@ pub fn move_player(
             direction = camera_rotation * direction;
         }
 
-        let move_delta = direction * PLAYER_SPEED * time.delta_secs();
+        let move_delta = direction * speed * time.delta_secs();
 
         let current_pos = transform.translation;
 
@@ -1,10 +1,21 @@
 use crate::NetworkClient;
 use crate::components::{
+    network::GameData,
     player::{FollowCamera, Player},
     projectile::Weapon,
 };
 use crate::systems::ui::death_screen::DeathState;
 use bevy::prelude::*;
+use shared::{WeaponKind, weapons};
+
+const WEAPON_KEYS: [KeyCode; 6] = [
+    KeyCode::Digit1,
+    KeyCode::Digit2,
+    KeyCode::Digit3,
+    KeyCode::Digit4,
+    KeyCode::Digit5,
+    KeyCode::Digit6,
+];
 
 pub fn hitscan_shooting(
     mouse_input: Res<ButtonInput<MouseButton>>,
@@ -13,6 +24,7 @@ pub fn hitscan_shooting(
     network: Res<NetworkClient>,
     time: Res<Time>,
     death_state: Res<DeathState>,
+    game_data: Res<GameData>,
 ) {
     if !mouse_input.just_pressed(MouseButton::Left) {
         return;
@@ -29,11 +41,25 @@ pub fn hitscan_shooting(
         return;
     };
 
+    // Wait for the server to finish our reload or hand us ammo
+    if game_data
+        .ammo
+        .as_ref()
+        .is_some_and(|status| status.reload_remaining.is_some() || status.ammo.magazine == 0)
+    {
+        return;
+    }
+
     for mut weapon in player_q.iter_mut() {
         let current_time = time.elapsed_secs();
 
-        // Check fire rate
-        if current_time - weapon.last_shot_time < 1.0 / weapon.fire_rate {
+        // Check fire rate of our current weapon, scaled by our loadout
+        let fire_rate = game_data
+            .my_player()
+            .map_or(weapons().get(WeaponKind::default()).fire_rate, |p| {
+                weapons().get(p.weapon).fire_rate * p.loadout.fire_rate
+            });
+        if current_time - weapon.last_shot_time < 1.0 / fire_rate {
             continue;
         }
 
@@ -53,3 +79,39 @@ pub fn hitscan_shooting(
         );
     }
 }
+
+// R reloads while alive; while dead it requests a respawn instead
+pub fn reload_weapon(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    network: Res<NetworkClient>,
+    death_state: Res<DeathState>,
+    game_data: Res<GameData>,
+) {
+    if death_state.is_dead || !keyboard_input.just_pressed(KeyCode::KeyR) {
+        return;
+    }
+
+    if let Some(status) = &game_data.ammo
+        && status.reload_remaining.is_none()
+        && status.ammo.can_reload(weapons().get(status.weapon))
+    {
+        network.send_reload();
+    }
+}
+
+// Number keys pick a weapon; the server confirms the switch
+pub fn switch_weapon(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    network: Res<NetworkClient>,
+    game_data: Res<GameData>,
+) {
+    let Some(current) = game_data.my_player().map(|p| p.weapon) else {
+        return;
+    };
+
+    for (key, weapon) in WEAPON_KEYS.iter().zip(WeaponKind::ALL) {
+        if keyboard_input.just_pressed(*key) && weapon != current {
+            network.send_switch_weapon(weapon);
+        }
+    }
+}
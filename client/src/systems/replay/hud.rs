@@ -0,0 +1,69 @@
+use bevy::prelude::*;
+
+use super::playback::ReplayPlayback;
+use crate::components::network::GameData;
+use crate::systems::spectator::camera::SpectatorCamera;
+
+const CONTROLS_HINT: &str = "Space pause | Left/Right seek 5s (Shift 30s) | Up/Down speed | Home restart | Click next/previous player | F free camera | Tab scores";
+
+#[derive(Component)]
+pub struct ReplayStatusText;
+
+pub fn setup_replay_hud(mut commands: Commands) {
+    // Playback bar along the bottom of the screen
+    commands
+        .spawn(Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(20.0),
+            width: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 24.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+                ReplayStatusText,
+            ));
+            parent.spawn((
+                Text::new(CONTROLS_HINT),
+                TextFont {
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.8, 0.8, 0.8)),
+            ));
+        });
+}
+
+fn format_time(ms: f64) -> String {
+    let secs = (ms / 1000.0) as u64;
+    format!("{:02}:{:02}", secs / 60, secs % 60)
+}
+
+pub fn update_replay_hud(
+    playback: Res<ReplayPlayback>,
+    camera: Res<SpectatorCamera>,
+    game_data: Res<GameData>,
+    mut status_query: Query<&mut Text, With<ReplayStatusText>>,
+) {
+    let Ok(mut text) = status_query.single_mut() else {
+        return;
+    };
+
+    let state = if playback.paused { "PAUSED" } else { "PLAYING" };
+    **text = format!(
+        "{} {}x  {} / {}  |  {}",
+        state,
+        playback.speed,
+        format_time(playback.time_ms),
+        format_time(playback.duration_ms()),
+        camera.view_name(&game_data)
+    );
+}
@@ -0,0 +1,239 @@
+use bevy::prelude::*;
+use shared::{
+    MemoryNetwork, MemoryTransport, ReplayEntry, ReplayHeader, ReplayReader, ReplayRecord,
+    ServerMessage, Transport,
+};
+use std::fs::File;
+use std::io::{self, BufReader};
+use std::net::SocketAddr;
+use std::path::Path;
+
+use crate::components::network::{GameData, RemotePlayer};
+use crate::net::NetworkClient;
+
+const SPEEDS: [f32; 7] = [0.25, 0.5, 1.0, 2.0, 4.0, 8.0, 16.0];
+const SEEK_STEP_MS: f64 = 5000.0;
+const LONG_SEEK_STEP_MS: f64 = 30000.0; // With Shift held
+
+// A recorded match being played back. Recorded messages are handed to the
+// NetworkClient over an in-memory link, so the game handles them exactly as
+// if a server had just sent them.
+#[derive(Resource)]
+pub struct ReplayPlayback {
+    pub header: ReplayHeader,
+    entries: Vec<ReplayEntry>,
+    next: usize, // Index of the next entry to play
+    pub time_ms: f64,
+    pub speed: f32,
+    pub paused: bool,
+    feed: MemoryTransport,
+    client_addr: SocketAddr,
+}
+
+impl ReplayPlayback {
+    // Read a replay and wire up a NetworkClient that plays it instead of talking to a server
+    pub fn open(path: &Path) -> io::Result<(Self, NetworkClient)> {
+        let mut reader = ReplayReader::new(BufReader::new(File::open(path)?))?;
+        let mut entries = Vec::new();
+        while let Some(entry) = reader.next_entry()? {
+            entries.push(entry);
+        }
+
+        let network = MemoryNetwork::new();
+        let feed = network.endpoint();
+        let client = network.endpoint();
+        let client_addr = client.local_addr()?;
+        let playback = Self {
+            header: reader.header().clone(),
+            entries,
+            next: 0,
+            time_ms: 0.0,
+            speed: 1.0,
+            paused: false,
+            feed,
+            client_addr,
+        };
+        let feed_addr = playback.feed.local_addr()?;
+        let client = NetworkClient::new(Box::new(client), feed_addr, "Replay".to_string());
+        Ok((playback, client))
+    }
+
+    pub fn duration_ms(&self) -> f64 {
+        self.entries
+            .last()
+            .map_or(0.0, |entry| entry.time_ms as f64)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.next >= self.entries.len()
+    }
+
+    fn send(&self, msg: &ServerMessage) {
+        if let Ok(encoded) = bincode::serde::encode_to_vec(msg, bincode::config::standard()) {
+            let _ = self.feed.send_to(&encoded, self.client_addr);
+        }
+    }
+
+    // Build the recorded maze, as a server does right after a join
+    pub fn send_maze(&self) {
+        let maze = &self.header.maze;
+        self.send(&ServerMessage::GameStarted {
+            seed: maze.seed,
+            width: maze.width,
+            height: maze.height,
+            difficulty: maze.difficulty.clone(),
+        });
+    }
+
+    // Play every entry up to the given time. Keyframes are skipped, they only
+    // matter when seeking.
+    fn play_until(&mut self, time_ms: f64, game_data: &mut GameData) {
+        while let Some(entry) = self.entries.get(self.next)
+            && entry.time_ms as f64 <= time_ms
+        {
+            match &entry.record {
+                // Playback stays open at the end rather than quitting like a client would
+                ReplayRecord::Event(msg) if matches!(**msg, ServerMessage::GameEnded { .. }) => {}
+                ReplayRecord::Event(msg) => self.send(msg),
+                ReplayRecord::Snapshot { players } => {
+                    for snapshot in players {
+                        self.send(&ServerMessage::PlayerMoved {
+                            player_id: snapshot.player_id.clone(),
+                            position: snapshot.position,
+                            rotation: snapshot.rotation,
+                        });
+                        if let Some(player) = game_data.players.get_mut(&snapshot.player_id) {
+                            player.health = snapshot.health;
+                            player.armor = snapshot.armor;
+                        }
+                    }
+                }
+                ReplayRecord::Keyframe { .. } => {}
+            }
+            self.next += 1;
+        }
+    }
+
+    // Jump anywhere by rebuilding the match from the last keyframe before the
+    // target and fast-forwarding the rest of the way
+    fn seek(
+        &mut self,
+        target_ms: f64,
+        game_data: &mut GameData,
+        network: &NetworkClient,
+        commands: &mut Commands,
+        remote_players: &Query<Entity, With<RemotePlayer>>,
+    ) {
+        let target_ms = target_ms.clamp(0.0, self.duration_ms());
+
+        // Forget the present: messages not yet handled, players and all match state
+        while network.try_recv().is_some() {}
+        for entity in remote_players.iter() {
+            commands.entity(entity).despawn();
+        }
+        *game_data = GameData::default();
+
+        let keyframe = self.entries.iter().rposition(|entry| {
+            entry.time_ms as f64 <= target_ms
+                && matches!(entry.record, ReplayRecord::Keyframe { .. })
+        });
+        self.next = 0;
+        if let Some(index) = keyframe
+            && let ReplayRecord::Keyframe { messages } = &self.entries[index].record
+        {
+            for msg in messages {
+                self.send(msg);
+            }
+            self.next = index + 1;
+        }
+        self.play_until(target_ms, game_data);
+        self.time_ms = target_ms;
+    }
+}
+
+pub fn start_replay(playback: Res<ReplayPlayback>) {
+    let header = &playback.header;
+    println!(
+        "▶️ Playing {} replay on maze seed {}, {:.0}s long",
+        header.mode.name(),
+        header.maze.seed,
+        playback.duration_ms() / 1000.0
+    );
+    playback.send_maze();
+}
+
+pub fn advance_replay(
+    time: Res<Time>,
+    mut playback: ResMut<ReplayPlayback>,
+    mut game_data: ResMut<GameData>,
+) {
+    if playback.paused {
+        return;
+    }
+
+    let time_ms = (playback.time_ms + time.delta_secs_f64() * 1000.0 * playback.speed as f64)
+        .min(playback.duration_ms());
+    playback.play_until(time_ms, &mut game_data);
+    playback.time_ms = time_ms;
+    if playback.is_finished() {
+        playback.paused = true;
+    }
+}
+
+// Space pauses, arrows seek and change speed, Home restarts
+pub fn replay_controls(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut playback: ResMut<ReplayPlayback>,
+    mut game_data: ResMut<GameData>,
+    network: Res<NetworkClient>,
+    mut commands: Commands,
+    remote_players: Query<Entity, With<RemotePlayer>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::Space) {
+        if playback.is_finished() {
+            playback.seek(
+                0.0,
+                &mut game_data,
+                &network,
+                &mut commands,
+                &remote_players,
+            );
+        }
+        playback.paused = !playback.paused;
+    }
+
+    let speed_index = SPEEDS
+        .iter()
+        .position(|&speed| speed == playback.speed)
+        .unwrap_or(2);
+    if keyboard_input.just_pressed(KeyCode::ArrowUp) {
+        playback.speed = SPEEDS[(speed_index + 1).min(SPEEDS.len() - 1)];
+    }
+    if keyboard_input.just_pressed(KeyCode::ArrowDown) {
+        playback.speed = SPEEDS[speed_index.saturating_sub(1)];
+    }
+
+    let step = if keyboard_input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]) {
+        LONG_SEEK_STEP_MS
+    } else {
+        SEEK_STEP_MS
+    };
+    let target_ms = if keyboard_input.just_pressed(KeyCode::ArrowLeft) {
+        Some(playback.time_ms - step)
+    } else if keyboard_input.just_pressed(KeyCode::ArrowRight) {
+        Some(playback.time_ms + step)
+    } else if keyboard_input.just_pressed(KeyCode::Home) {
+        Some(0.0)
+    } else {
+        None
+    };
+    if let Some(target_ms) = target_ms {
+        playback.seek(
+            target_ms,
+            &mut game_data,
+            &network,
+            &mut commands,
+            &remote_players,
+        );
+    }
+}
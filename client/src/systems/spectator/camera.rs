@@ -0,0 +1,169 @@
+use bevy::{input::mouse::MouseMotion, prelude::*};
+
+use crate::components::{
+    network::GameData,
+    player::{CameraController, FollowCamera},
+};
+
+const FREE_CAMERA_SPEED: f32 = 20.0;
+const FAST_CAMERA_SPEED: f32 = 60.0; // With Shift held
+const EYE_HEIGHT: f32 = 1.0; // Above a player's position, where the game camera sits
+const FOLLOW_SMOOTHING: f32 = 15.0; // Eases the camera between 20 Hz updates
+
+// Where the camera is when watching rather than playing, live or in a replay
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum CameraView {
+    #[default]
+    FreeCamera,
+    Following(String), // First person with this player
+}
+
+#[derive(Resource, Default)]
+pub struct SpectatorCamera {
+    pub view: CameraView,
+}
+
+impl SpectatorCamera {
+    // Who the camera rides along with, for HUDs
+    pub fn view_name(&self, game_data: &GameData) -> String {
+        match &self.view {
+            CameraView::FreeCamera => "Free camera".to_string(),
+            CameraView::Following(id) => game_data
+                .players
+                .get(id)
+                .map_or("Unknown".to_string(), |p| p.name.clone()),
+        }
+    }
+}
+
+// Clicking rides along with the next (left) or previous (right) living player,
+// F goes back to the free camera. Tab is left for the scoreboard.
+pub fn switch_camera_view(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    mut camera: ResMut<SpectatorCamera>,
+    game_data: Res<GameData>,
+) {
+    if keyboard_input.just_pressed(KeyCode::KeyF) {
+        camera.view = CameraView::FreeCamera;
+    }
+
+    let forward = mouse_input.just_pressed(MouseButton::Left);
+    if forward || mouse_input.just_pressed(MouseButton::Right) {
+        let mut players: Vec<_> = game_data.players.values().filter(|p| p.is_alive).collect();
+        players.sort_by(|a, b| a.name.cmp(&b.name));
+        let current = match &camera.view {
+            CameraView::Following(id) => players.iter().position(|p| &p.id == id),
+            CameraView::FreeCamera => None,
+        };
+        // Cycling past either end passes through the free camera
+        let index = match (current, forward) {
+            (Some(index), true) => Some(index + 1),
+            (None, true) => Some(0),
+            (Some(index), false) => index.checked_sub(1),
+            (None, false) => players.len().checked_sub(1),
+        };
+        camera.view = index
+            .and_then(|index| players.get(index))
+            .map_or(CameraView::FreeCamera, |p| {
+                CameraView::Following(p.id.clone())
+            });
+    }
+
+    // The player we were watching left
+    if let CameraView::Following(id) = &camera.view
+        && !game_data.players.contains_key(id)
+    {
+        camera.view = CameraView::FreeCamera;
+    }
+}
+
+// Fly anywhere: mouse to look, WASD to move, E and Q to rise and sink
+pub fn free_camera(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut motion_evr: EventReader<MouseMotion>,
+    time: Res<Time>,
+    camera: Res<SpectatorCamera>,
+    mut camera_query: Query<(&mut Transform, &mut CameraController), With<FollowCamera>>,
+) {
+    let delta: Vec2 = motion_evr.read().map(|ev| ev.delta).sum();
+    if camera.view != CameraView::FreeCamera {
+        return;
+    }
+    let Ok((mut transform, mut controller)) = camera_query.single_mut() else {
+        return;
+    };
+
+    controller.yaw -= delta.x * controller.sensitivity;
+    controller.pitch = (controller.pitch - delta.y * controller.sensitivity).clamp(-89.0, 89.0);
+    transform.rotation = Quat::from_euler(
+        EulerRot::YXZ,
+        controller.yaw.to_radians(),
+        controller.pitch.to_radians(),
+        0.0,
+    );
+
+    let mut direction = Vec3::ZERO;
+    if keyboard_input.pressed(KeyCode::KeyW) {
+        direction += *transform.forward();
+    }
+    if keyboard_input.pressed(KeyCode::KeyS) {
+        direction -= *transform.forward();
+    }
+    if keyboard_input.pressed(KeyCode::KeyA) {
+        direction -= *transform.right();
+    }
+    if keyboard_input.pressed(KeyCode::KeyD) {
+        direction += *transform.right();
+    }
+    if keyboard_input.pressed(KeyCode::KeyE) {
+        direction += Vec3::Y;
+    }
+    if keyboard_input.pressed(KeyCode::KeyQ) {
+        direction -= Vec3::Y;
+    }
+
+    let speed = if keyboard_input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]) {
+        FAST_CAMERA_SPEED
+    } else {
+        FREE_CAMERA_SPEED
+    };
+    transform.translation += direction.normalize_or_zero() * speed * time.delta_secs();
+}
+
+// Where a player's eyes are and which way they look, from their networked transform
+pub fn eye_pose(position: Vec3, rotation: Quat) -> (Vec3, Quat) {
+    // Clients send their rotation with the model's quarter turn included
+    let tank_correction = Quat::from_axis_angle(Vec3::Y, 90.0_f32.to_radians());
+    (
+        position + Vec3::Y * EYE_HEIGHT,
+        rotation * tank_correction.inverse(),
+    )
+}
+
+// See through the eyes of the player being watched
+pub fn follow_watched_player(
+    time: Res<Time>,
+    camera: Res<SpectatorCamera>,
+    game_data: Res<GameData>,
+    mut camera_query: Query<(&mut Transform, &mut CameraController), With<FollowCamera>>,
+) {
+    let CameraView::Following(id) = &camera.view else {
+        return;
+    };
+    let (Some(player), Ok((mut transform, mut controller))) =
+        (game_data.players.get(id), camera_query.single_mut())
+    else {
+        return;
+    };
+
+    let (eye, rotation) = eye_pose(player.position, player.rotation);
+    let blend = 1.0 - (-FOLLOW_SMOOTHING * time.delta_secs()).exp();
+    transform.translation = transform.translation.lerp(eye, blend);
+    transform.rotation = transform.rotation.slerp(rotation, blend);
+
+    // Leaving the free camera later starts from where this view looked
+    let (yaw, pitch, _) = transform.rotation.to_euler(EulerRot::YXZ);
+    controller.yaw = yaw.to_degrees();
+    controller.pitch = pitch.to_degrees();
+}
@@ -0,0 +1,62 @@
+use bevy::prelude::*;
+
+use super::camera::SpectatorCamera;
+use crate::components::network::GameData;
+
+const CONTROLS_HINT: &str =
+    "Click next/previous player | F free camera | Mouse, WASD, E and Q to fly | Tab scores";
+
+#[derive(Component)]
+pub struct SpectatorStatusText;
+
+pub fn setup_spectator_hud(mut commands: Commands) {
+    // Who we're watching, along the bottom of the screen
+    commands
+        .spawn(Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(20.0),
+            width: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 24.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+                SpectatorStatusText,
+            ));
+            parent.spawn((
+                Text::new(CONTROLS_HINT),
+                TextFont {
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.8, 0.8, 0.8)),
+            ));
+        });
+}
+
+pub fn update_spectator_hud(
+    camera: Res<SpectatorCamera>,
+    game_data: Res<GameData>,
+    mut status_query: Query<&mut Text, With<SpectatorStatusText>>,
+) {
+    let Ok(mut text) = status_query.single_mut() else {
+        return;
+    };
+
+    let status = if game_data.spectating {
+        format!("SPECTATING  |  {}", camera.view_name(&game_data))
+    } else {
+        "Joining as a spectator...".to_string()
+    };
+    if text.0 != status {
+        text.0 = status;
+    }
+}
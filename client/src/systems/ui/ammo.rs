@@ -0,0 +1,106 @@
+use crate::components::network::GameData;
+use bevy::prelude::*;
+use shared::weapons;
+
+#[derive(Component)]
+pub struct AmmoDisplay;
+
+#[derive(Component)]
+pub struct AmmoText;
+
+#[derive(Component)]
+pub struct ReloadText;
+
+pub fn setup_ammo_display(mut commands: Commands) {
+    // Weapon name and rounds left in the bottom-left corner
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(20.0),
+                left: Val::Px(20.0),
+                padding: UiRect::all(Val::Px(10.0)),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(4.0),
+                display: Display::None,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+            AmmoDisplay,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 24.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                AmmoText,
+            ));
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(1.0, 0.8, 0.2)),
+                ReloadText,
+            ));
+        });
+}
+
+pub fn update_ammo_display(
+    time: Res<Time>,
+    mut game_data: ResMut<GameData>,
+    mut root_query: Query<&mut Node, With<AmmoDisplay>>,
+    mut ammo_query: Query<(&mut Text, &mut TextColor), (With<AmmoText>, Without<ReloadText>)>,
+    mut reload_query: Query<&mut Text, (With<ReloadText>, Without<AmmoText>)>,
+) {
+    // Count the reload down locally until the server confirms it
+    if let Some(remaining) = game_data
+        .ammo
+        .as_mut()
+        .and_then(|status| status.reload_remaining.as_mut())
+    {
+        *remaining = (*remaining - time.delta_secs()).max(0.0);
+    }
+
+    let Ok(mut root) = root_query.single_mut() else {
+        return;
+    };
+    let Some(status) = &game_data.ammo else {
+        root.display = Display::None;
+        return;
+    };
+    root.display = Display::Flex;
+
+    let ammo_text = format!(
+        "{}  {} / {}",
+        weapons().get(status.weapon).name,
+        status.ammo.magazine,
+        status.ammo.reserve
+    );
+    if let Ok((mut text, mut color)) = ammo_query.single_mut() {
+        if text.0 != ammo_text {
+            text.0 = ammo_text;
+        }
+        color.0 = if status.ammo.magazine == 0 {
+            Color::srgb(1.0, 0.3, 0.3)
+        } else {
+            Color::WHITE
+        };
+    }
+
+    let reload_text = match status.reload_remaining {
+        Some(secs) => format!("RELOADING {:.1}s", secs),
+        None if status.ammo.magazine == 0 && status.ammo.reserve == 0 => "OUT OF AMMO".to_string(),
+        None if status.ammo.magazine == 0 => "Press R to reload".to_string(),
+        None => String::new(),
+    };
+    if let Ok(mut text) = reload_query.single_mut()
+        && text.0 != reload_text
+    {
+        text.0 = reload_text;
+    }
+}
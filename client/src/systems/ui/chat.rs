@@ -0,0 +1,268 @@
+use crate::components::network::GameData;
+use crate::net::NetworkClient;
+use bevy::input::ButtonState;
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::prelude::*;
+use shared::{ChatChannel, MAX_CHAT_LENGTH};
+
+const CHAT_SECS: f32 = 10.0; // How long a message stays up while not typing
+const FADE_SECS: f32 = 1.0;
+const MAX_CHAT_ENTRIES: usize = 8;
+
+// What we're typing, if the chat box is open
+#[derive(Resource)]
+pub struct ChatInput {
+    pub open: bool,
+    pub channel: ChatChannel,
+    pub text: String,
+}
+
+impl Default for ChatInput {
+    fn default() -> Self {
+        Self {
+            open: false,
+            channel: ChatChannel::All,
+            text: String::new(),
+        }
+    }
+}
+
+// Run condition for gameplay input, which waits while we type
+pub fn chat_closed(chat: Res<ChatInput>) -> bool {
+    !chat.open
+}
+
+#[derive(Component)]
+pub struct ChatHistory;
+
+#[derive(Component)]
+pub struct ChatEntry {
+    pub timer: Timer,
+}
+
+#[derive(Component)]
+pub struct ChatInputBox;
+
+#[derive(Component)]
+pub struct ChatInputText;
+
+fn channel_tag(channel: ChatChannel) -> &'static str {
+    match channel {
+        ChatChannel::All => "[ALL]",
+        ChatChannel::Team => "[TEAM]",
+    }
+}
+
+pub fn setup_chat(mut commands: Commands) {
+    // Recent messages above the ammo display, with the input box underneath
+    commands
+        .spawn(Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(120.0),
+            left: Val::Px(20.0),
+            width: Val::Px(440.0),
+            flex_direction: FlexDirection::Column,
+            row_gap: Val::Px(6.0),
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn((
+                Node {
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(2.0),
+                    ..default()
+                },
+                ChatHistory,
+            ));
+            parent
+                .spawn((
+                    Node {
+                        padding: UiRect::all(Val::Px(6.0)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+                    Visibility::Hidden,
+                    ChatInputBox,
+                ))
+                .with_children(|input| {
+                    input.spawn((
+                        Text::new(""),
+                        TextFont {
+                            font_size: 18.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                        ChatInputText,
+                    ));
+                });
+        });
+}
+
+// Enter opens the chat box for everyone and T for the team; Enter sends, Escape cancels
+pub fn chat_input(
+    mut key_events: EventReader<KeyboardInput>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut chat: ResMut<ChatInput>,
+    game_data: Res<GameData>,
+    network: Res<NetworkClient>,
+) {
+    if !chat.open {
+        // Keys pressed while playing aren't typed, including the one that opens the box
+        key_events.clear();
+        let has_team =
+            game_data.spectating || game_data.my_player().is_some_and(|p| p.team.is_some());
+        let channel = if keyboard_input.just_pressed(KeyCode::Enter) {
+            ChatChannel::All
+        } else if keyboard_input.just_pressed(KeyCode::KeyT) && has_team {
+            ChatChannel::Team
+        } else {
+            return;
+        };
+        *chat = ChatInput {
+            open: true,
+            channel,
+            text: String::new(),
+        };
+        return;
+    }
+
+    for event in key_events.read() {
+        if event.state != ButtonState::Pressed {
+            continue;
+        }
+        let typed = match &event.logical_key {
+            Key::Enter => {
+                let text = chat.text.trim();
+                if !text.is_empty() {
+                    network.send_chat(chat.channel, text.to_string());
+                }
+                chat.open = false;
+                break;
+            }
+            Key::Escape => {
+                chat.open = false;
+                break;
+            }
+            Key::Backspace => {
+                chat.text.pop();
+                continue;
+            }
+            Key::Space => " ",
+            Key::Character(typed) => typed.as_str(),
+            _ => continue,
+        };
+        for ch in typed.chars().filter(|ch| !ch.is_control()) {
+            if chat.text.chars().count() < MAX_CHAT_LENGTH {
+                chat.text.push(ch);
+            }
+        }
+    }
+}
+
+// Add a received message to the history, the sender's name in their color
+pub fn show_chat(
+    commands: &mut Commands,
+    history_query: &Query<Entity, With<ChatHistory>>,
+    game_data: &GameData,
+    sender_id: Option<&str>,
+    sender_name: &str,
+    channel: ChatChannel,
+    text: &str,
+) {
+    let Ok(history) = history_query.single() else {
+        return;
+    };
+    // Spectators have no player color
+    let name_color = sender_id
+        .and_then(|id| game_data.players.get(id))
+        .map_or(Color::srgb(0.7, 0.7, 0.7), |p| {
+            Color::srgb(p.color[0], p.color[1], p.color[2])
+        });
+    let font = TextFont {
+        font_size: 18.0,
+        ..default()
+    };
+
+    commands.entity(history).with_children(|parent| {
+        parent
+            .spawn((
+                Text::new(format!("{} ", channel_tag(channel))),
+                font.clone(),
+                TextColor(Color::srgb(0.6, 0.6, 0.6)),
+                BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.5)),
+                ChatEntry {
+                    timer: Timer::from_seconds(CHAT_SECS, TimerMode::Once),
+                },
+            ))
+            .with_children(|entry| {
+                entry.spawn((
+                    TextSpan::new(sender_name.to_string()),
+                    font.clone(),
+                    TextColor(name_color),
+                ));
+                entry.spawn((
+                    TextSpan::new(format!(": {}", text)),
+                    font.clone(),
+                    TextColor(Color::WHITE),
+                ));
+            });
+    });
+}
+
+pub fn update_chat(
+    mut commands: Commands,
+    time: Res<Time>,
+    chat: Res<ChatInput>,
+    mut entry_query: Query<(
+        Entity,
+        &mut ChatEntry,
+        &Children,
+        &mut TextColor,
+        &mut BackgroundColor,
+    )>,
+    mut span_query: Query<&mut TextColor, (With<TextSpan>, Without<ChatEntry>)>,
+    mut input_box_query: Query<&mut Visibility, With<ChatInputBox>>,
+    mut input_text_query: Query<&mut Text, With<ChatInputText>>,
+) {
+    // Only the most recent messages are kept
+    let excess = entry_query.iter().count().saturating_sub(MAX_CHAT_ENTRIES);
+    let mut oldest: Vec<(Entity, f32)> = entry_query
+        .iter()
+        .map(|(entity, entry, ..)| (entity, entry.timer.elapsed_secs()))
+        .collect();
+    oldest.sort_by(|a, b| b.1.total_cmp(&a.1));
+    for (entity, _) in oldest.into_iter().take(excess) {
+        commands.entity(entity).despawn();
+    }
+
+    // Messages fade away, but the whole history comes back while typing
+    for (_, mut entry, children, mut tag_color, mut background) in entry_query.iter_mut() {
+        entry.timer.tick(time.delta());
+        let alpha = if chat.open {
+            1.0
+        } else {
+            (entry.timer.remaining_secs() / FADE_SECS).min(1.0)
+        };
+        tag_color.0.set_alpha(alpha);
+        background.0.set_alpha(0.5 * alpha);
+        for child in children.iter() {
+            if let Ok(mut color) = span_query.get_mut(child) {
+                color.0.set_alpha(alpha);
+            }
+        }
+    }
+
+    if let Ok(mut visibility) = input_box_query.single_mut() {
+        *visibility = if chat.open {
+            Visibility::Visible
+        } else {
+            Visibility::Hidden
+        };
+    }
+    if let Ok(mut text) = input_text_query.single_mut() {
+        let typed = format!("{} {}_", channel_tag(chat.channel), chat.text);
+        if text.0 != typed {
+            text.0 = typed;
+        }
+    }
+}
@@ -0,0 +1,222 @@
+use crate::components::player::FollowCamera;
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use std::collections::{HashMap, HashSet};
+use std::f32::consts::FRAC_PI_4;
+
+use super::death_screen::DeathState;
+
+const HIT_MARKER_SECS: f32 = 0.2;
+const KILL_MARKER_SECS: f32 = 0.5;
+const KILL_MARKER_SCALE: f32 = 1.6;
+const ARC_SECS: f32 = 1.5;
+const ARC_RADIUS: f32 = 0.45; // Share of the shorter window side, so arcs hug the edge
+const ARC_SEGMENTS: usize = 7;
+const ARC_SEGMENT_ANGLE: f32 = 0.07; // Radians each segment covers
+const ARC_THICKNESS: f32 = 6.0;
+
+// Feedback from PlayerDamaged: hit markers for our shots, arcs toward whoever hurt us
+#[derive(Resource, Default)]
+pub struct DamageIndicators {
+    hit_marker: Option<(Timer, bool)>,    // Fades out, true for a kill
+    arcs: HashMap<String, (Vec3, Timer)>, // Attacker's position, one arc per attacker
+}
+
+impl DamageIndicators {
+    pub fn hit(&mut self, kill: bool) {
+        // A kill marker isn't cut short by the next hit
+        if let Some((timer, true)) = &self.hit_marker
+            && !kill
+            && !timer.finished()
+        {
+            return;
+        }
+        let secs = if kill {
+            KILL_MARKER_SECS
+        } else {
+            HIT_MARKER_SECS
+        };
+        self.hit_marker = Some((Timer::from_seconds(secs, TimerMode::Once), kill));
+    }
+
+    pub fn damaged_by(&mut self, attacker_id: String, position: Vec3) {
+        self.arcs.insert(
+            attacker_id,
+            (position, Timer::from_seconds(ARC_SECS, TimerMode::Once)),
+        );
+    }
+}
+
+#[derive(Component)]
+pub struct HitMarkerLine;
+
+#[derive(Component)]
+pub struct DamageArcs;
+
+#[derive(Component)]
+pub struct DamageArcSegment {
+    attacker_id: String,
+    index: usize,
+}
+
+pub fn setup_damage_indicators(mut commands: Commands) {
+    // Hit marker, an X around the crosshairs
+    commands
+        .spawn(Node {
+            position_type: PositionType::Absolute,
+            left: Val::Percent(50.0),
+            top: Val::Percent(60.0),
+            width: Val::Px(40.0),
+            height: Val::Px(40.0),
+            margin: UiRect {
+                left: Val::Px(-20.0),
+                top: Val::Px(-20.0),
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            for (x, y) in [(-1.0, -1.0), (1.0, -1.0), (-1.0, 1.0), (1.0, 1.0)] {
+                parent.spawn((
+                    Node {
+                        position_type: PositionType::Absolute,
+                        left: Val::Px(20.0 + x * 9.0 - 5.0),
+                        top: Val::Px(20.0 + y * 9.0 - 1.0),
+                        width: Val::Px(10.0),
+                        height: Val::Px(2.0),
+                        ..default()
+                    },
+                    // Point each line at the center
+                    Transform::from_rotation(Quat::from_rotation_z(x * y * FRAC_PI_4)),
+                    BackgroundColor(Color::WHITE),
+                    Visibility::Hidden,
+                    HitMarkerLine,
+                ));
+            }
+        });
+
+    // Damage direction arcs are laid out around the screen center
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            ..default()
+        },
+        DamageArcs,
+    ));
+}
+
+pub fn update_damage_indicators(
+    mut commands: Commands,
+    time: Res<Time>,
+    death_state: Res<DeathState>,
+    mut indicators: ResMut<DamageIndicators>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<&Transform, (With<FollowCamera>, Without<HitMarkerLine>)>,
+    mut marker_query: Query<
+        (&mut Transform, &mut BackgroundColor, &mut Visibility),
+        (With<HitMarkerLine>, Without<DamageArcSegment>),
+    >,
+    arcs_query: Query<Entity, With<DamageArcs>>,
+    mut segment_query: Query<
+        (
+            Entity,
+            &DamageArcSegment,
+            &mut Node,
+            &mut Transform,
+            &mut BackgroundColor,
+        ),
+        Without<HitMarkerLine>,
+    >,
+) {
+    let delta = time.delta();
+
+    // Hit marker: white for a hit, bigger and red for a kill, fading out
+    if let Some((timer, _)) = indicators.hit_marker.as_mut() {
+        timer.tick(delta);
+    }
+    let marker = indicators
+        .hit_marker
+        .as_ref()
+        .filter(|(timer, _)| !timer.finished());
+    for (mut transform, mut color, mut visibility) in marker_query.iter_mut() {
+        let Some((timer, kill)) = marker else {
+            *visibility = Visibility::Hidden;
+            continue;
+        };
+        *visibility = Visibility::Visible;
+        let alpha = timer.fraction_remaining();
+        color.0 = if *kill {
+            Color::srgba(1.0, 0.2, 0.2, alpha)
+        } else {
+            Color::srgba(1.0, 1.0, 1.0, alpha)
+        };
+        transform.scale = Vec3::splat(if *kill { KILL_MARKER_SCALE } else { 1.0 });
+    }
+
+    // Arcs fade out, and nobody is hurting us while we're dead
+    if death_state.is_dead {
+        indicators.arcs.clear();
+    }
+    indicators.arcs.retain(|_, (_, timer)| {
+        timer.tick(delta);
+        !timer.finished()
+    });
+
+    let mut shown = HashSet::new();
+    for (entity, segment, ..) in segment_query.iter() {
+        if indicators.arcs.contains_key(&segment.attacker_id) {
+            shown.insert(segment.attacker_id.clone());
+        } else {
+            commands.entity(entity).despawn();
+        }
+    }
+    if let Ok(arcs) = arcs_query.single() {
+        for attacker_id in indicators.arcs.keys().filter(|id| !shown.contains(*id)) {
+            commands.entity(arcs).with_children(|parent| {
+                for index in 0..ARC_SEGMENTS {
+                    parent.spawn((
+                        Node {
+                            position_type: PositionType::Absolute,
+                            ..default()
+                        },
+                        BackgroundColor(Color::NONE),
+                        DamageArcSegment {
+                            attacker_id: attacker_id.clone(),
+                            index,
+                        },
+                    ));
+                }
+            });
+        }
+    }
+
+    let (Ok(window), Ok(camera)) = (windows.single(), camera_query.single()) else {
+        return;
+    };
+    let center = Vec2::new(window.width(), window.height()) / 2.0;
+    let radius = window.width().min(window.height()) * ARC_RADIUS;
+    let forward = camera.forward().with_y(0.0).normalize_or_zero();
+    let right = camera.right().with_y(0.0).normalize_or_zero();
+    let length = radius * ARC_SEGMENT_ANGLE + 1.0; // A little overlap hides the joins
+
+    for (_, segment, mut node, mut transform, mut color) in segment_query.iter_mut() {
+        let Some((source, timer)) = indicators.arcs.get(&segment.attacker_id) else {
+            continue;
+        };
+        // Clockwise from the top of the screen, straight ahead being up
+        let to_source = *source - camera.translation;
+        let bearing = to_source.dot(right).atan2(to_source.dot(forward));
+        let angle =
+            bearing + (segment.index as f32 - (ARC_SEGMENTS - 1) as f32 / 2.0) * ARC_SEGMENT_ANGLE;
+        let position = center + Vec2::new(angle.sin(), -angle.cos()) * radius;
+
+        node.left = Val::Px(position.x - length / 2.0);
+        node.top = Val::Px(position.y - ARC_THICKNESS / 2.0);
+        node.width = Val::Px(length);
+        node.height = Val::Px(ARC_THICKNESS);
+        transform.rotation = Quat::from_rotation_z(angle);
+        color.0 = Color::srgba(1.0, 0.1, 0.1, 0.8 * timer.fraction_remaining());
+    }
+}
@@ -1,6 +1,7 @@
 use crate::components::network::GameData;
 use crate::net::NetworkClient;
 use bevy::prelude::*;
+use shared::{GameMode, GameState};
 
 #[derive(Resource, Default)]
 pub struct DeathState {
@@ -26,6 +27,9 @@ pub struct DeathScreenUI;
 #[derive(Component)]
 pub struct DeathText;
 
+#[derive(Component)]
+pub struct DeathHintText;
+
 #[derive(Component)]
 pub struct DamageOverlay;
 
@@ -72,6 +76,7 @@ pub fn setup_death_screen(mut commands: Commands) {
                     top: Val::Px(100.0),
                     ..default()
                 },
+                DeathHintText,
             ));
         });
 
@@ -94,14 +99,33 @@ pub fn setup_death_screen(mut commands: Commands) {
 
 pub fn handle_death_screen(
     death_state: Res<DeathState>,
-    mut death_screen_query: Query<&mut Visibility, With<DeathScreenUI>>,
+    game_data: Res<GameData>,
+    mut death_screen_query: Query<(&mut Visibility, &mut BackgroundColor), With<DeathScreenUI>>,
+    mut hint_query: Query<&mut Text, With<DeathHintText>>,
 ) {
-    for mut visibility in death_screen_query.iter_mut() {
+    // Eliminated players watch the rest of the round through a lighter overlay
+    let eliminated = game_data.mode == Some(GameMode::Elimination)
+        && game_data.state == Some(GameState::GameStarted);
+
+    for (mut visibility, mut background) in death_screen_query.iter_mut() {
         *visibility = if death_state.is_dead {
             Visibility::Visible
         } else {
             Visibility::Hidden
         };
+        background.0 = Color::srgba(0.0, 0.0, 0.0, if eliminated { 0.35 } else { 0.8 });
+    }
+
+    let message = if eliminated {
+        let alive = game_data.players.values().filter(|p| p.is_alive).count();
+        format!("Eliminated - spectating, {} players remaining", alive)
+    } else {
+        "Press R to respawn".to_string()
+    };
+    for mut hint in hint_query.iter_mut() {
+        if hint.0 != message {
+            hint.0 = message.clone();
+        }
     }
 }
 
@@ -130,10 +154,10 @@ pub fn handle_damage_overlay(
 
 pub fn update_death_state(game_data: Res<GameData>, mut death_state: ResMut<DeathState>) {
     // Update death state based on local player status
-    if let Some(my_id) = &game_data.my_id {
-        if let Some(my_player) = game_data.players.get(my_id) {
-            death_state.is_dead = !my_player.is_alive;
-        }
+    if let Some(my_id) = &game_data.my_id
+        && let Some(my_player) = game_data.players.get(my_id)
+    {
+        death_state.is_dead = !my_player.is_alive;
     }
 }
 
@@ -0,0 +1,110 @@
+use bevy::input::mouse::{MouseScrollUnit, MouseWheel};
+use bevy::prelude::*;
+use std::collections::VecDeque;
+
+use super::chat::ChatInput;
+
+const MAX_LOG_ENTRIES: usize = 100;
+const LINE_HEIGHT: f32 = 20.0; // Pixels scrolled per line of mouse wheel
+
+// Joins, leaves, kills and streaks, oldest first
+#[derive(Resource, Default)]
+pub struct EventLog {
+    pub entries: VecDeque<String>,
+    pub open: bool,
+    changed: bool,
+}
+
+impl EventLog {
+    pub fn push(&mut self, entry: String) {
+        if self.entries.len() == MAX_LOG_ENTRIES {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(entry);
+        self.changed = true;
+    }
+}
+
+#[derive(Component)]
+pub struct EventLogPanel;
+
+#[derive(Component)]
+pub struct EventLogText;
+
+pub fn setup_event_log(mut commands: Commands) {
+    // Scrollable panel down the right side, under the kill feed, toggled with L
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(200.0),
+                right: Val::Px(20.0),
+                width: Val::Px(340.0),
+                max_height: Val::Px(300.0),
+                padding: UiRect::all(Val::Px(10.0)),
+                overflow: Overflow::scroll_y(),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+            ScrollPosition::default(),
+            Visibility::Hidden,
+            EventLogPanel,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                EventLogText,
+            ));
+        });
+}
+
+pub fn update_event_log(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    chat: Res<ChatInput>,
+    mut mouse_wheel: EventReader<MouseWheel>,
+    mut event_log: ResMut<EventLog>,
+    mut panel_query: Query<(&mut Visibility, &mut ScrollPosition), With<EventLogPanel>>,
+    mut text_query: Query<&mut Text, With<EventLogText>>,
+) {
+    let (Ok((mut visibility, mut scroll)), Ok(mut text)) =
+        (panel_query.single_mut(), text_query.single_mut())
+    else {
+        return;
+    };
+
+    if !chat.open && keyboard_input.just_pressed(KeyCode::KeyL) {
+        event_log.open = !event_log.open;
+    }
+    *visibility = if event_log.open {
+        Visibility::Visible
+    } else {
+        Visibility::Hidden
+    };
+
+    // New entries jump to the bottom, the wheel scrolls back through older ones
+    if event_log.changed {
+        event_log.changed = false;
+        text.0 = event_log
+            .entries
+            .iter()
+            .cloned()
+            .collect::<Vec<_>>()
+            .join("\n");
+        scroll.offset_y = f32::MAX;
+    }
+    for event in mouse_wheel.read() {
+        if !event_log.open {
+            continue;
+        }
+        let lines = match event.unit {
+            MouseScrollUnit::Line => event.y * LINE_HEIGHT,
+            MouseScrollUnit::Pixel => event.y,
+        };
+        scroll.offset_y = (scroll.offset_y - lines).max(0.0);
+    }
+}
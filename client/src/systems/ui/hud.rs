@@ -0,0 +1,270 @@
+use crate::components::network::GameData;
+use crate::plugins::network::LocalPlayerResource;
+use bevy::prelude::*;
+use shared::{GameMode, MAX_ARMOR, Player};
+
+const BAR_WIDTH: f32 = 260.0;
+const BAR_HEIGHT: f32 = 14.0;
+
+#[derive(Component)]
+pub struct Hud;
+
+#[derive(Component)]
+pub struct HudScoreText;
+
+#[derive(Component)]
+pub struct HealthFill;
+
+#[derive(Component)]
+pub struct HealthText;
+
+#[derive(Component)]
+pub struct ArmorRow;
+
+#[derive(Component)]
+pub struct ArmorFill;
+
+#[derive(Component)]
+pub struct ArmorText;
+
+pub fn setup_hud(mut commands: Commands) {
+    // Score, rank, health and armor centered along the bottom edge
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(20.0),
+                width: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                display: Display::None,
+                ..default()
+            },
+            Hud,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    Node {
+                        padding: UiRect::all(Val::Px(10.0)),
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        row_gap: Val::Px(6.0),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+                ))
+                .with_children(|panel| {
+                    panel.spawn((
+                        Text::new(""),
+                        TextFont {
+                            font_size: 18.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                        HudScoreText,
+                    ));
+                    spawn_bar(panel, (), "HP", Color::WHITE, HealthFill, HealthText);
+                    spawn_bar(
+                        panel,
+                        ArmorRow,
+                        "AR",
+                        Color::srgb(0.3, 0.6, 1.0),
+                        ArmorFill,
+                        ArmorText,
+                    );
+                });
+        });
+}
+
+// A labelled bar row: "HP [#####     ] 85"
+fn spawn_bar(
+    parent: &mut ChildSpawnerCommands,
+    row: impl Bundle,
+    label: &str,
+    color: Color,
+    fill: impl Component,
+    value: impl Component,
+) {
+    parent
+        .spawn((
+            Node {
+                column_gap: Val::Px(8.0),
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            row,
+        ))
+        .with_children(|row| {
+            row.spawn((
+                Text::new(label),
+                TextFont {
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.8, 0.8, 0.8)),
+            ));
+            row.spawn((
+                Node {
+                    width: Val::Px(BAR_WIDTH),
+                    height: Val::Px(BAR_HEIGHT),
+                    ..default()
+                },
+                BackgroundColor(Color::srgba(1.0, 1.0, 1.0, 0.15)),
+            ))
+            .with_children(|bar| {
+                bar.spawn((
+                    Node {
+                        width: Val::Percent(100.0),
+                        height: Val::Percent(100.0),
+                        ..default()
+                    },
+                    BackgroundColor(color),
+                    fill,
+                ));
+            });
+            row.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 18.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                Node {
+                    width: Val::Px(36.0),
+                    ..default()
+                },
+                value,
+            ));
+        });
+}
+
+pub fn update_hud(
+    game_data: Res<GameData>,
+    local_player: Res<LocalPlayerResource>,
+    mut hud_query: Query<&mut Node, (With<Hud>, Without<ArmorRow>)>,
+    mut score_query: Query<&mut Text, With<HudScoreText>>,
+    mut health_fill_query: Query<
+        (&mut Node, &mut BackgroundColor),
+        (With<HealthFill>, Without<Hud>, Without<ArmorRow>),
+    >,
+    mut health_text_query: Query<&mut Text, (With<HealthText>, Without<HudScoreText>)>,
+    mut armor_row_query: Query<&mut Node, (With<ArmorRow>, Without<Hud>)>,
+    mut armor_fill_query: Query<
+        &mut Node,
+        (
+            With<ArmorFill>,
+            Without<Hud>,
+            Without<ArmorRow>,
+            Without<HealthFill>,
+        ),
+    >,
+    mut armor_text_query: Query<
+        &mut Text,
+        (With<ArmorText>, Without<HudScoreText>, Without<HealthText>),
+    >,
+) {
+    let Ok(mut hud) = hud_query.single_mut() else {
+        return;
+    };
+
+    // Only while we have a living player of our own; the death screen covers the rest
+    let me = game_data
+        .my_player()
+        .filter(|me| local_player.entity.is_some() && me.is_alive);
+    let Some(me) = me else {
+        hud.display = Display::None;
+        return;
+    };
+    hud.display = Display::Flex;
+
+    let health = me.health.max(0.0);
+    let fraction = (health / me.max_health).clamp(0.0, 1.0);
+    if let Ok((mut node, mut color)) = health_fill_query.single_mut() {
+        node.width = Val::Percent(fraction * 100.0);
+        color.0 = if fraction > 0.6 {
+            Color::srgb(0.2, 0.9, 0.2)
+        } else if fraction > 0.3 {
+            Color::srgb(1.0, 0.8, 0.2)
+        } else {
+            Color::srgb(1.0, 0.3, 0.3)
+        };
+    }
+    set_text(&mut health_text_query, format!("{}", health.ceil() as u32));
+
+    if let Ok(mut row) = armor_row_query.single_mut() {
+        // Armor only shows once picked up
+        row.display = if me.armor > 0.0 {
+            Display::Flex
+        } else {
+            Display::None
+        };
+    }
+    if let Ok(mut node) = armor_fill_query.single_mut() {
+        node.width = Val::Percent((me.armor / MAX_ARMOR).clamp(0.0, 1.0) * 100.0);
+    }
+    set_text(&mut armor_text_query, format!("{}", me.armor.ceil() as u32));
+
+    set_text(&mut score_query, score_line(&game_data, me));
+}
+
+fn set_text<F: bevy::ecs::query::QueryFilter>(query: &mut Query<&mut Text, F>, value: String) {
+    if let Ok(mut text) = query.single_mut()
+        && text.0 != value
+    {
+        text.0 = value;
+    }
+}
+
+// "12 / 20 frags  |  2nd of 6", racing the same number the server checks against the limit
+fn score_line(game_data: &GameData, me: &Player) -> String {
+    let Some(mode) = game_data.mode else {
+        return String::new();
+    };
+    let race_score = |player: &Player| match mode {
+        GameMode::Deathmatch | GameMode::TeamDeathmatch | GameMode::Infection => player.kills,
+        _ => player.score,
+    };
+    let unit = match mode {
+        GameMode::CaptureTheFlag => "captures",
+        GameMode::KingOfTheHill | GameMode::TeamKingOfTheHill => "points",
+        GameMode::Elimination => "rounds",
+        _ => "frags",
+    };
+
+    // Team modes race the team total and rank us among our teammates
+    let team = me.team.filter(|_| mode.is_team_mode());
+    let score = match team {
+        Some(team) => game_data.team_scores.get(&team).copied().unwrap_or(0),
+        None => race_score(me),
+    };
+    let rivals: Vec<&Player> = game_data
+        .players
+        .values()
+        .filter(|p| team.is_none() || p.team == team)
+        .collect();
+    let rank = 1 + rivals
+        .iter()
+        .filter(|p| race_score(p) > race_score(me))
+        .count();
+
+    let score = match game_data.score_limit {
+        Some(limit) => format!("{} / {} {}", score, limit, unit),
+        None => format!("{} {}", score, unit),
+    };
+    let rank = format!("{} of {}", ordinal(rank), rivals.len());
+    match team {
+        Some(team) => format!("{} {}  |  {} on {}", team.name(), score, rank, team.name()),
+        None => format!("{}  |  {}", score, rank),
+    }
+}
+
+fn ordinal(n: usize) -> String {
+    let suffix = match (n % 10, n % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{}{}", n, suffix)
+}
@@ -0,0 +1,140 @@
+use crate::components::network::GameData;
+use bevy::prelude::*;
+use shared::{DamageKind, WeaponKind, weapons};
+
+const KILL_FEED_SECS: f32 = 5.0;
+const FADE_SECS: f32 = 1.0; // Entries fade out over the end of their time
+const MAX_KILL_FEED_ENTRIES: usize = 5;
+
+#[derive(Component)]
+pub struct KillFeedContainer;
+
+#[derive(Component)]
+pub struct KillFeedEntry {
+    pub timer: Timer,
+}
+
+pub fn setup_kill_feed(mut commands: Commands) {
+    // Recent kills down the top right corner, newest at the bottom
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(20.0),
+            right: Val::Px(20.0),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::FlexEnd,
+            row_gap: Val::Px(4.0),
+            ..default()
+        },
+        KillFeedContainer,
+    ));
+}
+
+// What killed the victim, in square brackets between the names
+pub fn weapon_label(weapon: Option<WeaponKind>, kind: &DamageKind) -> String {
+    match (weapon, kind) {
+        (Some(weapon), DamageKind::Splash) => format!("[{} splash]", weapons().get(weapon).name),
+        (Some(weapon), _) => format!("[{}]", weapons().get(weapon).name),
+        (None, DamageKind::Environmental) => "[world]".to_string(),
+        (None, _) => "[killed]".to_string(),
+    }
+}
+
+// Add "killer [weapon] victim" to the feed in the players' colors
+pub fn show_kill(
+    commands: &mut Commands,
+    container_query: &Query<Entity, With<KillFeedContainer>>,
+    game_data: &GameData,
+    killer_id: Option<&str>,
+    victim_id: &str,
+    weapon: Option<WeaponKind>,
+    kind: &DamageKind,
+) {
+    let Ok(container) = container_query.single() else {
+        return;
+    };
+    let name_and_color = |id: &str| {
+        game_data
+            .players
+            .get(id)
+            .map_or(("Unknown".to_string(), Color::WHITE), |p| {
+                (
+                    p.name.clone(),
+                    Color::srgb(p.color[0], p.color[1], p.color[2]),
+                )
+            })
+    };
+    let killer = killer_id.map(name_and_color);
+    let victim = name_and_color(victim_id);
+    let label = weapon_label(weapon, kind);
+
+    commands.entity(container).with_children(|parent| {
+        parent
+            .spawn((
+                Node {
+                    flex_direction: FlexDirection::Row,
+                    column_gap: Val::Px(8.0),
+                    padding: UiRect::axes(Val::Px(8.0), Val::Px(4.0)),
+                    ..default()
+                },
+                BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+                KillFeedEntry {
+                    timer: Timer::from_seconds(KILL_FEED_SECS, TimerMode::Once),
+                },
+            ))
+            .with_children(|entry| {
+                let mut text = |text: String, color: Color| {
+                    entry.spawn((
+                        Text::new(text),
+                        TextFont {
+                            font_size: 18.0,
+                            ..default()
+                        },
+                        TextColor(color),
+                    ));
+                };
+                if let Some((name, color)) = killer {
+                    text(name, color);
+                }
+                text(label, Color::srgb(0.8, 0.8, 0.8));
+                text(victim.0, victim.1);
+            });
+    });
+}
+
+pub fn update_kill_feed(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut entry_query: Query<(Entity, &mut KillFeedEntry, &Children, &mut BackgroundColor)>,
+    mut text_query: Query<&mut TextColor>,
+) {
+    // Too many kills at once push the oldest out early
+    let excess = entry_query
+        .iter()
+        .count()
+        .saturating_sub(MAX_KILL_FEED_ENTRIES);
+    let mut oldest: Vec<(Entity, f32)> = entry_query
+        .iter()
+        .map(|(entity, entry, ..)| (entity, entry.timer.elapsed_secs()))
+        .collect();
+    oldest.sort_by(|a, b| b.1.total_cmp(&a.1));
+    for (entity, _) in oldest.into_iter().take(excess) {
+        commands.entity(entity).despawn();
+    }
+
+    for (entity, mut entry, children, mut background) in entry_query.iter_mut() {
+        entry.timer.tick(time.delta());
+        if entry.timer.finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        let alpha = (entry.timer.remaining_secs() / FADE_SECS).min(1.0);
+        background.0.set_alpha(0.6 * alpha);
+        for child in children.iter() {
+            if let Ok(mut color) = text_query.get_mut(child) {
+                color.0.set_alpha(alpha);
+            }
+        }
+    }
+}
@@ -0,0 +1,169 @@
+use bevy::prelude::*;
+use bevy::render::camera::Viewport;
+use bevy::render::view::RenderLayers;
+use bevy::window::PrimaryWindow;
+use shared::{
+    KILLCAM_SECS, KillcamFrame, KillcamShot, PLAYER_HIT_RADIUS, WeaponKind, killcam_pose, weapons,
+};
+
+use super::death_screen::DeathState;
+use crate::systems::spectator::camera::eye_pose;
+
+const KILLCAM_LAYER: usize = 1; // Ghosts and tracers only the killcam camera draws
+const PIP_WIDTH: f32 = 0.32; // Share of the window the killcam takes up
+const PIP_MARGIN: f32 = 20.0;
+const CAPTION_HEIGHT: f32 = 36.0;
+const HOLD_SECS: f32 = 1.0; // Pause on the kill before the clip loops
+const TRACER_SECS: f32 = 0.15;
+
+// The moments before our last death, seen through the killer's eyes
+pub struct KillcamClip {
+    pub killer_name: String,
+    pub weapon: Option<WeaponKind>,
+    pub killer_health: f32,
+    pub killer_frames: Vec<KillcamFrame>,
+    pub victim_frames: Vec<KillcamFrame>,
+    pub shots: Vec<KillcamShot>,
+}
+
+#[derive(Resource, Default)]
+pub struct KillcamState {
+    pub clip: Option<KillcamClip>,
+    pub elapsed: f32,
+}
+
+impl KillcamState {
+    pub fn play(&mut self, clip: KillcamClip) {
+        self.clip = Some(clip);
+        self.elapsed = 0.0;
+    }
+}
+
+#[derive(Component)]
+pub struct KillcamCamera;
+
+#[derive(Component)]
+pub struct KillcamCaption;
+
+#[derive(Default, Reflect, GizmoConfigGroup)]
+pub struct KillcamGizmos;
+
+pub fn setup_killcam(mut commands: Commands, mut gizmo_config: ResMut<GizmoConfigStore>) {
+    // Picture-in-picture camera drawn over the death screen, off until we die
+    commands.spawn((
+        Camera3d::default(),
+        Camera {
+            order: 1,
+            is_active: false,
+            ..default()
+        },
+        RenderLayers::from_layers(&[0, KILLCAM_LAYER]),
+        KillcamCamera,
+    ));
+
+    // Who killed us, just above the picture
+    commands.spawn((
+        Text::new(""),
+        TextFont {
+            font_size: 20.0,
+            ..default()
+        },
+        TextColor(Color::srgb(1.0, 0.8, 0.2)),
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+        Node {
+            position_type: PositionType::Absolute,
+            padding: UiRect::all(Val::Px(6.0)),
+            ..default()
+        },
+        Visibility::Hidden,
+        KillcamCaption,
+    ));
+
+    let (config, _) = gizmo_config.config_mut::<KillcamGizmos>();
+    config.render_layers = RenderLayers::layer(KILLCAM_LAYER);
+}
+
+pub fn play_killcam(
+    time: Res<Time>,
+    death_state: Res<DeathState>,
+    mut killcam: ResMut<KillcamState>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut camera_query: Query<(&mut Camera, &mut Transform), With<KillcamCamera>>,
+    mut caption_query: Query<(&mut Text, &mut Node, &mut Visibility), With<KillcamCaption>>,
+    mut gizmos: Gizmos<KillcamGizmos>,
+) {
+    let (Ok((mut camera, mut transform)), Ok((mut caption, mut caption_node, mut visibility))) =
+        (camera_query.single_mut(), caption_query.single_mut())
+    else {
+        return;
+    };
+
+    // Respawning ends the killcam
+    if !death_state.is_dead {
+        killcam.clip = None;
+    }
+    let KillcamState { clip, elapsed } = &mut *killcam;
+    let (Some(clip), Ok(window)) = (clip.as_ref(), window_query.single()) else {
+        camera.is_active = false;
+        *visibility = Visibility::Hidden;
+        return;
+    };
+
+    // Loop the clip, holding on the kill for a moment each time
+    *elapsed = (*elapsed + time.delta_secs()) % (KILLCAM_SECS + HOLD_SECS);
+    let time_ms = elapsed.min(KILLCAM_SECS) * 1000.0;
+
+    // Bottom center, between the ammo display and the minimap and clear of the
+    // kill feed, in logical pixels for the caption and physical for the camera
+    let width = window.width() * PIP_WIDTH;
+    let height = width * 9.0 / 16.0;
+    let left = (window.width() - width) / 2.0;
+    let top = window.height() - height - PIP_MARGIN;
+    let scale = window.scale_factor();
+    camera.viewport = Some(Viewport {
+        physical_position: (Vec2::new(left, top) * scale).as_uvec2(),
+        physical_size: (Vec2::new(width, height) * scale)
+            .as_uvec2()
+            .max(UVec2::ONE),
+        ..default()
+    });
+    camera.is_active = true;
+
+    if let Some((position, rotation)) = killcam_pose(&clip.killer_frames, time_ms) {
+        let (eye, look) = eye_pose(position, rotation);
+        transform.translation = eye;
+        transform.rotation = look;
+    }
+    if let Some((position, _)) = killcam_pose(&clip.victim_frames, time_ms) {
+        gizmos.sphere(
+            Isometry3d::from_translation(position),
+            PLAYER_HIT_RADIUS,
+            Color::srgb(1.0, 0.2, 0.2),
+        );
+    }
+    for shot in &clip.shots {
+        let age = (time_ms - shot.time_ms as f32) / 1000.0;
+        if (0.0..TRACER_SECS).contains(&age) {
+            gizmos.line(
+                shot.origin,
+                shot.origin + shot.direction * shot.distance,
+                Color::srgb(1.0, 0.9, 0.3),
+            );
+        }
+    }
+
+    let weapon = clip
+        .weapon
+        .map(|weapon| format!(" with {}", weapons().get(weapon).name))
+        .unwrap_or_default();
+    let text = format!(
+        "Killed by {}{} - {:.0} HP left",
+        clip.killer_name, weapon, clip.killer_health
+    );
+    if caption.0 != text {
+        caption.0 = text;
+    }
+    caption_node.left = Val::Px(left);
+    caption_node.top = Val::Px(top - CAPTION_HEIGHT);
+    *visibility = Visibility::Visible;
+}
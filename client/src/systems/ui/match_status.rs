@@ -0,0 +1,102 @@
+use crate::components::network::GameData;
+use bevy::prelude::*;
+use shared::{GameMode, GameState, Team};
+
+#[derive(Component)]
+pub struct MatchStatusText;
+
+pub fn setup_match_status(mut commands: Commands) {
+    // Match status banner centered at the top of the screen
+    commands
+        .spawn(Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(20.0),
+            width: Val::Percent(100.0),
+            justify_content: JustifyContent::Center,
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 28.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                MatchStatusText,
+            ));
+        });
+}
+
+pub fn update_match_status(
+    time: Res<Time>,
+    mut game_data: ResMut<GameData>,
+    mut status_query: Query<&mut Text, With<MatchStatusText>>,
+) {
+    // Count down locally so the timer stays smooth between server updates
+    if let Some(remaining) = game_data.state_time_remaining.as_mut() {
+        *remaining = (*remaining - time.delta_secs()).max(0.0);
+    }
+
+    let Ok(mut text) = status_query.single_mut() else {
+        return;
+    };
+
+    let remaining = game_data.state_time_remaining.map(|r| r.ceil() as u32);
+    let status = match (&game_data.state, remaining) {
+        (Some(GameState::WaitingForPlayers), _) => "WARMUP - waiting for players".to_string(),
+        (Some(GameState::Countdown), Some(secs)) => format!("MATCH STARTS IN {}", secs),
+        (Some(GameState::GameStarted), secs) => {
+            let clock = secs
+                .map(|secs| format!("{}:{:02}", secs / 60, secs % 60))
+                .unwrap_or_default();
+            match game_data.score_limit {
+                Some(limit) => format!("{}  |  First to {}", clock, limit),
+                None => clock,
+            }
+        }
+        (Some(GameState::GameOver), Some(secs)) => format!("INTERMISSION - next round in {}", secs),
+        _ => String::new(),
+    };
+
+    // Team modes lead with the running team score
+    let status = if game_data.mode.is_some_and(|mode| mode.is_team_mode()) && !status.is_empty() {
+        let score = |team: Team| game_data.team_scores.get(&team).copied().unwrap_or(0);
+        format!(
+            "RED {} - {} BLUE  |  {}",
+            score(Team::Red),
+            score(Team::Blue),
+            status
+        )
+    } else if game_data.mode == Some(GameMode::Infection) && !status.is_empty() {
+        let infected = game_data
+            .players
+            .values()
+            .filter(|p| p.is_infected())
+            .count();
+        let survivors = game_data.players.len() - infected;
+        format!(
+            "{} SURVIVORS - {} INFECTED  |  {}",
+            survivors, infected, status
+        )
+    } else if game_data.mode == Some(GameMode::Elimination)
+        && game_data.state == Some(GameState::GameStarted)
+    {
+        // Elimination leads with the round, who is left and our round wins
+        let alive = game_data.players.values().filter(|p| p.is_alive).count();
+        let rounds_won = game_data.my_player().map_or(0, |p| p.score);
+        format!(
+            "ROUND {}  |  {} alive  |  {} won  |  {}",
+            game_data.round.unwrap_or(1),
+            alive,
+            rounds_won,
+            status
+        )
+    } else {
+        status
+    };
+
+    if text.0 != status {
+        text.0 = status;
+    }
+}
@@ -1 +1,11 @@
+pub mod ammo;
+pub mod chat;
+pub mod damage_indicators;
 pub mod death_screen;
+pub mod event_log;
+pub mod hud;
+pub mod kill_feed;
+pub mod killcam;
+pub mod match_status;
+pub mod scoreboard;
+pub mod zone_status;
@@ -0,0 +1,260 @@
+use crate::components::network::GameData;
+use bevy::prelude::*;
+use shared::{Player, Team};
+
+// Display order of team groups
+const TEAM_ORDER: [Team; 4] = [Team::Red, Team::Blue, Team::Survivors, Team::Infected];
+// Name, score, kills, deaths, assists, K/D, ping, status
+const COLUMN_WIDTHS: [f32; 8] = [220.0, 70.0, 50.0, 50.0, 50.0, 60.0, 70.0, 80.0];
+const SWATCH_SIZE: f32 = 14.0;
+
+#[derive(Component)]
+pub struct Scoreboard;
+
+#[derive(Component)]
+pub struct ScoreboardRows;
+
+// One line of the scoreboard, compared frame to frame to skip needless rebuilds
+#[derive(Clone, PartialEq)]
+pub enum ScoreboardLine {
+    Heading(String, Color),
+    Columns,
+    Player {
+        color: Color,
+        cells: [String; 8],
+        is_me: bool,
+        is_alive: bool,
+    },
+    Note(String),
+}
+
+pub fn setup_scoreboard(mut commands: Commands) {
+    // Centered panel, shown while Tab is held
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            Visibility::Hidden,
+            Scoreboard,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Node {
+                    flex_direction: FlexDirection::Column,
+                    padding: UiRect::all(Val::Px(20.0)),
+                    row_gap: Val::Px(4.0),
+                    ..default()
+                },
+                BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
+                ScoreboardRows,
+            ));
+        });
+}
+
+pub fn update_scoreboard(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    game_data: Res<GameData>,
+    mut scoreboard_query: Query<&mut Visibility, With<Scoreboard>>,
+    rows_query: Query<Entity, With<ScoreboardRows>>,
+    mut shown: Local<Vec<ScoreboardLine>>,
+) {
+    let (Ok(mut visibility), Ok(rows)) = (scoreboard_query.single_mut(), rows_query.single())
+    else {
+        return;
+    };
+
+    if !keyboard_input.pressed(KeyCode::Tab) {
+        *visibility = Visibility::Hidden;
+        return;
+    }
+    *visibility = Visibility::Visible;
+
+    let lines = scoreboard_lines(&game_data);
+    if *shown == lines {
+        return;
+    }
+    commands.entity(rows).despawn_related::<Children>();
+    commands.entity(rows).with_children(|parent| {
+        for line in &lines {
+            spawn_line(parent, line);
+        }
+    });
+    *shown = lines;
+}
+
+fn scoreboard_lines(game_data: &GameData) -> Vec<ScoreboardLine> {
+    let mut players: Vec<&Player> = game_data.players.values().collect();
+    players.sort_by(|a, b| {
+        (b.score, b.kills)
+            .cmp(&(a.score, a.kills))
+            .then(a.deaths.cmp(&b.deaths))
+            .then(a.name.cmp(&b.name))
+    });
+
+    let title = game_data.mode.map_or("Scoreboard", |mode| mode.name());
+    let mut lines = vec![ScoreboardLine::Heading(title.to_string(), Color::WHITE)];
+
+    // Team modes, and Infection's survivors and infected, list each side separately
+    if players.iter().any(|p| p.team.is_some()) {
+        for team in TEAM_ORDER {
+            let members: Vec<&Player> = players
+                .iter()
+                .copied()
+                .filter(|p| p.team == Some(team))
+                .collect();
+            if members.is_empty() {
+                continue;
+            }
+            let heading = match game_data.team_scores.get(&team) {
+                Some(score) => format!("{}  {}", team.name().to_uppercase(), score),
+                None => format!("{}  ({})", team.name().to_uppercase(), members.len()),
+            };
+            let [r, g, b] = team.color();
+            lines.push(ScoreboardLine::Heading(heading, Color::srgb(r, g, b)));
+            lines.push(ScoreboardLine::Columns);
+            lines.extend(members.iter().map(|p| player_line(p, game_data)));
+        }
+    } else {
+        lines.push(ScoreboardLine::Columns);
+        lines.extend(players.iter().map(|p| player_line(p, game_data)));
+    }
+
+    if !game_data.spectators.is_empty() {
+        lines.push(ScoreboardLine::Note(format!(
+            "Spectating: {}",
+            game_data.spectators.join(", ")
+        )));
+    }
+    lines
+}
+
+fn player_line(player: &Player, game_data: &GameData) -> ScoreboardLine {
+    let kd = player.kills as f32 / player.deaths.max(1) as f32;
+    let ping = player
+        .ping_ms
+        .map_or("-".to_string(), |ms| format!("{} ms", ms));
+    let status = if !player.is_alive {
+        "Dead"
+    } else if player.is_infected() {
+        "Infected"
+    } else {
+        "Alive"
+    };
+    ScoreboardLine::Player {
+        color: Color::srgb(player.color[0], player.color[1], player.color[2]),
+        cells: [
+            player.name.clone(),
+            player.score.to_string(),
+            player.kills.to_string(),
+            player.deaths.to_string(),
+            player.assists.to_string(),
+            format!("{:.2}", kd),
+            ping,
+            status.to_string(),
+        ],
+        is_me: game_data.my_id.as_deref() == Some(player.id.as_str()),
+        is_alive: player.is_alive,
+    }
+}
+
+fn spawn_line(parent: &mut ChildSpawnerCommands, line: &ScoreboardLine) {
+    match line {
+        ScoreboardLine::Heading(text, color) => {
+            parent.spawn((
+                Text::new(text.clone()),
+                TextFont {
+                    font_size: 24.0,
+                    ..default()
+                },
+                TextColor(*color),
+                Node {
+                    margin: UiRect::top(Val::Px(8.0)),
+                    ..default()
+                },
+            ));
+        }
+        ScoreboardLine::Columns => {
+            let titles = ["Name", "Score", "K", "D", "A", "K/D", "Ping", ""];
+            spawn_row(
+                parent,
+                None,
+                &titles.map(str::to_string),
+                Color::srgb(0.6, 0.6, 0.6),
+            );
+        }
+        ScoreboardLine::Player {
+            color,
+            cells,
+            is_me,
+            is_alive,
+        } => {
+            // Our own row stands out, the dead are greyed out
+            let text_color = match (is_me, is_alive) {
+                (_, false) => Color::srgb(0.5, 0.5, 0.5),
+                (true, true) => Color::srgb(1.0, 0.8, 0.2),
+                (false, true) => Color::WHITE,
+            };
+            spawn_row(parent, Some(*color), cells, text_color);
+        }
+        ScoreboardLine::Note(text) => {
+            parent.spawn((
+                Text::new(text.clone()),
+                TextFont {
+                    font_size: 18.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.8, 0.8, 0.8)),
+                Node {
+                    margin: UiRect::top(Val::Px(8.0)),
+                    ..default()
+                },
+            ));
+        }
+    }
+}
+
+fn spawn_row(
+    parent: &mut ChildSpawnerCommands,
+    swatch: Option<Color>,
+    cells: &[String; 8],
+    text_color: Color,
+) {
+    parent
+        .spawn(Node {
+            flex_direction: FlexDirection::Row,
+            align_items: AlignItems::Center,
+            column_gap: Val::Px(8.0),
+            ..default()
+        })
+        .with_children(|row| {
+            row.spawn((
+                Node {
+                    width: Val::Px(SWATCH_SIZE),
+                    height: Val::Px(SWATCH_SIZE),
+                    ..default()
+                },
+                BackgroundColor(swatch.unwrap_or(Color::NONE)),
+            ));
+            for (cell, width) in cells.iter().zip(COLUMN_WIDTHS) {
+                row.spawn((
+                    Text::new(cell.clone()),
+                    TextFont {
+                        font_size: 18.0,
+                        ..default()
+                    },
+                    TextColor(text_color),
+                    Node {
+                        width: Val::Px(width),
+                        ..default()
+                    },
+                ));
+            }
+        });
+}
@@ -0,0 +1,125 @@
+use crate::components::network::GameData;
+use bevy::prelude::*;
+
+const PROGRESS_BAR_WIDTH: f32 = 240.0;
+
+#[derive(Component)]
+pub struct ZoneStatus;
+
+#[derive(Component)]
+pub struct ZoneStatusText;
+
+#[derive(Component)]
+pub struct ZoneProgressFill;
+
+pub fn setup_zone_status(mut commands: Commands) {
+    // Hill status and capture progress under the match status banner
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(60.0),
+                width: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(4.0),
+                display: Display::None,
+                ..default()
+            },
+            ZoneStatus,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 20.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                ZoneStatusText,
+            ));
+            parent
+                .spawn((
+                    Node {
+                        width: Val::Px(PROGRESS_BAR_WIDTH),
+                        height: Val::Px(8.0),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+                ))
+                .with_children(|bar| {
+                    bar.spawn((
+                        Node {
+                            width: Val::Percent(0.0),
+                            height: Val::Percent(100.0),
+                            ..default()
+                        },
+                        BackgroundColor(Color::WHITE),
+                        ZoneProgressFill,
+                    ));
+                });
+        });
+}
+
+pub fn update_zone_status(
+    time: Res<Time>,
+    mut game_data: ResMut<GameData>,
+    mut root_query: Query<&mut Node, (With<ZoneStatus>, Without<ZoneProgressFill>)>,
+    mut text_query: Query<&mut Text, With<ZoneStatusText>>,
+    mut fill_query: Query<(&mut Node, &mut BackgroundColor), With<ZoneProgressFill>>,
+) {
+    // Count down locally so the timer stays smooth between server updates
+    if let Some(moves_in) = game_data.zone.as_mut().and_then(|z| z.moves_in.as_mut()) {
+        *moves_in = (*moves_in - time.delta_secs()).max(0.0);
+    }
+
+    let Ok(mut root) = root_query.single_mut() else {
+        return;
+    };
+    let Some(zone) = &game_data.zone else {
+        root.display = Display::None;
+        return;
+    };
+    root.display = Display::Flex;
+
+    let (status, progress, color) = if zone.contested {
+        (
+            "HILL CONTESTED".to_string(),
+            zone.progress,
+            Color::srgb(1.0, 0.85, 0.0),
+        )
+    } else if let Some(capturing) = &zone.capturing {
+        (
+            format!(
+                "{} is taking the hill",
+                game_data.zone_owner_name(capturing)
+            ),
+            zone.progress,
+            game_data.zone_owner_color(capturing),
+        )
+    } else if let Some(owner) = &zone.owner {
+        (
+            format!("{} holds the hill", game_data.zone_owner_name(owner)),
+            1.0,
+            game_data.zone_owner_color(owner),
+        )
+    } else {
+        ("The hill is neutral".to_string(), 0.0, Color::WHITE)
+    };
+
+    let status = match zone.moves_in {
+        Some(secs) => format!("{}  |  moves in {}s", status, secs.ceil() as u32),
+        None => status,
+    };
+
+    if let Ok(mut text) = text_query.single_mut()
+        && text.0 != status
+    {
+        text.0 = status;
+    }
+
+    if let Ok((mut fill, mut background)) = fill_query.single_mut() {
+        fill.width = Val::Percent(progress.clamp(0.0, 1.0) * 100.0);
+        background.0 = color;
+    }
+}
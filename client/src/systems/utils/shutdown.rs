@@ -23,7 +23,7 @@ pub fn handle_shutdown_signal(network: Res<NetworkClient>, mut exit: EventWriter
 
 // System to handle app exit events (window close, Cmd+Q, etc.)
 pub fn handle_app_exit(mut exit_events: EventReader<AppExit>, network: Res<NetworkClient>) {
-    for _exit_event in exit_events.read() {
+    if exit_events.read().next().is_some() {
         println!("App is exiting, sending LeaveGame message...");
 
         // Send LeaveGame message to server
@@ -33,7 +33,6 @@ pub fn handle_app_exit(mut exit_events: EventReader<AppExit>, network: Res<Netwo
         std::thread::sleep(std::time::Duration::from_millis(100));
 
         println!("Gracefully disconnected from server");
-        break; // Only handle the first exit event
     }
 }
 
@@ -0,0 +1,153 @@
+use bevy::prelude::*;
+use shared::{FLAG_TOUCH_RADIUS, FlagState, Team};
+
+use crate::components::{
+    network::GameData,
+    player::Player,
+    world::{FlagBaseMarker, FlagMarker},
+};
+
+const FLAG_HEIGHT: f32 = 3.0;
+const CARRIED_FLAG_OFFSET: Vec3 = Vec3::new(0.0, 1.0, 0.0); // Ride above the carrier's sphere
+
+fn team_color(team: Team) -> Color {
+    let [r, g, b] = team.color();
+    Color::srgb(r, g, b)
+}
+
+// Where a flag should be drawn, using our own predicted position if we carry it
+fn flag_position(flag: &FlagState, game_data: &GameData, local_position: Option<Vec3>) -> Vec3 {
+    match flag.carrier_id() {
+        Some(carrier_id) if game_data.my_id.as_deref() == Some(carrier_id) => {
+            local_position.unwrap_or(flag.position) + CARRIED_FLAG_OFFSET
+        }
+        Some(carrier_id) => {
+            game_data
+                .players
+                .get(carrier_id)
+                .map_or(flag.position, |p| p.position)
+                + CARRIED_FLAG_OFFSET
+        }
+        None => flag.position,
+    }
+}
+
+fn spawn_flag(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<StandardMaterial>,
+    team: Team,
+    position: Vec3,
+) {
+    let color = team_color(team);
+    let banner_material = materials.add(StandardMaterial {
+        base_color: color,
+        emissive: LinearRgba::from(color) * 2.0,
+        ..default()
+    });
+
+    commands
+        .spawn((
+            Transform::from_translation(position),
+            Visibility::default(),
+            FlagMarker { team },
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Mesh3d(meshes.add(Cylinder::new(0.08, FLAG_HEIGHT))),
+                MeshMaterial3d(materials.add(Color::srgb(0.85, 0.85, 0.85))),
+                Transform::from_xyz(0.0, FLAG_HEIGHT / 2.0, 0.0),
+            ));
+            parent.spawn((
+                Mesh3d(meshes.add(Cuboid::new(1.2, 0.7, 0.05))),
+                MeshMaterial3d(banner_material),
+                Transform::from_xyz(0.6, FLAG_HEIGHT - 0.4, 0.0),
+            ));
+        });
+}
+
+fn spawn_flag_base(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<StandardMaterial>,
+    team: Team,
+    base: Vec3,
+) {
+    let color = team_color(team).with_alpha(0.5);
+    commands.spawn((
+        Mesh3d(meshes.add(Cylinder::new(FLAG_TOUCH_RADIUS, 0.05))),
+        MeshMaterial3d(materials.add(StandardMaterial {
+            base_color: color,
+            alpha_mode: AlphaMode::Blend,
+            ..default()
+        })),
+        Transform::from_translation(base.with_y(0.25)),
+        FlagBaseMarker { team },
+    ));
+}
+
+// Keep the 3D flags and their base pads in sync with the server's flag state
+pub fn update_flags(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    game_data: Res<GameData>,
+    local_player: Query<&Transform, (With<Player>, Without<FlagMarker>)>,
+    mut flag_query: Query<
+        (Entity, &FlagMarker, &mut Transform),
+        (Without<Player>, Without<FlagBaseMarker>),
+    >,
+    mut base_query: Query<
+        (Entity, &FlagBaseMarker, &mut Transform),
+        (Without<Player>, Without<FlagMarker>),
+    >,
+    time: Res<Time>,
+) {
+    let local_position = local_player.single().ok().map(|t| t.translation);
+
+    let mut has_flag = Vec::new();
+    for (entity, marker, mut transform) in flag_query.iter_mut() {
+        match game_data.flags.get(&marker.team) {
+            Some(flag) => {
+                transform.translation = flag_position(flag, &game_data, local_position);
+                // Slow spin so the banner reads from every direction
+                transform.rotate_y(time.delta_secs());
+                has_flag.push(marker.team);
+            }
+            None => commands.entity(entity).despawn(),
+        }
+    }
+
+    let mut has_base = Vec::new();
+    for (entity, marker, mut transform) in base_query.iter_mut() {
+        match game_data.flags.get(&marker.team) {
+            Some(flag) => {
+                transform.translation = flag.base.with_y(0.25);
+                has_base.push(marker.team);
+            }
+            None => commands.entity(entity).despawn(),
+        }
+    }
+
+    for flag in game_data.flags.values() {
+        if !has_flag.contains(&flag.team) {
+            let position = flag_position(flag, &game_data, local_position);
+            spawn_flag(
+                &mut commands,
+                &mut meshes,
+                &mut materials,
+                flag.team,
+                position,
+            );
+        }
+        if !has_base.contains(&flag.team) {
+            spawn_flag_base(
+                &mut commands,
+                &mut meshes,
+                &mut materials,
+                flag.team,
+                flag.base,
+            );
+        }
+    }
+}
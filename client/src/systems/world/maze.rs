@@ -1,10 +1,12 @@
 use crate::components::{
     maze::{MazeFloor, MazeWall},
+    network::GameData,
     player::Player,
     world::{Collidable, SharedMaze},
 };
 use bevy::math::Vec3;
 use bevy::prelude::*;
+use shared::world_to_grid;
 
 #[derive(Resource)]
 pub struct MazeMaterials {
@@ -40,13 +42,19 @@ pub fn setup_maze_materials(
     });
 }
 
-/// System that renders the maze on startup
+/// System that renders the maze whenever the server sends a new one
 pub fn setup_maze(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     maze_materials: Res<MazeMaterials>,
     maze_data: Res<SharedMaze>,
+    old_tiles: Query<Entity, Or<(With<MazeWall>, With<MazeFloor>)>>,
 ) {
+    // Clear the previous round's maze
+    for entity in old_tiles.iter() {
+        commands.entity(entity).despawn();
+    }
+
     // Scale and offset values for maze positioning - no gaps between tiles
     let tile_size = 4.0; // Size of each maze tile
     let maze_width = maze_data.grid[0].len() as f32 * tile_size;
@@ -89,8 +97,22 @@ pub fn setup_maze(
 pub fn position_player_in_maze(
     mut player_query: Query<&mut Transform, With<Player>>,
     maze_data: Res<SharedMaze>,
+    game_data: Res<GameData>,
 ) {
     if let Ok(mut player_transform) = player_query.single_mut() {
+        // Prefer the spawn point the server assigned us
+        if let Some(my_player) = game_data
+            .my_id
+            .as_ref()
+            .and_then(|id| game_data.players.get(id))
+            && let Some((x, y)) = world_to_grid(&maze_data.grid, my_player.position)
+            && !maze_data.grid[y][x]
+        {
+            player_transform.translation =
+                Vec3::new(my_player.position.x, 2.5, my_player.position.z);
+            return;
+        }
+
         // Find the first valid (non-wall) position in the maze
         let tile_size = 4.0;
         let maze_width = maze_data.grid[0].len() as f32 * tile_size;
@@ -1,4 +1,8 @@
+pub mod flags;
 pub mod lights;
 pub mod maze;
+pub mod pickups;
+pub mod projectiles;
 pub mod setup;
 pub mod ui;
+pub mod zone;
@@ -0,0 +1,78 @@
+use bevy::prelude::*;
+use shared::PickupKind;
+
+use crate::components::{network::GameData, world::PickupMarker};
+
+const PICKUP_HEIGHT: f32 = 1.5;
+const SPIN_SPEED: f32 = 1.5; // Radians per second
+const BOB_HEIGHT: f32 = 0.25;
+
+fn pickup_mesh(kind: PickupKind) -> Mesh {
+    match kind {
+        PickupKind::HealthPack => Cuboid::new(1.0, 1.0, 1.0).into(),
+        PickupKind::Armor => Cylinder::new(0.6, 0.3).into(),
+        PickupKind::SpeedBoost => Torus::new(0.35, 0.65).into(),
+        PickupKind::DamageBoost => Tetrahedron::default().into(),
+        PickupKind::Invisibility => Sphere::new(0.6).into(),
+    }
+}
+
+// Spawn, hide and spin pickup meshes to match the server's spawners
+pub fn update_pickups(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    time: Res<Time>,
+    game_data: Res<GameData>,
+    mut pickup_query: Query<(Entity, &PickupMarker, &mut Transform, &mut Visibility)>,
+) {
+    let elapsed = time.elapsed_secs();
+
+    for (entity, marker, mut transform, mut visibility) in pickup_query.iter_mut() {
+        let Some(pickup) = game_data.pickups.get(&marker.id) else {
+            commands.entity(entity).despawn();
+            continue;
+        };
+
+        *visibility = if pickup.available {
+            Visibility::Visible
+        } else {
+            Visibility::Hidden
+        };
+
+        // Each pickup bobs slightly out of step with the others
+        let bob = (elapsed * 2.0 + marker.id as f32).sin() * BOB_HEIGHT;
+        transform.translation = pickup.position + Vec3::Y * (PICKUP_HEIGHT + bob);
+        transform.rotation = Quat::from_rotation_y(elapsed * SPIN_SPEED);
+    }
+
+    for pickup in game_data.pickups.values() {
+        if pickup_query
+            .iter()
+            .any(|(_, marker, _, _)| marker.id == pickup.id)
+        {
+            continue;
+        }
+
+        let [r, g, b] = pickup.kind.color();
+        commands.spawn((
+            Mesh3d(meshes.add(pickup_mesh(pickup.kind))),
+            MeshMaterial3d(materials.add(StandardMaterial {
+                base_color: Color::srgb(r, g, b),
+                emissive: LinearRgba::new(r * 3.0, g * 3.0, b * 3.0, 1.0),
+                ..default()
+            })),
+            Transform::from_translation(pickup.position + Vec3::Y * PICKUP_HEIGHT),
+            Visibility::default(),
+            PickupMarker { id: pickup.id },
+        ));
+    }
+}
+
+// Count power-ups down locally between server status updates
+pub fn tick_power_ups(time: Res<Time>, mut game_data: ResMut<GameData>) {
+    let dt = time.delta_secs();
+    for player in game_data.players.values_mut() {
+        player.tick_power_ups(dt);
+    }
+}
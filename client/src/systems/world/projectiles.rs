@@ -0,0 +1,62 @@
+use bevy::prelude::*;
+use shared::weapons;
+
+use crate::components::{network::GameData, projectile::Projectile};
+
+// Keep projectile meshes in step with the server, extrapolating between snapshots
+pub fn update_projectiles(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    time: Res<Time>,
+    game_data: Res<GameData>,
+    mut projectile_query: Query<(Entity, &mut Projectile, &mut Transform)>,
+) {
+    let dt = time.delta_secs();
+
+    for (entity, mut projectile, mut transform) in projectile_query.iter_mut() {
+        let Some(state) = game_data.projectiles.get(&projectile.id) else {
+            commands.entity(entity).despawn();
+            continue;
+        };
+
+        if state.position != projectile.synced_position {
+            projectile.synced_position = state.position;
+            projectile.velocity = state.velocity;
+            transform.translation = state.position;
+        } else {
+            projectile.velocity.y -= projectile.gravity * dt;
+            transform.translation += projectile.velocity * dt;
+            transform.translation.y = transform.translation.y.max(0.0);
+        }
+    }
+
+    for state in game_data.projectiles.values() {
+        if projectile_query
+            .iter()
+            .any(|(_, projectile, _)| projectile.id == state.id)
+        {
+            continue;
+        }
+        let Some(def) = &weapons().get(state.weapon).projectile else {
+            continue;
+        };
+
+        let [r, g, b] = def.color;
+        commands.spawn((
+            Mesh3d(meshes.add(Sphere::new(def.radius))),
+            MeshMaterial3d(materials.add(StandardMaterial {
+                base_color: Color::srgb(r, g, b),
+                emissive: LinearRgba::new(r * 6.0, g * 6.0, b * 6.0, 1.0),
+                ..default()
+            })),
+            Transform::from_translation(state.position),
+            Projectile {
+                id: state.id,
+                velocity: state.velocity,
+                gravity: def.gravity,
+                synced_position: state.position,
+            },
+        ));
+    }
+}
@@ -16,6 +16,7 @@ pub fn setup_world(
     // camera
     commands.spawn((
         Camera3d::default(),
+        IsDefaultUiCamera, // Not the killcam, which renders on top
         Transform::from_xyz(2.0, 6.0, 2.0),
         FollowCamera,
         CameraController {
@@ -36,10 +36,10 @@ pub fn update_fps_counter(
     mut query: Query<&mut TextSpan, With<FpsCounter>>,
 ) {
     for mut span in &mut query {
-        if let Some(fps) = diagnostics.get(&FrameTimeDiagnosticsPlugin::FPS) {
-            if let Some(value) = fps.smoothed() {
-                **span = format!("{value:.2}");
-            }
+        if let Some(fps) = diagnostics.get(&FrameTimeDiagnosticsPlugin::FPS)
+            && let Some(value) = fps.smoothed()
+        {
+            **span = format!("{value:.2}");
         }
     }
 }
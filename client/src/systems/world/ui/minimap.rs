@@ -1,6 +1,11 @@
 use crate::components::network::GameData;
-use crate::components::world::{Minimap, MinimapInitialized, MinimapPixel, PlayerDot, SharedMaze};
+use crate::components::player::FollowCamera;
+use crate::components::world::{
+    Minimap, MinimapFlag, MinimapInitialized, MinimapPixel, MinimapZone, PlayerDot,
+    RemotePlayerDot, SharedMaze,
+};
 use bevy::prelude::*;
+use shared::PowerUp;
 
 const MINIMAP_SIZE: f32 = 200.0;
 const MINIMAP_MARGIN: f32 = 20.0;
@@ -30,10 +35,30 @@ pub fn setup_minimap(mut commands: Commands) {
     ));
 }
 
+// Clear the minimap so update_minimap redraws it for a new round's maze
+pub fn reset_minimap(
+    mut commands: Commands,
+    minimap_query: Query<Entity, (With<Minimap>, With<MinimapInitialized>)>,
+) {
+    for minimap_entity in minimap_query.iter() {
+        commands
+            .entity(minimap_entity)
+            .despawn_related::<Children>()
+            .remove::<MinimapInitialized>();
+    }
+}
+
 pub fn update_minimap(
     mut commands: Commands,
     minimap_query: Query<Entity, (With<Minimap>, Without<MinimapInitialized>)>,
-    player_query: Query<&Transform, (With<Camera>, Without<MinimapPixel>, Without<PlayerDot>)>,
+    player_query: Query<
+        &Transform,
+        (
+            With<FollowCamera>,
+            Without<MinimapPixel>,
+            Without<PlayerDot>,
+        ),
+    >,
     shared_maze: Res<SharedMaze>,
 ) {
     // Only initialize the minimap once
@@ -117,7 +142,7 @@ pub fn update_minimap(
 
 pub fn update_player_position_on_minimap(
     mut player_dot_query: Query<&mut Node, With<PlayerDot>>,
-    player_query: Query<&Transform, (With<Camera>, Without<PlayerDot>)>,
+    player_query: Query<&Transform, (With<FollowCamera>, Without<PlayerDot>)>,
     shared_maze: Res<SharedMaze>,
     time: Res<Time>,
     mut minimap_timer: Local<MinimapTimer>,
@@ -128,50 +153,276 @@ pub fn update_player_position_on_minimap(
         return;
     }
     minimap_timer.last_update = current_time;
-    if let Ok(player_transform) = player_query.single() {
-        if let Ok(mut player_dot_node) = player_dot_query.single_mut() {
-            let player_pos = player_transform.translation;
+    if let Ok(player_transform) = player_query.single()
+        && let Ok(mut player_dot_node) = player_dot_query.single_mut()
+    {
+        let player_pos = player_transform.translation;
 
-            // Convert 3D world coordinates to minimap coordinates
-            let maze = &shared_maze.grid;
-            let maze_width = maze[0].len() as f32;
-            let maze_height = maze.len() as f32;
-            let pixel_size = MINIMAP_SIZE / maze_width.max(maze_height);
+        // Convert 3D world coordinates to minimap coordinates
+        let maze = &shared_maze.grid;
+        let maze_width = maze[0].len() as f32;
+        let maze_height = maze.len() as f32;
+        let pixel_size = MINIMAP_SIZE / maze_width.max(maze_height);
 
-            // Convert world position to maze grid coordinates
-            let grid_x = (player_pos.x + (maze_width * TILE_SIZE / 2.0)) / TILE_SIZE;
-            let grid_z = (player_pos.z + (maze_height * TILE_SIZE / 2.0)) / TILE_SIZE;
+        // Convert world position to maze grid coordinates
+        let grid_x = (player_pos.x + (maze_width * TILE_SIZE / 2.0)) / TILE_SIZE;
+        let grid_z = (player_pos.z + (maze_height * TILE_SIZE / 2.0)) / TILE_SIZE;
 
-            // Convert grid coordinates to minimap pixels
-            let minimap_x = grid_x * pixel_size;
-            let minimap_z = grid_z * pixel_size;
+        // Convert grid coordinates to minimap pixels
+        let minimap_x = grid_x * pixel_size;
+        let minimap_z = grid_z * pixel_size;
 
-            // Clamp to minimap bounds
-            let minimap_x = minimap_x.clamp(0.0, MINIMAP_SIZE - 8.0);
-            let minimap_z = minimap_z.clamp(0.0, MINIMAP_SIZE - 8.0);
+        // Clamp to minimap bounds
+        let minimap_x = minimap_x.clamp(0.0, MINIMAP_SIZE - 8.0);
+        let minimap_z = minimap_z.clamp(0.0, MINIMAP_SIZE - 8.0);
 
-            // Update player dot position
-            player_dot_node.left = Val::Px(minimap_x);
-            player_dot_node.top = Val::Px(minimap_z);
-        }
+        // Update player dot position
+        player_dot_node.left = Val::Px(minimap_x);
+        player_dot_node.top = Val::Px(minimap_z);
     }
 }
 
 // System to update local player dot color based on server-assigned color
 pub fn update_player_dot_colors(
     mut player_dot_query: Query<(&mut BackgroundColor, &mut BorderColor), With<PlayerDot>>,
+    mut minimap_query: Query<&mut BorderColor, (With<Minimap>, Without<PlayerDot>)>,
     game_data: Res<GameData>,
 ) {
     // Update local player dot color only
-    if let Some(my_id) = &game_data.my_id {
-        if let Some(player) = game_data.players.get(my_id) {
-            if let Ok(mut colors) = player_dot_query.single_mut() {
-                let new_color = Color::srgb(player.color[0], player.color[1], player.color[2]);
-                if colors.0.0 != new_color {
-                    colors.0.0 = new_color;
-                    colors.1.0 = Color::WHITE;
-                }
+    if let Some(player) = game_data.my_player() {
+        // Tint the minimap frame with our team color in team modes
+        let frame_color = player
+            .team
+            .map(|team| Color::srgb(team.color()[0], team.color()[1], team.color()[2]))
+            .unwrap_or(Color::WHITE);
+        if let Ok(mut border) = minimap_query.single_mut()
+            && border.0 != frame_color
+        {
+            border.0 = frame_color;
+        }
+
+        if let Ok(mut colors) = player_dot_query.single_mut() {
+            let new_color = Color::srgb(player.color[0], player.color[1], player.color[2]);
+            if colors.0.0 != new_color {
+                colors.0.0 = new_color;
+                colors.1.0 = Color::WHITE;
             }
         }
     }
 }
+
+// Convert a world position to pixel offsets inside the minimap
+fn world_to_minimap(position: Vec3, maze: &SharedMaze) -> (f32, f32) {
+    let maze_width = maze.grid[0].len() as f32;
+    let maze_height = maze.grid.len() as f32;
+    let pixel_size = MINIMAP_SIZE / maze_width.max(maze_height);
+
+    let grid_x = (position.x + (maze_width * TILE_SIZE / 2.0)) / TILE_SIZE;
+    let grid_z = (position.z + (maze_height * TILE_SIZE / 2.0)) / TILE_SIZE;
+
+    (
+        (grid_x * pixel_size).clamp(0.0, MINIMAP_SIZE - 8.0),
+        (grid_z * pixel_size).clamp(0.0, MINIMAP_SIZE - 8.0),
+    )
+}
+
+// Count down an active radar pulse
+pub fn tick_radar_pulse(time: Res<Time>, mut game_data: ResMut<GameData>) {
+    if let Some(remaining) = game_data.radar_remaining.as_mut() {
+        *remaining -= time.delta_secs();
+        if *remaining <= 0.0 {
+            game_data.radar_remaining = None;
+        }
+    }
+}
+
+// Show living teammates on the minimap in their team color, and
+// everyone else who isn't invisible while a radar pulse is active
+pub fn update_remote_player_dots(
+    mut commands: Commands,
+    minimap_query: Query<Entity, (With<Minimap>, With<MinimapInitialized>)>,
+    mut dot_query: Query<(Entity, &RemotePlayerDot, &mut Node, &mut BackgroundColor)>,
+    game_data: Res<GameData>,
+    shared_maze: Res<SharedMaze>,
+) {
+    let Ok(minimap_entity) = minimap_query.single() else {
+        return;
+    };
+    let Some(me) = game_data.my_player() else {
+        return;
+    };
+
+    let visible: Vec<_> = game_data
+        .players
+        .values()
+        .filter(|p| {
+            p.id != me.id
+                && p.is_alive
+                && (me.is_teammate(p)
+                    || (game_data.radar_remaining.is_some()
+                        && !p.has_power_up(PowerUp::Invisibility)))
+        })
+        .collect();
+
+    // Move or remove existing dots
+    let mut has_dot = Vec::new();
+    for (entity, dot, mut node, mut background) in dot_query.iter_mut() {
+        match visible.iter().find(|p| p.id == dot.id) {
+            Some(player) => {
+                let (x, z) = world_to_minimap(player.position, &shared_maze);
+                node.left = Val::Px(x);
+                node.top = Val::Px(z);
+                background.0 = Color::srgb(player.color[0], player.color[1], player.color[2]);
+                has_dot.push(dot.id.clone());
+            }
+            None => {
+                commands.entity(entity).despawn();
+            }
+        }
+    }
+
+    // Spawn dots for newly visible players
+    for player in visible.iter().filter(|p| !has_dot.contains(&p.id)) {
+        let (x, z) = world_to_minimap(player.position, &shared_maze);
+        commands.entity(minimap_entity).with_children(|parent| {
+            parent.spawn((
+                Node {
+                    position_type: PositionType::Absolute,
+                    left: Val::Px(x),
+                    top: Val::Px(z),
+                    width: Val::Px(6.0),
+                    height: Val::Px(6.0),
+                    ..default()
+                },
+                BackgroundColor(Color::srgb(
+                    player.color[0],
+                    player.color[1],
+                    player.color[2],
+                )),
+                RemotePlayerDot {
+                    id: player.id.clone(),
+                },
+            ));
+        });
+    }
+}
+
+// Show both Capture the Flag flags on the minimap, following their carriers
+pub fn update_minimap_flags(
+    mut commands: Commands,
+    minimap_query: Query<Entity, (With<Minimap>, With<MinimapInitialized>)>,
+    mut marker_query: Query<(Entity, &MinimapFlag, &mut Node)>,
+    game_data: Res<GameData>,
+    shared_maze: Res<SharedMaze>,
+) {
+    let Ok(minimap_entity) = minimap_query.single() else {
+        return;
+    };
+
+    let flag_position = |team| {
+        game_data.flags.get(&team).map(|flag| {
+            flag.carrier_id()
+                .and_then(|id| game_data.players.get(id))
+                .map_or(flag.position, |carrier| carrier.position)
+        })
+    };
+
+    let mut has_marker = Vec::new();
+    for (entity, marker, mut node) in marker_query.iter_mut() {
+        match flag_position(marker.team) {
+            Some(position) => {
+                let (x, z) = world_to_minimap(position, &shared_maze);
+                node.left = Val::Px(x);
+                node.top = Val::Px(z);
+                has_marker.push(marker.team);
+            }
+            None => {
+                commands.entity(entity).despawn();
+            }
+        }
+    }
+
+    for flag in game_data.flags.values() {
+        if has_marker.contains(&flag.team) {
+            continue;
+        }
+        let Some(position) = flag_position(flag.team) else {
+            continue;
+        };
+
+        let (x, z) = world_to_minimap(position, &shared_maze);
+        let [r, g, b] = flag.team.color();
+        commands.entity(minimap_entity).with_children(|parent| {
+            parent.spawn((
+                Node {
+                    position_type: PositionType::Absolute,
+                    left: Val::Px(x),
+                    top: Val::Px(z),
+                    width: Val::Px(8.0),
+                    height: Val::Px(8.0),
+                    border: UiRect::all(Val::Px(2.0)),
+                    ..default()
+                },
+                BackgroundColor(Color::srgb(r, g, b)),
+                BorderColor(Color::srgb(1.0, 0.85, 0.0)),
+                MinimapFlag { team: flag.team },
+            ));
+        });
+    }
+}
+
+// Outline the King of the Hill zone on the minimap in its owner's color
+pub fn update_minimap_zone(
+    mut commands: Commands,
+    minimap_query: Query<Entity, (With<Minimap>, With<MinimapInitialized>)>,
+    mut zone_query: Query<(Entity, &mut Node, &mut BorderColor), With<MinimapZone>>,
+    game_data: Res<GameData>,
+    shared_maze: Res<SharedMaze>,
+) {
+    let Ok(minimap_entity) = minimap_query.single() else {
+        return;
+    };
+    let Some(zone) = &game_data.zone else {
+        for (entity, _, _) in zone_query.iter() {
+            commands.entity(entity).despawn();
+        }
+        return;
+    };
+
+    let maze_cells = shared_maze.grid.len().max(shared_maze.grid[0].len()) as f32;
+    let size = zone.radius * 2.0 / TILE_SIZE * (MINIMAP_SIZE / maze_cells);
+    let (x, z) = world_to_minimap(zone.center, &shared_maze);
+    let (left, top) = (x - size / 2.0, z - size / 2.0);
+    let color = zone
+        .owner
+        .as_ref()
+        .map(|owner| game_data.zone_owner_color(owner))
+        .unwrap_or(Color::WHITE);
+
+    match zone_query.single_mut() {
+        Ok((_, mut node, mut border)) => {
+            node.left = Val::Px(left);
+            node.top = Val::Px(top);
+            border.0 = color;
+        }
+        Err(_) => {
+            commands.entity(minimap_entity).with_children(|parent| {
+                parent.spawn((
+                    Node {
+                        position_type: PositionType::Absolute,
+                        left: Val::Px(left),
+                        top: Val::Px(top),
+                        width: Val::Px(size),
+                        height: Val::Px(size),
+                        border: UiRect::all(Val::Px(2.0)),
+                        ..default()
+                    },
+                    BorderColor(color),
+                    BorderRadius::MAX,
+                    BackgroundColor(Color::srgba(1.0, 1.0, 1.0, 0.15)),
+                    MinimapZone,
+                ));
+            });
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use bevy::prelude::*;
+use shared::ControlZone;
+
+use crate::components::{network::GameData, world::ZoneMarker};
+
+const ZONE_FLOOR_HEIGHT: f32 = 0.22; // Just above the floor tiles
+
+// Owner's color, flashing yellow while contested and a neutral white otherwise
+fn zone_color(zone: &ControlZone, game_data: &GameData, elapsed: f32) -> Color {
+    if zone.contested {
+        let pulse = 0.5 + 0.5 * (elapsed * 8.0).sin();
+        return Color::srgba(1.0, 0.85, 0.0, 0.3 + 0.3 * pulse);
+    }
+
+    zone.owner
+        .as_ref()
+        .map(|owner| game_data.zone_owner_color(owner))
+        .unwrap_or(Color::WHITE)
+        .with_alpha(0.45)
+}
+
+// Keep the floor disc for the control zone in sync with the server
+pub fn update_zone(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    game_data: Res<GameData>,
+    mut zone_query: Query<
+        (Entity, &mut Transform, &MeshMaterial3d<StandardMaterial>),
+        With<ZoneMarker>,
+    >,
+    time: Res<Time>,
+) {
+    let Some(zone) = &game_data.zone else {
+        for (entity, _, _) in zone_query.iter() {
+            commands.entity(entity).despawn();
+        }
+        return;
+    };
+
+    let color = zone_color(zone, &game_data, time.elapsed_secs());
+    let translation = zone.center.with_y(ZONE_FLOOR_HEIGHT);
+
+    match zone_query.single_mut() {
+        Ok((_, mut transform, material)) => {
+            transform.translation = translation;
+            if let Some(material) = materials.get_mut(&material.0) {
+                material.base_color = color;
+                material.emissive = LinearRgba::from(color) * 0.5;
+            }
+        }
+        Err(_) => {
+            commands.spawn((
+                Mesh3d(meshes.add(Cylinder::new(zone.radius, 0.04))),
+                MeshMaterial3d(materials.add(StandardMaterial {
+                    base_color: color,
+                    emissive: LinearRgba::from(color) * 0.5,
+                    alpha_mode: AlphaMode::Blend,
+                    ..default()
+                })),
+                Transform::from_translation(translation),
+                ZoneMarker,
+            ));
+        }
+    }
+}
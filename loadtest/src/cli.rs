@@ -0,0 +1,70 @@
+use tokio::time::Duration;
+
+#[derive(clap::Parser)]
+#[clap(about = "Headless simulated players for load and soak testing a Sphere Wars server")]
+pub struct Cli {
+    #[clap(long, default_value = "127.0.0.1", help = "Server host address")]
+    pub host: String,
+    #[clap(short, long, default_value = "8080", help = "Server port")]
+    pub port: u16,
+    #[clap(
+        short = 'n',
+        long,
+        default_value = "8",
+        help = "Simulated players to connect"
+    )]
+    pub players: usize,
+    #[clap(
+        short,
+        long,
+        default_value = "0",
+        help = "How long to run in seconds (0 runs until Ctrl+C)"
+    )]
+    pub duration: u64,
+    #[clap(long, default_value = "10", help = "Seconds between progress reports")]
+    pub report_interval: u64,
+    #[clap(
+        long,
+        default_value = "0",
+        help = "Average seconds before a player leaves and rejoins (0 disables it)",
+        long_help = "Average seconds a simulated player stays before leaving and rejoining from a new port.\nChurn exercises the join and leave paths, which is where per-player bookkeeping leaks show up."
+    )]
+    pub churn: u64,
+    #[clap(
+        long,
+        default_value = "loadbot",
+        help = "Name prefix for simulated players"
+    )]
+    pub name_prefix: String,
+}
+
+impl Cli {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.players == 0 {
+            return Err("--players must be at least 1".to_string());
+        }
+
+        if self.report_interval == 0 {
+            return Err("--report-interval must be at least 1".to_string());
+        }
+
+        // Names are capped at 20 characters by the game client
+        let longest_name = format!("{}-{}", self.name_prefix, self.players);
+        if longest_name.len() > 20 {
+            return Err(format!(
+                "--name-prefix is too long, '{}' exceeds 20 characters",
+                longest_name
+            ));
+        }
+
+        Ok(())
+    }
+
+    pub fn duration(&self) -> Option<Duration> {
+        (self.duration > 0).then(|| Duration::from_secs(self.duration))
+    }
+
+    pub fn churn(&self) -> Option<Duration> {
+        (self.churn > 0).then(|| Duration::from_secs(self.churn))
+    }
+}
@@ -0,0 +1,154 @@
+use clap::Parser;
+use shared::{ClientMessage, ServerMessage, ServerStats};
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use tokio::net::UdpSocket;
+use tokio::signal;
+use tokio::sync::watch;
+use tokio::time::{Duration, Instant};
+
+mod cli;
+mod sim;
+mod stats;
+
+use cli::Cli;
+use sim::SimPlayer;
+use stats::{Stats, leftover_warnings, print_report};
+
+const JOIN_STAGGER: Duration = Duration::from_millis(100); // Don't flood the server with joins
+const STATS_TIMEOUT: Duration = Duration::from_secs(1);
+const SETTLE_TIME: Duration = Duration::from_secs(1); // Lets the server process leaves and drop its bots
+
+// Ask the server for its bookkeeping sizes over a socket of our own
+async fn query_stats(socket: &UdpSocket) -> Option<ServerStats> {
+    let encoded =
+        bincode::serde::encode_to_vec(ClientMessage::QueryStats, bincode::config::standard())
+            .ok()?;
+    socket.send(&encoded).await.ok()?;
+
+    let mut buf = vec![0; 65536];
+    let deadline = Instant::now() + STATS_TIMEOUT;
+    while let Ok(Ok(len)) = tokio::time::timeout_at(deadline, socket.recv(&mut buf)).await {
+        if let Ok((ServerMessage::ServerStats { stats }, _)) =
+            bincode::serde::decode_from_slice(&buf[..len], bincode::config::standard())
+        {
+            return Some(stats);
+        }
+    }
+    None
+}
+
+#[tokio::main]
+async fn main() {
+    let cli = Cli::parse();
+
+    if let Err(error) = cli.validate() {
+        eprintln!("Error: {}", error);
+        std::process::exit(1);
+    }
+
+    let server_addr: SocketAddr = match tokio::net::lookup_host((cli.host.as_str(), cli.port))
+        .await
+        .ok()
+        .and_then(|mut addrs| addrs.next())
+    {
+        Some(addr) => addr,
+        None => {
+            eprintln!("Error: Cannot resolve {}:{}", cli.host, cli.port);
+            std::process::exit(1);
+        }
+    };
+
+    let probe = UdpSocket::bind("0.0.0.0:0").await.unwrap();
+    probe.connect(server_addr).await.unwrap();
+    if query_stats(&probe).await.is_none() {
+        eprintln!(
+            "❌ No reply from {}, is the server running with --expose-stats?",
+            server_addr
+        );
+        std::process::exit(1);
+    }
+
+    println!("🤖 Sphere Wars Load Test");
+    println!("Server: {}", server_addr);
+    println!(
+        "Players: {}{}",
+        cli.players,
+        match cli.churn() {
+            Some(churn) => format!(", rejoining every ~{}s", churn.as_secs()),
+            None => String::new(),
+        }
+    );
+    println!(
+        "Duration: {}",
+        match cli.duration() {
+            Some(duration) => format!("{}s", duration.as_secs()),
+            None => "until Ctrl+C".to_string(),
+        }
+    );
+    println!("=====================================");
+
+    let stats = Arc::new(Mutex::new(Stats::default()));
+    let (shutdown_tx, shutdown_rx) = watch::channel(false);
+    let mut players = Vec::new();
+    for index in 0..cli.players {
+        let player = SimPlayer::new(
+            format!("{}-{}", cli.name_prefix, index + 1),
+            server_addr,
+            cli.churn(),
+            stats.clone(),
+        );
+        players.push(tokio::spawn(player.run(shutdown_rx.clone())));
+        tokio::time::sleep(JOIN_STAGGER).await;
+    }
+
+    let started = Instant::now();
+    let deadline = cli.duration().map(|duration| started + duration);
+    let mut report_timer = tokio::time::interval(Duration::from_secs(cli.report_interval));
+    report_timer.tick().await;
+
+    loop {
+        tokio::select! {
+            _ = report_timer.tick() => {
+                let server_stats = query_stats(&probe).await;
+                print_report(started.elapsed(), &mut stats.lock().unwrap(), server_stats.as_ref());
+            }
+            _ = signal::ctrl_c() => {
+                println!("Received shutdown signal, disconnecting players...");
+                break;
+            }
+            _ = tokio::time::sleep_until(deadline.unwrap_or(started)), if deadline.is_some() => {
+                println!("Duration reached, disconnecting players...");
+                break;
+            }
+        }
+    }
+
+    let _ = shutdown_tx.send(true);
+    for player in players {
+        let _ = player.await;
+    }
+    tokio::time::sleep(SETTLE_TIME).await;
+
+    // Assumes nobody else is playing on the server under test
+    let server_stats = query_stats(&probe).await;
+    print_report(
+        started.elapsed(),
+        &mut stats.lock().unwrap(),
+        server_stats.as_ref(),
+    );
+    let Some(server_stats) = server_stats else {
+        eprintln!("❌ No reply from the server after the test");
+        std::process::exit(1);
+    };
+
+    let leftovers = leftover_warnings(&server_stats);
+    if leftovers.is_empty() {
+        println!("✅ Server cleaned up after every player");
+    } else {
+        for leftover in &leftovers {
+            eprintln!("❌ {}", leftover);
+        }
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,359 @@
+use bevy::math::{Quat, Vec3};
+use rand::Rng;
+use rand::seq::IndexedRandom;
+use shared::{
+    ClientMessage, MazeConfig, MazeGrid, ServerMessage, find_path, generate_maze_from_config,
+    grid_to_world, world_to_grid,
+};
+use std::collections::VecDeque;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use tokio::net::UdpSocket;
+use tokio::sync::watch;
+use tokio::time::{Duration, Instant};
+
+use crate::stats::Stats;
+
+const TICK_INTERVAL: Duration = Duration::from_millis(50); // Same rate the game client moves at
+const PLAYER_SPEED: f32 = 15.0;
+const SHOT_CHANCE: f64 = 0.1; // Per tick, so about two shots a second
+const PING_INTERVAL: Duration = Duration::from_secs(1);
+const PING_TIMEOUT: Duration = Duration::from_secs(2); // Unanswered pings count as lost
+const JOIN_RETRY: Duration = Duration::from_secs(2);
+const RESPAWN_WAIT: Duration = Duration::from_millis(3200); // Just past the server's respawn delay
+const RESPAWN_RETRY: Duration = Duration::from_secs(1);
+
+// One headless player that wanders the maze and shoots at random
+pub struct SimPlayer {
+    name: String,
+    server_addr: SocketAddr,
+    churn: Option<Duration>,
+    stats: Arc<Mutex<Stats>>,
+}
+
+// Everything a simulated player knows during one connection
+struct Session {
+    socket: UdpSocket,
+    player_id: Option<String>,
+    join_sent_at: Instant,
+    position: Vec3,
+    is_alive: bool,
+    died_at: Option<Instant>,
+    respawn_sent_at: Option<Instant>,
+    grid: Option<MazeGrid>,
+    open_cells: Vec<(usize, usize)>,
+    path: Vec<Vec3>,          // Waypoints still to walk, the next one last
+    pings: VecDeque<Instant>, // Send times of pings still waiting for a reply
+    ping_sent_at: Instant,
+    pong_due: Option<u32>, // The server's last ping, answered on the next tick
+}
+
+impl SimPlayer {
+    pub fn new(
+        name: String,
+        server_addr: SocketAddr,
+        churn: Option<Duration>,
+        stats: Arc<Mutex<Stats>>,
+    ) -> Self {
+        Self {
+            name,
+            server_addr,
+            churn,
+            stats,
+        }
+    }
+
+    // Play until shut down, reconnecting from a fresh port whenever churn says so
+    pub async fn run(self, mut shutdown: watch::Receiver<bool>) {
+        loop {
+            let socket = match UdpSocket::bind("0.0.0.0:0").await {
+                Ok(socket) => socket,
+                Err(e) => {
+                    self.record_error(&format!("Failed to bind socket: {}", e));
+                    return;
+                }
+            };
+            if let Err(e) = socket.connect(self.server_addr).await {
+                self.record_error(&format!("Failed to connect socket: {}", e));
+                return;
+            }
+
+            let mut session = Session {
+                socket,
+                player_id: None,
+                join_sent_at: Instant::now(),
+                position: Vec3::ZERO,
+                is_alive: false,
+                died_at: None,
+                respawn_sent_at: None,
+                grid: None,
+                open_cells: Vec::new(),
+                path: Vec::new(),
+                pings: VecDeque::new(),
+                ping_sent_at: Instant::now(),
+                pong_due: None,
+            };
+            if self.play(&mut session, &mut shutdown).await {
+                return;
+            }
+        }
+    }
+
+    // One connection from join to leave, returning true when shutting down
+    async fn play(&self, session: &mut Session, shutdown: &mut watch::Receiver<bool>) -> bool {
+        self.join(session).await;
+        let stay_for = self
+            .churn
+            .map(|churn| churn.mul_f64(rand::rng().random_range(0.5..1.5)));
+        let started = Instant::now();
+        let mut ticker = tokio::time::interval(TICK_INTERVAL);
+        let mut buf = vec![0; 65536];
+
+        loop {
+            tokio::select! {
+                result = session.socket.recv(&mut buf) => match result {
+                    Ok(len) => self.handle_packet(session, &buf[..len]),
+                    Err(e) => self.record_error(&format!("Receive failed: {}", e)),
+                },
+                _ = ticker.tick() => self.act(session).await,
+                _ = shutdown.changed() => {
+                    self.leave(session).await;
+                    return true;
+                }
+            }
+
+            if session.player_id.is_some() && stay_for.is_some_and(|d| started.elapsed() >= d) {
+                self.leave(session).await;
+                return false;
+            }
+        }
+    }
+
+    async fn join(&self, session: &mut Session) {
+        let join_msg = ClientMessage::JoinGame {
+            player_name: self.name.clone(),
+        };
+        self.send(session, &join_msg).await;
+        session.join_sent_at = Instant::now();
+    }
+
+    async fn leave(&self, session: &mut Session) {
+        self.send(session, &ClientMessage::LeaveGame).await;
+        if session.player_id.take().is_some() {
+            let mut stats = self.stats.lock().unwrap();
+            stats.joined -= 1;
+            stats.leaves += 1;
+        }
+    }
+
+    async fn send(&self, session: &Session, msg: &ClientMessage) {
+        let encoded = match bincode::serde::encode_to_vec(msg, bincode::config::standard()) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                self.record_error(&format!("Failed to serialize message: {}", e));
+                return;
+            }
+        };
+
+        match session.socket.send(&encoded).await {
+            Ok(_) => self.stats.lock().unwrap().messages_sent += 1,
+            Err(e) => self.record_error(&format!("Send failed: {}", e)),
+        }
+    }
+
+    fn record_error(&self, message: &str) {
+        self.stats.lock().unwrap().record_error(message);
+    }
+
+    fn handle_packet(&self, session: &mut Session, packet: &[u8]) {
+        let Ok((msg, _)) = bincode::serde::decode_from_slice(packet, bincode::config::standard())
+        else {
+            self.stats.lock().unwrap().decode_failures += 1;
+            return;
+        };
+        self.stats.lock().unwrap().messages_received += 1;
+
+        let is_me = |player_id: &str| session.player_id.as_deref() == Some(player_id);
+        match msg {
+            ServerMessage::GameJoined { player_id } => {
+                if session.player_id.is_none() {
+                    let mut stats = self.stats.lock().unwrap();
+                    stats.joined += 1;
+                    stats.joins += 1;
+                }
+                session.player_id = Some(player_id);
+            }
+            ServerMessage::GameState { players, .. } => {
+                if let Some(me) = session
+                    .player_id
+                    .as_ref()
+                    .and_then(|player_id| players.get(player_id))
+                {
+                    session.position = me.position;
+                    session.is_alive = me.is_alive;
+                    session.path.clear();
+                }
+            }
+            ServerMessage::GameStarted {
+                seed,
+                width,
+                height,
+                difficulty,
+            } => {
+                let config = MazeConfig::new(seed, width, height, &difficulty);
+                let grid = generate_maze_from_config(&config).grid;
+                session.open_cells = grid
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(y, row)| {
+                        row.iter()
+                            .enumerate()
+                            .filter(|(_, is_wall)| !**is_wall)
+                            .map(move |(x, _)| (x, y))
+                    })
+                    .collect();
+                session.grid = Some(grid);
+                session.path.clear();
+            }
+            ServerMessage::PlayerRespawned {
+                player_id,
+                position,
+            } if is_me(&player_id) => {
+                session.position = position;
+                session.is_alive = true;
+                session.died_at = None;
+                session.path.clear();
+            }
+            ServerMessage::PlayerDied { player_id, .. } if is_me(&player_id) => {
+                session.is_alive = false;
+                session.died_at = Some(Instant::now());
+                self.stats.lock().unwrap().deaths += 1;
+            }
+            ServerMessage::HealthCheck => {
+                if let Some(sent_at) = session.pings.pop_front() {
+                    self.stats.lock().unwrap().rtts.push(sent_at.elapsed());
+                }
+            }
+            ServerMessage::Ping { seq } => session.pong_due = Some(seq),
+            ServerMessage::NameAlreadyTaken => {
+                self.record_error(&format!("Name {} already taken", self.name));
+            }
+            ServerMessage::Error { message } => self.record_error(&message),
+            ServerMessage::GameEnded { reason } => {
+                self.record_error(&format!("Game ended: {}", reason))
+            }
+            _ => {}
+        }
+    }
+
+    // Per-tick behaviour: keep the connection alive, respawn, wander and shoot
+    async fn act(&self, session: &mut Session) {
+        if session.player_id.is_none() {
+            if session.join_sent_at.elapsed() >= JOIN_RETRY {
+                self.join(session).await;
+            }
+            return;
+        }
+
+        self.ping(session).await;
+
+        if !session.is_alive {
+            let can_respawn = session
+                .died_at
+                .is_some_and(|died_at| died_at.elapsed() >= RESPAWN_WAIT);
+            let retry_due = session
+                .respawn_sent_at
+                .is_none_or(|sent_at| sent_at.elapsed() >= RESPAWN_RETRY);
+            if can_respawn && retry_due {
+                self.send(session, &ClientMessage::Respawn).await;
+                session.respawn_sent_at = Some(Instant::now());
+            }
+            return;
+        }
+
+        let heading = self.walk(session);
+        let rotation = Quat::from_rotation_y(rand::rng().random_range(0.0..std::f32::consts::TAU));
+        let move_msg = ClientMessage::PlayerMove {
+            position: session.position,
+            rotation: heading.unwrap_or(rotation),
+        };
+        self.send(session, &move_msg).await;
+
+        if rand::rng().random_bool(SHOT_CHANCE) {
+            let direction = rotation * Vec3::NEG_Z;
+            let shoot_msg = ClientMessage::PlayerShoot {
+                origin: session.position,
+                direction,
+            };
+            self.send(session, &shoot_msg).await;
+            self.stats.lock().unwrap().shots += 1;
+        }
+    }
+
+    // Round-trip probe using the health check the game client connects with,
+    // also answering the ping the server measures its own round trips with
+    async fn ping(&self, session: &mut Session) {
+        while session
+            .pings
+            .front()
+            .is_some_and(|sent_at| sent_at.elapsed() >= PING_TIMEOUT)
+        {
+            session.pings.pop_front();
+            self.stats.lock().unwrap().pings_lost += 1;
+        }
+
+        if let Some(seq) = session.pong_due.take() {
+            self.send(session, &ClientMessage::Pong { seq }).await;
+        }
+
+        if session.ping_sent_at.elapsed() >= PING_INTERVAL {
+            self.send(session, &ClientMessage::TestHealth).await;
+            session.ping_sent_at = Instant::now();
+            session.pings.push_back(session.ping_sent_at);
+            self.stats.lock().unwrap().pings_sent += 1;
+        }
+    }
+
+    // Move a tick's worth towards a random open cell, returning the way the player faces
+    fn walk(&self, session: &mut Session) -> Option<Quat> {
+        let grid = session.grid.as_ref()?;
+
+        if session.path.is_empty() {
+            let goal = *session.open_cells.choose(&mut rand::rng())?;
+            let y = session.position.y;
+            session.path = world_to_grid(grid, session.position)
+                .and_then(|start| find_path(grid, start, goal))
+                .unwrap_or_default()
+                .into_iter()
+                .skip(1)
+                .rev()
+                .map(|(x, z)| {
+                    let center = grid_to_world(grid, x, z);
+                    Vec3::new(center.x, y, center.z)
+                })
+                .collect();
+        }
+
+        let mut step = PLAYER_SPEED * TICK_INTERVAL.as_secs_f32();
+        let mut heading = None;
+        while step > 0.0
+            && let Some(&waypoint) = session.path.last()
+        {
+            let to_waypoint = waypoint - session.position;
+            let distance = to_waypoint.length();
+            if distance <= step {
+                session.position = waypoint;
+                step -= distance;
+                session.path.pop();
+            } else {
+                session.position += to_waypoint / distance * step;
+                heading = Some(Quat::from_rotation_y(f32::atan2(
+                    -to_waypoint.x,
+                    -to_waypoint.z,
+                )));
+                step = 0.0;
+            }
+        }
+        heading
+    }
+}
@@ -0,0 +1,150 @@
+use shared::ServerStats;
+use std::collections::HashMap;
+use tokio::time::Duration;
+
+// Counters shared by every simulated player
+#[derive(Default)]
+pub struct Stats {
+    pub joined: usize, // Simulated players currently in the game
+    pub joins: u64,
+    pub leaves: u64,
+    pub messages_sent: u64,
+    pub messages_received: u64,
+    pub decode_failures: u64,
+    pub pings_sent: u64,
+    pub pings_lost: u64,
+    pub rtts: Vec<Duration>, // Round trips since the last report
+    pub shots: u64,
+    pub deaths: u64,
+    pub errors: HashMap<String, u64>, // Error messages from the server, by text
+}
+
+impl Stats {
+    pub fn record_error(&mut self, message: &str) {
+        // Group messages that only differ by a number, e.g. respawn countdowns
+        let key: String = message
+            .chars()
+            .map(|c| if c.is_ascii_digit() { '#' } else { c })
+            .collect();
+        *self.errors.entry(key).or_default() += 1;
+    }
+}
+
+fn percentile(sorted: &[Duration], p: f64) -> Duration {
+    let idx = ((sorted.len() - 1) as f64 * p).round() as usize;
+    sorted[idx]
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+pub fn print_report(elapsed: Duration, stats: &mut Stats, server: Option<&ServerStats>) {
+    println!("----- {:.0}s -----", elapsed.as_secs_f64());
+    println!(
+        "Players: {} joined ({} joins, {} leaves), {} shots, {} deaths",
+        stats.joined, stats.joins, stats.leaves, stats.shots, stats.deaths
+    );
+    println!(
+        "Messages: {} sent, {} received, {} undecodable",
+        stats.messages_sent, stats.messages_received, stats.decode_failures
+    );
+
+    let loss = if stats.pings_sent > 0 {
+        stats.pings_lost as f64 / stats.pings_sent as f64 * 100.0
+    } else {
+        0.0
+    };
+    let mut rtts = std::mem::take(&mut stats.rtts);
+    rtts.sort();
+    if rtts.is_empty() {
+        println!("Latency: no replies, {:.1}% packet loss", loss);
+    } else {
+        println!(
+            "Latency: p50 {:.1}ms, p95 {:.1}ms, max {:.1}ms, {:.1}% packet loss",
+            millis(percentile(&rtts, 0.5)),
+            millis(percentile(&rtts, 0.95)),
+            millis(rtts[rtts.len() - 1]),
+            loss
+        );
+    }
+
+    let mut errors: Vec<_> = stats.errors.iter().collect();
+    errors.sort_by(|a, b| b.1.cmp(a.1));
+    for (message, count) in errors {
+        println!("Server error x{}: {}", count, message);
+    }
+
+    match server {
+        Some(server) => {
+            println!(
+                "Server: {} players ({} connections, {} bots), {} spectators, {} spawn points held, {} pending respawns, {} projectiles",
+                server.players,
+                server.connections,
+                server.bots,
+                server.spectators,
+                server.used_spawn_points,
+                server.pending_respawns,
+                server.projectiles
+            );
+            for warning in leak_warnings(server) {
+                println!("⚠️  {}", warning);
+            }
+        }
+        None => println!("Server: no reply to stats query"),
+    }
+}
+
+// Bookkeeping that can't be right while the game is running, whoever is connected
+pub fn leak_warnings(server: &ServerStats) -> Vec<String> {
+    let mut warnings = Vec::new();
+    if server.players != server.connections + server.bots {
+        warnings.push(format!(
+            "{} players but only {} connections and {} bots",
+            server.players, server.connections, server.bots
+        ));
+    }
+    if server.used_spawn_points > server.players {
+        warnings.push(format!(
+            "{} spawn points held by {} players",
+            server.used_spawn_points, server.players
+        ));
+    }
+    if server.pending_respawns > server.players {
+        warnings.push(format!(
+            "{} pending respawns for {} players",
+            server.pending_respawns, server.players
+        ));
+    }
+    warnings
+}
+
+// Once every simulated player has left, nothing they touched should remain
+pub fn leftover_warnings(server: &ServerStats) -> Vec<String> {
+    let mut warnings = Vec::new();
+    if server.connections > 0 {
+        warnings.push(format!(
+            "{} connections left after everyone left",
+            server.connections
+        ));
+    }
+    if server.players > 0 {
+        warnings.push(format!(
+            "{} players left after everyone left",
+            server.players
+        ));
+    }
+    if server.used_spawn_points > 0 {
+        warnings.push(format!(
+            "{} spawn points still held after everyone left",
+            server.used_spawn_points
+        ));
+    }
+    if server.pending_respawns > 0 {
+        warnings.push(format!(
+            "{} pending respawns left after everyone left",
+            server.pending_respawns
+        ));
+    }
+    warnings
+}
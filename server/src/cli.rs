@@ -1,3 +1,8 @@
+use shared::{GameMode, NetworkConditions};
+use std::path::PathBuf;
+use tokio::time::Duration;
+
+use crate::config::{BotSkill, ServerConfig};
 use crate::utils;
 
 #[derive(clap::Parser)]
@@ -16,16 +21,207 @@ pub struct Cli {
     pub difficulty: String,
     #[clap(short, long, help = "Host on local IP")]
     pub local: bool,
+    #[clap(
+        short,
+        long,
+        default_value = "deathmatch",
+        help = "Game mode",
+        long_help = "Game mode played on the server:\n  deathmatch      - Free-for-all, most kills wins\n  team-deathmatch - Red vs Blue, team kills count towards the frag limit\n  capture-the-flag - Red vs Blue, bring the enemy flag back to your base\n  king-of-the-hill - Free-for-all, score by holding the control zone alone\n  team-king-of-the-hill - Red vs Blue, score by holding the control zone\n  elimination - Last player standing wins the round, no respawns mid-round\n  infection - One infected player spreads it with every kill, survivors hold out until time runs out"
+    )]
+    pub mode: String,
+    #[clap(long, help = "Allow players to damage their own teammates")]
+    pub friendly_fire: bool,
+    #[clap(
+        long,
+        default_value = "2",
+        help = "Players required before the warmup ends"
+    )]
+    pub min_players: usize,
+    #[clap(
+        long,
+        default_value = "5",
+        help = "Countdown before a match starts, in seconds"
+    )]
+    pub countdown: u64,
+    #[clap(
+        long,
+        default_value = "600",
+        help = "Match time limit in seconds (0 disables it)"
+    )]
+    pub time_limit: u64,
+    #[clap(
+        long,
+        default_value = "20",
+        help = "Kills needed to win a match (0 disables it)"
+    )]
+    pub frag_limit: u32,
+    #[clap(
+        long,
+        default_value = "3",
+        help = "Flag captures needed to win Capture the Flag (0 disables it)"
+    )]
+    pub capture_limit: u32,
+    #[clap(
+        long,
+        default_value = "100",
+        help = "Hill points needed to win King of the Hill (0 disables it)"
+    )]
+    pub hill_limit: u32,
+    #[clap(
+        long,
+        default_value = "60",
+        help = "Seconds before the King of the Hill zone moves"
+    )]
+    pub hill_rotation: u64,
+    #[clap(
+        long,
+        default_value = "5",
+        help = "Rounds needed to win Elimination (0 disables it)"
+    )]
+    pub round_limit: u32,
+    #[clap(
+        long,
+        default_value = "10",
+        help = "Intermission between matches, in seconds"
+    )]
+    pub intermission: u64,
+    #[clap(
+        long,
+        default_value = "0",
+        help = "Bots to fill the server with, leaving one by one as humans join"
+    )]
+    pub bots: usize,
+    #[clap(
+        long,
+        default_value = "normal",
+        help = "Bot skill level",
+        long_help = "Bot skill sets how fast bots react and how well they aim:\n  easy   - Slow to react, wide aim error\n  normal - Moderate reaction time and aim\n  hard   - Quick to react, tight aim"
+    )]
+    pub bot_skill: String,
+    #[clap(
+        long,
+        value_name = "DIR",
+        help = "Record a replay of every maze played into this directory",
+        long_help = "Record a replay of every maze played into this directory.\nEach replay holds the maze, every tick's player positions and health, and every joined, moved, shot, damaged and died event."
+    )]
+    pub record_replays: Option<PathBuf>,
+    #[clap(
+        long,
+        value_name = "FILE",
+        help = "Star out the words listed in this file from chat",
+        long_help = "Star out the words listed in this file from chat.\nOne word per line, matched whole and ignoring case; blank lines and lines starting with # are skipped."
+    )]
+    pub chat_filter: Option<PathBuf>,
+    #[clap(
+        long,
+        help = "Answer stats queries from anyone, for load testing",
+        long_help = "Answer stats queries from anyone, for load testing.\nThe server's bookkeeping sizes go to whoever asks, joined or not, so only turn this on for servers under test."
+    )]
+    pub expose_stats: bool,
+    #[clap(
+        long,
+        default_value = "0",
+        help = "Simulated one-way latency in milliseconds"
+    )]
+    pub sim_latency: u64,
+    #[clap(
+        long,
+        default_value = "0",
+        help = "Simulated random extra latency of up to this many milliseconds"
+    )]
+    pub sim_jitter: u64,
+    #[clap(
+        long,
+        default_value = "0",
+        help = "Simulated chance of losing a packet, from 0 to 1"
+    )]
+    pub sim_loss: f32,
+    #[clap(
+        long,
+        default_value = "0",
+        help = "Simulated chance of delivering a packet twice, from 0 to 1"
+    )]
+    pub sim_duplication: f32,
+    #[clap(
+        long,
+        default_value = "0",
+        help = "Simulated chance of delivering a packet out of order, from 0 to 1"
+    )]
+    pub sim_reordering: f32,
 }
 
 impl Cli {
     pub fn validate(&self) -> Result<(), String> {
         match self.difficulty.as_str() {
-            "easy" | "medium" | "hard" => Ok(()),
-            _ => Err(format!(
-                "Invalid difficulty '{}'. Valid options are: easy, medium, hard",
-                self.difficulty
-            )),
+            "easy" | "medium" | "hard" => {}
+            _ => {
+                return Err(format!(
+                    "Invalid difficulty '{}'. Valid options are: easy, medium, hard",
+                    self.difficulty
+                ));
+            }
+        }
+
+        if parse_game_mode(&self.mode).is_none() {
+            return Err(format!(
+                "Invalid mode '{}'. Valid options are: deathmatch, team-deathmatch, capture-the-flag, king-of-the-hill, team-king-of-the-hill, elimination, infection",
+                self.mode
+            ));
+        }
+
+        if parse_bot_skill(&self.bot_skill).is_none() {
+            return Err(format!(
+                "Invalid bot skill '{}'. Valid options are: easy, normal, hard",
+                self.bot_skill
+            ));
+        }
+
+        if self.min_players == 0 {
+            return Err("--min-players must be at least 1".to_string());
+        }
+
+        self.network_conditions().validate()?;
+
+        Ok(())
+    }
+
+    // Build once after validating, it reads the chat filter from disk
+    pub fn server_config(&self) -> Result<ServerConfig, String> {
+        let blocked_words = match &self.chat_filter {
+            Some(path) => utils::load_blocked_words(path)
+                .map_err(|e| format!("Cannot read chat filter '{}': {}", path.display(), e))?,
+            None => Vec::new(),
+        };
+
+        Ok(ServerConfig {
+            difficulty: self.difficulty.clone(),
+            mode: parse_game_mode(&self.mode).unwrap_or(GameMode::Deathmatch),
+            friendly_fire: self.friendly_fire,
+            min_players: self.min_players,
+            countdown: Duration::from_secs(self.countdown),
+            time_limit: (self.time_limit > 0).then(|| Duration::from_secs(self.time_limit)),
+            frag_limit: (self.frag_limit > 0).then_some(self.frag_limit),
+            capture_limit: (self.capture_limit > 0).then_some(self.capture_limit),
+            hill_limit: (self.hill_limit > 0).then_some(self.hill_limit),
+            hill_rotation: Duration::from_secs(self.hill_rotation.max(1)),
+            round_limit: (self.round_limit > 0).then_some(self.round_limit),
+            intermission: Duration::from_secs(self.intermission),
+            bots: self.bots,
+            bot_skill: parse_bot_skill(&self.bot_skill).unwrap_or(BotSkill::Normal),
+            replay_dir: self.record_replays.clone(),
+            blocked_words,
+            expose_stats: self.expose_stats,
+        })
+    }
+
+    // Bad network to simulate on every packet the server sends and receives
+    pub fn network_conditions(&self) -> NetworkConditions {
+        NetworkConditions {
+            latency: Duration::from_millis(self.sim_latency),
+            jitter: Duration::from_millis(self.sim_jitter),
+            loss: self.sim_loss,
+            duplication: self.sim_duplication,
+            reordering: self.sim_reordering,
         }
     }
 
@@ -36,3 +232,25 @@ impl Cli {
         self.host.clone()
     }
 }
+
+fn parse_game_mode(mode: &str) -> Option<GameMode> {
+    match mode {
+        "deathmatch" | "dm" => Some(GameMode::Deathmatch),
+        "team-deathmatch" | "tdm" => Some(GameMode::TeamDeathmatch),
+        "capture-the-flag" | "ctf" => Some(GameMode::CaptureTheFlag),
+        "king-of-the-hill" | "koth" => Some(GameMode::KingOfTheHill),
+        "team-king-of-the-hill" | "tkoth" => Some(GameMode::TeamKingOfTheHill),
+        "elimination" | "lms" => Some(GameMode::Elimination),
+        "infection" => Some(GameMode::Infection),
+        _ => None,
+    }
+}
+
+fn parse_bot_skill(skill: &str) -> Option<BotSkill> {
+    match skill {
+        "easy" => Some(BotSkill::Easy),
+        "normal" => Some(BotSkill::Normal),
+        "hard" => Some(BotSkill::Hard),
+        _ => None,
+    }
+}
@@ -0,0 +1,77 @@
+use shared::GameMode;
+use std::path::PathBuf;
+use tokio::time::Duration;
+
+// Match and gameplay settings resolved from the command line
+#[derive(Debug, Clone)]
+pub struct ServerConfig {
+    pub difficulty: String,
+    pub mode: GameMode,
+    pub friendly_fire: bool,
+    pub min_players: usize,
+    pub countdown: Duration,
+    pub time_limit: Option<Duration>, // None means no time limit
+    pub frag_limit: Option<u32>,      // None means no frag limit
+    pub capture_limit: Option<u32>,   // Capture the Flag only
+    pub hill_limit: Option<u32>,      // King of the Hill points to win
+    pub hill_rotation: Duration,      // How long the hill stays in one place
+    pub round_limit: Option<u32>,     // Elimination rounds to win
+    pub intermission: Duration,
+    pub bots: usize, // Server-side players filling in for missing humans
+    pub bot_skill: BotSkill,
+    pub replay_dir: Option<PathBuf>, // Where match replays are recorded, if anywhere
+    pub blocked_words: Vec<String>,  // Starred out of chat, lowercase
+    pub expose_stats: bool,          // Answer QueryStats, off so strangers can't read or amplify
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            difficulty: "medium".to_string(),
+            mode: GameMode::Deathmatch,
+            friendly_fire: false,
+            min_players: 2,
+            countdown: Duration::from_secs(5),
+            time_limit: Some(Duration::from_secs(600)),
+            frag_limit: Some(20),
+            capture_limit: Some(3),
+            hill_limit: Some(100),
+            hill_rotation: Duration::from_secs(60),
+            round_limit: Some(5),
+            intermission: Duration::from_secs(10),
+            bots: 0,
+            bot_skill: BotSkill::Normal,
+            replay_dir: None,
+            blocked_words: Vec::new(),
+            expose_stats: false,
+        }
+    }
+}
+
+// How quickly and accurately bots fight back
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BotSkill {
+    Easy,
+    Normal,
+    Hard,
+}
+
+impl BotSkill {
+    // Delay between first seeing an enemy and opening fire
+    pub fn reaction_time(&self) -> Duration {
+        match self {
+            BotSkill::Easy => Duration::from_millis(900),
+            BotSkill::Normal => Duration::from_millis(500),
+            BotSkill::Hard => Duration::from_millis(250),
+        }
+    }
+
+    // Cone half-angle in degrees that a bot's aim wanders within
+    pub fn aim_error(&self) -> f32 {
+        match self {
+            BotSkill::Easy => 9.0,
+            BotSkill::Normal => 5.0,
+            BotSkill::Hard => 2.0,
+        }
+    }
+}
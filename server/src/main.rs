@@ -1,13 +1,15 @@
 use clap::Parser;
+use shared::{SimulatedTransport, Transport};
 use tokio::signal;
 
 mod cli;
+mod config;
 mod server;
 mod utils;
 
 use cli::Cli;
 use server::GameServer;
-use utils::{create_udp_server_socket, print_info};
+use utils::{create_udp_server_transport, print_info};
 
 #[tokio::main]
 async fn main() {
@@ -16,15 +18,24 @@ async fn main() {
     let host = cli.get_host().await;
 
     // Validate CLI arguments
-    if let Err(error) = cli.validate() {
-        eprintln!("Error: {}", error);
-        std::process::exit(1);
-    }
-
-    print_info(&cli);
-
-    let listener_socket = create_udp_server_socket(&host, cli.port).await;
-    let mut listener = GameServer::new(listener_socket, cli.difficulty);
+    let config = match cli.validate().and_then(|()| cli.server_config()) {
+        Ok(config) => config,
+        Err(error) => {
+            eprintln!("Error: {}", error);
+            std::process::exit(1);
+        }
+    };
+
+    print_info(&cli, &config);
+
+    let udp = create_udp_server_transport(&host, cli.port);
+    let conditions = cli.network_conditions();
+    let transport: Box<dyn Transport> = if conditions.is_perfect() {
+        Box::new(udp)
+    } else {
+        Box::new(SimulatedTransport::new(udp, conditions))
+    };
+    let mut listener = GameServer::new(transport, config);
 
     // Setup signal handling for graceful shutdown
     tokio::select! {
@@ -1,68 +1,192 @@
 use bevy::math::{Quat, Vec3};
 use rand::Rng;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::net::SocketAddr;
-use tokio::net::UdpSocket;
+use std::sync::Mutex;
 use tokio::time::{Duration, Instant};
 use uuid::Uuid;
 
 use shared::{
-    ClientMessage, GameState, HitscanResult, MAZE_HEIGHT, MAZE_WIDTH, MazeConfig, MazeData, Player,
-    ServerMessage, SpawnPoint, WeaponConfig, generate_maze_from_config,
+    ClientMessage, ControlZone, DamageEvent, FlagState, GameMode, GameState, MAZE_HEIGHT,
+    MAZE_WIDTH, MazeConfig, MazeData, PickupState, Player, ServerMessage, ServerStats, SpawnPoint,
+    Team, Transport, WeaponKind, central_spawn, generate_maze_from_config, spread_direction,
+    wall_hit_distance, weapons,
 };
 
+use crate::config::ServerConfig;
 use crate::utils::{log_error, log_info};
 
+mod ammo;
+mod bots;
+mod chat;
+mod combat;
+mod ctf;
+mod infection;
+mod killcam;
+mod koth;
+mod pickups;
+mod pings;
+mod projectiles;
+mod replay;
+mod rounds;
+mod spectators;
+mod streaks;
+mod teams;
+#[cfg(test)]
+mod tests;
+
+const TICK_INTERVAL: Duration = Duration::from_millis(50); // 20 ticks per second
+const FIRE_RATE_TOLERANCE: f64 = 0.8; // Accept shots arriving slightly early
+const RESPAWN_DELAY: Duration = Duration::from_secs(3);
+
+// Wall-clock seconds, used for timestamps stored on players
+fn unix_time_secs() -> f64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs_f64()
+}
+
 pub struct GameServer {
-    listener: UdpSocket,
+    transport: Box<dyn Transport>,
+    config: ServerConfig,
     players: HashMap<String, Player>,
     addr_to_id: HashMap<SocketAddr, String>,
+    spectators: HashMap<SocketAddr, String>, // Name of everyone watching without playing
     state: GameState,
-    difficulty: String,
+    state_changed_at: Instant,
     game_start_time: Option<f64>,
     maze_seed: Option<u64>,
     maze_data: Option<MazeData>,
-    used_spawn_points: Vec<usize>,
+    used_spawn_points: HashMap<String, usize>, // Spawn point each player was last placed at
     pending_respawns: HashMap<String, Instant>,
+    team_scores: HashMap<Team, u32>,
+    flags: HashMap<Team, FlagState>,
+    flag_dropped_at: HashMap<Team, Instant>,
+    zone: Option<ControlZone>,
+    zone_moved_at: Instant,
+    zone_synced_at: Instant,
+    zone_score_timer: f32, // Seconds of uncontested holding not yet turned into points
+    round: u32,
+    round_ended_at: Option<Instant>,
+    projectiles: Vec<projectiles::LiveProjectile>,
+    next_projectile_id: u32,
+    pickups: Vec<PickupState>,
+    pickup_respawns: HashMap<u32, Instant>, // When each collected pickup comes back
+    bots: HashMap<String, bots::Bot>,       // Keyed by player id, bots have no address
+    histories: HashMap<String, killcam::History>, // Recent movement and shots, for killcams
+    chat_sent: HashMap<SocketAddr, VecDeque<Instant>>, // Recent chat from each client, for rate limiting
+    ping_round: (u32, Instant), // Sequence number of the last ping and when it went out
+    replay: Mutex<Option<replay::Recorder>>, // Locked because events are recorded while broadcasting
 }
 
 impl GameServer {
-    pub fn new(listener: UdpSocket, difficulty: String) -> Self {
-        Self {
-            listener,
+    pub fn new(transport: Box<dyn Transport>, config: ServerConfig) -> Self {
+        let mut server = Self {
+            transport,
+            config,
             players: HashMap::new(),
             addr_to_id: HashMap::new(),
+            spectators: HashMap::new(),
             state: GameState::WaitingForPlayers,
-            difficulty,
+            state_changed_at: Instant::now(),
             game_start_time: None,
             maze_seed: None,
             maze_data: None,
-            used_spawn_points: Vec::new(),
+            used_spawn_points: HashMap::new(),
             pending_respawns: HashMap::new(),
-        }
+            team_scores: HashMap::new(),
+            flags: HashMap::new(),
+            flag_dropped_at: HashMap::new(),
+            zone: None,
+            zone_moved_at: Instant::now(),
+            zone_synced_at: Instant::now(),
+            zone_score_timer: 0.0,
+            round: 0,
+            round_ended_at: None,
+            projectiles: Vec::new(),
+            next_projectile_id: 0,
+            pickups: Vec::new(),
+            pickup_respawns: HashMap::new(),
+            bots: HashMap::new(),
+            histories: HashMap::new(),
+            chat_sent: HashMap::new(),
+            ping_round: (0, Instant::now()),
+            replay: Mutex::new(None),
+        };
+        server.reset_team_scores();
+        server
     }
 
     pub async fn listen_and_serve(&mut self) {
+        let mut ticker = tokio::time::interval(TICK_INTERVAL);
         loop {
-            let mut buf = [0; 4096];
-            let (amt, addr) = self.listener.recv_from(&mut buf).await.unwrap();
-            if let Ok((client_msg, _)) =
-                bincode::serde::decode_from_slice(&buf[..amt], bincode::config::standard())
-            {
-                self.mux(addr, client_msg).await;
+            // Wait for either incoming packets or the next game tick
+            tokio::select! {
+                readable = self.transport.readable() => match readable {
+                    Ok(()) => self.receive_packets().await,
+                    Err(e) => log_error(&format!("ERROR: Failed to wait for packets: {}", e)),
+                },
+                _ = ticker.tick() => self.tick().await,
             }
         }
     }
 
+    // Handle every packet that has arrived, until the transport runs dry
+    async fn receive_packets(&mut self) {
+        loop {
+            match self.transport.try_recv_from() {
+                Ok(Some((packet, addr))) => {
+                    if let Ok((client_msg, _)) =
+                        bincode::serde::decode_from_slice(&packet, bincode::config::standard())
+                    {
+                        self.mux(addr, client_msg).await;
+                    }
+                }
+                Ok(None) => break,
+                Err(e) => {
+                    log_error(&format!("ERROR: Failed to receive packet: {}", e));
+                    break;
+                }
+            }
+        }
+    }
+
+    // Runs once per tick for time-driven game logic
+    async fn tick(&mut self) {
+        self.update_match_state().await;
+        self.update_flags().await;
+        self.update_zone().await;
+        self.update_round().await;
+        self.update_infection().await;
+        self.update_reloads().await;
+        self.update_projectiles().await;
+        self.update_hazards().await;
+        self.update_pickups().await;
+        self.update_bots().await;
+        self.update_pings().await;
+        self.record_histories();
+        self.record_snapshot();
+    }
+
     // this handles messages, and replies accordingly
     async fn mux(&mut self, addr: SocketAddr, msg: ClientMessage) {
         match msg {
             ClientMessage::TestHealth => {
                 self.handle_test_health(addr).await;
             }
+            ClientMessage::QueryStats => {
+                self.handle_query_stats(addr).await;
+            }
+            ClientMessage::CheckName { name } => {
+                self.handle_check_name(addr, name).await;
+            }
             ClientMessage::JoinGame { player_name } => {
                 self.handle_join_game(addr, player_name).await;
             }
+            ClientMessage::JoinAsSpectator { spectator_name } => {
+                self.handle_join_as_spectator(addr, spectator_name).await;
+            }
             ClientMessage::LeaveGame => {
                 self.handle_leave_game(addr).await;
             }
@@ -72,9 +196,21 @@ impl GameServer {
             ClientMessage::PlayerShoot { origin, direction } => {
                 self.handle_player_shoot(addr, origin, direction).await;
             }
+            ClientMessage::SwitchWeapon { weapon } => {
+                self.handle_switch_weapon(addr, weapon).await;
+            }
+            ClientMessage::Reload => {
+                self.handle_reload(addr).await;
+            }
             ClientMessage::Respawn => {
                 self.handle_respawn(addr).await;
             }
+            ClientMessage::Pong { seq } => {
+                self.handle_pong(addr, seq);
+            }
+            ClientMessage::Chat { channel, text } => {
+                self.handle_chat(addr, channel, text).await;
+            }
         }
     }
 
@@ -87,7 +223,7 @@ impl GameServer {
             }
         };
 
-        match self.listener.send_to(&encoded, addr).await {
+        match self.transport.send_to(&encoded, addr) {
             Ok(bytes_sent) => {
                 if bytes_sent != encoded.len() {
                     log_info(&format!(
@@ -104,49 +240,345 @@ impl GameServer {
         }
     }
 
+    fn player_addr(&self, player_id: &str) -> Option<SocketAddr> {
+        self.addr_to_id
+            .iter()
+            .find(|(_, id)| id.as_str() == player_id)
+            .map(|(addr, _)| *addr)
+    }
+
+    // Everyone connected, players and spectators alike
+    fn client_addrs(&self) -> impl Iterator<Item = &SocketAddr> {
+        self.addr_to_id.keys().chain(self.spectators.keys())
+    }
+
     async fn broadcast(&self, msg: &ServerMessage) {
-        for addr in self.addr_to_id.keys() {
+        self.record_event(msg);
+        for addr in self.client_addrs() {
             self.send_message(*addr, msg).await;
         }
     }
 
     async fn broadcast_to_others(&self, exclude_addr: SocketAddr, msg: &ServerMessage) {
-        for addr in self.addr_to_id.keys() {
+        self.record_event(msg);
+        for addr in self.client_addrs() {
             if *addr != exclude_addr {
                 self.send_message(*addr, msg).await;
             }
         }
     }
 
+    fn is_spawn_point_used(&self, idx: usize) -> bool {
+        self.used_spawn_points.values().any(|&used| used == idx)
+    }
+
     // Get a random available spawn point
-    fn get_random_spawn_point(&mut self) -> Option<SpawnPoint> {
+    fn get_random_spawn_point(&mut self, player_id: &str) -> Option<SpawnPoint> {
         if let Some(maze_data) = &self.maze_data {
             let available_points: Vec<usize> = (0..maze_data.spawn_points.len())
-                .filter(|i| !self.used_spawn_points.contains(i))
+                .filter(|i| !self.is_spawn_point_used(*i))
                 .collect();
 
             if !available_points.is_empty() {
                 let mut rng = rand::thread_rng();
                 let selected_idx = available_points[rng.gen_range(0..available_points.len())];
-                self.used_spawn_points.push(selected_idx);
+                self.used_spawn_points
+                    .insert(player_id.to_string(), selected_idx);
                 return Some(maze_data.spawn_points[selected_idx].clone());
             }
         }
         None
     }
 
+    // Team players spawn away from enemies, everyone else spawns randomly.
+    // A player only ever holds one spawn point, so respawning frees the last one.
+    fn get_spawn_point(&mut self, player_id: &str, team: Option<Team>) -> Option<SpawnPoint> {
+        self.release_spawn_point(player_id);
+        team.and_then(|team| self.get_team_spawn_point(player_id, team))
+            .or_else(|| self.get_random_spawn_point(player_id))
+    }
+
     // Release a spawn point when player leaves
-    fn release_spawn_point(&mut self, position: Vec3) {
-        if let Some(maze_data) = &self.maze_data {
-            for (idx, spawn_point) in maze_data.spawn_points.iter().enumerate() {
-                let distance = spawn_point.position.distance(position);
-                if distance < 2.0 {
-                    // Close enough to be the same spawn point
-                    self.used_spawn_points.retain(|&x| x != idx);
-                    break;
+    fn release_spawn_point(&mut self, player_id: &str) {
+        self.used_spawn_points.remove(player_id);
+    }
+
+    fn generate_maze(&mut self, seed: u64) {
+        let config = MazeConfig::new(seed, MAZE_WIDTH, MAZE_HEIGHT, &self.config.difficulty);
+        self.maze_seed = Some(seed);
+        self.maze_data = Some(generate_maze_from_config(&config));
+        self.start_replay(&config);
+        self.used_spawn_points.clear();
+        self.reset_flags();
+        self.reset_zone();
+        self.reset_pickups();
+    }
+
+    // Generate maze data if not already generated
+    fn ensure_maze(&mut self) {
+        if self.maze_data.is_none() {
+            let seed = self.maze_seed.unwrap_or_else(|| {
+                std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap()
+                    .as_secs()
+            });
+            self.generate_maze(seed);
+        }
+    }
+
+    fn maze_message(&self) -> Option<ServerMessage> {
+        self.maze_seed.map(|seed| ServerMessage::GameStarted {
+            seed,
+            width: MAZE_WIDTH,
+            height: MAZE_HEIGHT,
+            difficulty: self.config.difficulty.clone(),
+        })
+    }
+
+    fn game_state_message(&self) -> ServerMessage {
+        ServerMessage::GameState {
+            players: self.players.clone(),
+            state: self.state.clone(),
+            game_start_time: self.game_start_time,
+            team_scores: self.team_scores.clone(),
+        }
+    }
+
+    // How long the current match phase lasts, if it is timed
+    fn state_duration(&self) -> Option<Duration> {
+        match self.state {
+            GameState::WaitingForPlayers => None,
+            GameState::Countdown => Some(self.config.countdown),
+            GameState::GameStarted => self.config.time_limit,
+            GameState::GameOver => Some(self.config.intermission),
+        }
+    }
+
+    fn match_state_message(&self) -> ServerMessage {
+        let time_remaining = self.state_duration().map(|duration| {
+            duration
+                .saturating_sub(self.state_changed_at.elapsed())
+                .as_secs_f32()
+        });
+
+        ServerMessage::MatchStateChanged {
+            state: self.state.clone(),
+            mode: self.config.mode,
+            time_remaining,
+            score_limit: self.score_limit(),
+        }
+    }
+
+    // Objective modes race their objective, every other mode races frags
+    fn score_limit(&self) -> Option<u32> {
+        match self.config.mode {
+            GameMode::CaptureTheFlag => self.config.capture_limit,
+            GameMode::KingOfTheHill | GameMode::TeamKingOfTheHill => self.config.hill_limit,
+            GameMode::Elimination => self.config.round_limit,
+            GameMode::Infection => None, // Decided by the clock or a full outbreak
+            _ => self.config.frag_limit,
+        }
+    }
+
+    async fn set_state(&mut self, state: GameState) {
+        log_info(&format!("Match state: {:?} -> {:?}", self.state, state));
+        self.state = state;
+        self.state_changed_at = Instant::now();
+
+        let match_msg = self.match_state_message();
+        self.broadcast(&match_msg).await;
+    }
+
+    // Drive the match lifecycle: warmup -> countdown -> match -> intermission -> new round
+    async fn update_match_state(&mut self) {
+        let elapsed = self.state_changed_at.elapsed();
+        let enough_players = self.players.len() >= self.config.min_players;
+
+        match self.state {
+            GameState::WaitingForPlayers => {
+                if enough_players {
+                    self.set_state(GameState::Countdown).await;
                 }
             }
+            GameState::Countdown => {
+                if !enough_players {
+                    self.set_state(GameState::WaitingForPlayers).await;
+                } else if elapsed >= self.config.countdown {
+                    self.start_match().await;
+                }
+            }
+            GameState::GameStarted => {
+                if self.players.is_empty() {
+                    self.game_start_time = None;
+                    self.set_state(GameState::WaitingForPlayers).await;
+                } else if self
+                    .config
+                    .time_limit
+                    .is_some_and(|time_limit| elapsed >= time_limit)
+                {
+                    self.end_match("Time limit reached").await;
+                }
+            }
+            GameState::GameOver => {
+                if elapsed >= self.config.intermission {
+                    self.start_new_round().await;
+                }
+            }
+        }
+    }
+
+    // Countdown finished: wipe warmup stats and put everyone back into play
+    async fn start_match(&mut self) {
+        self.game_start_time = Some(unix_time_secs());
+
+        for player in self.players.values_mut() {
+            player.kills = 0;
+            player.deaths = 0;
+            player.assists = 0;
+            player.streak = 0;
+            player.score = 0;
+            player.health = player.max_health;
+        }
+        self.reset_team_scores();
+        self.reset_flags();
+        self.reset_zone();
+        self.round = 1;
+        self.round_ended_at = None;
+        self.reset_infection();
+        let patient_zero = if self.config.mode == GameMode::Infection {
+            self.pick_patient_zero()
+        } else {
+            None
+        };
+
+        let dead_players: Vec<String> = self
+            .players
+            .values()
+            .filter(|p| !p.is_alive)
+            .map(|p| p.id.clone())
+            .collect();
+        for player_id in dead_players {
+            self.respawn_player(&player_id).await;
+        }
+
+        self.set_state(GameState::GameStarted).await;
+        let state_msg = self.game_state_message();
+        self.broadcast(&state_msg).await;
+        // Every spawner starts the match stocked
+        self.reset_pickups();
+        let pickups_msg = self.pickup_states_message();
+        self.broadcast(&pickups_msg).await;
+        if !self.flags.is_empty() {
+            let flags_msg = self.flag_states_message();
+            self.broadcast(&flags_msg).await;
+        }
+        self.broadcast_zone(true).await;
+        if self.config.mode == GameMode::Elimination {
+            let round_msg = ServerMessage::RoundStarted { round: self.round };
+            self.broadcast(&round_msg).await;
+        }
+        if let Some(player_id) = patient_zero {
+            let infected_msg = ServerMessage::PlayerInfected {
+                player_id,
+                infected_by: None,
+            };
+            self.broadcast(&infected_msg).await;
+        }
+    }
+
+    async fn end_match(&mut self, reason: &str) {
+        let winner_id = self
+            .players
+            .values()
+            .max_by_key(|p| (p.score, p.kills, std::cmp::Reverse(p.deaths)))
+            .map(|p| p.id.clone());
+
+        let winning_team = if self.config.mode == GameMode::Infection {
+            Some(self.surviving_team())
+        } else if self.config.mode.is_team_mode() {
+            self.leading_team()
+        } else {
+            None
+        };
+
+        log_info(&format!("Match over: {}", reason));
+        self.set_state(GameState::GameOver).await;
+
+        let results_msg = ServerMessage::MatchResults {
+            winner_id,
+            winning_team,
+            reason: reason.to_string(),
+            players: self.players.clone(),
+        };
+        self.broadcast(&results_msg).await;
+    }
+
+    // Called after each kill or capture during a match
+    async fn check_score_limit(&mut self) {
+        if self.state != GameState::GameStarted {
+            return;
         }
+
+        // Team modes race the team totals, free-for-all races individual scores
+        let best_score = if self.config.mode.is_team_mode() {
+            self.team_scores.values().copied().max()
+        } else if self.config.mode.has_control_zone() || self.config.mode == GameMode::Elimination {
+            self.players.values().map(|p| p.score).max()
+        } else {
+            self.players.values().map(|p| p.kills).max()
+        };
+
+        if let Some(score_limit) = self.score_limit()
+            && best_score.is_some_and(|score| score >= score_limit)
+        {
+            let reason = match self.config.mode {
+                GameMode::CaptureTheFlag => "Capture limit reached",
+                GameMode::KingOfTheHill | GameMode::TeamKingOfTheHill => "Hill limit reached",
+                GameMode::Elimination => "Round limit reached",
+                _ => "Frag limit reached",
+            };
+            self.end_match(reason).await;
+        }
+    }
+
+    // Intermission finished: fresh maze, everyone respawned, back to warmup
+    async fn start_new_round(&mut self) {
+        self.game_start_time = None;
+        self.state = GameState::WaitingForPlayers;
+        self.state_changed_at = Instant::now();
+        self.reset_infection();
+        self.load_new_maze().await;
+
+        let match_msg = self.match_state_message();
+        self.broadcast(&match_msg).await;
+        if !self.flags.is_empty() {
+            let flags_msg = self.flag_states_message();
+            self.broadcast(&flags_msg).await;
+        }
+        self.broadcast_zone(true).await;
+    }
+
+    // Fresh random maze with every player respawned in it
+    async fn load_new_maze(&mut self) {
+        self.generate_maze(rand::random());
+        self.pending_respawns.clear();
+        self.clear_projectiles().await;
+        self.reset_bots();
+
+        let player_ids: Vec<String> = self.players.keys().cloned().collect();
+        for player_id in player_ids {
+            self.respawn_player(&player_id).await;
+        }
+
+        // Positions first so clients place themselves once the new maze is built
+        let state_msg = self.game_state_message();
+        self.broadcast(&state_msg).await;
+        if let Some(maze_msg) = self.maze_message() {
+            self.broadcast(&maze_msg).await;
+        }
+        let pickups_msg = self.pickup_states_message();
+        self.broadcast(&pickups_msg).await;
     }
 
     // TestHealth makes sure server is running
@@ -155,6 +587,60 @@ impl GameServer {
         self.send_message(addr, &health_msg).await;
     }
 
+    // Only when enabled: the answer is bigger than the question and nobody has to join
+    async fn handle_query_stats(&mut self, addr: SocketAddr) {
+        if !self.config.expose_stats {
+            return;
+        }
+        let stats = ServerStats {
+            players: self.players.len(),
+            connections: self.addr_to_id.len(),
+            bots: self.bots.len(),
+            spectators: self.spectators.len(),
+            used_spawn_points: self.used_spawn_points.len(),
+            pending_respawns: self.pending_respawns.len(),
+            projectiles: self.projectiles.len(),
+        };
+        let stats_msg = ServerMessage::ServerStats { stats };
+        self.send_message(addr, &stats_msg).await;
+    }
+
+    // A fresh player on a balanced team, placed at a spawn point in the current maze
+    fn create_player(&mut self, player_name: String) -> Player {
+        // Random color, or auto-balanced onto a team
+        let mut player = Player::new(Uuid::new_v4().to_string(), player_name);
+        if self.config.mode.is_team_mode() {
+            player.join_team(self.pick_balanced_team());
+        }
+
+        // Infection latecomers join the outbreak while there are survivors to hunt,
+        // warmup players start healthy
+        if self.config.mode == GameMode::Infection {
+            if self.state == GameState::GameStarted
+                && self.players.values().any(|p| !p.is_infected())
+            {
+                player.infect();
+            } else {
+                player.cure();
+            }
+            player.health = player.max_health;
+        }
+
+        // Nobody joins an elimination round already in progress
+        if self.is_round_in_progress() {
+            player.is_alive = false;
+        }
+
+        self.ensure_maze();
+
+        // Assign spawn point to player
+        if let Some(spawn_point) = self.get_spawn_point(&player.id, player.team) {
+            player.position = spawn_point.position;
+            player.rotation = spawn_point.rotation;
+        }
+        player
+    }
+
     // Handler methods for each message type
     async fn handle_join_game(&mut self, addr: SocketAddr, player_name: String) {
         log_info(&format!("Player {} joined", player_name));
@@ -168,35 +654,18 @@ impl GameServer {
         }
 
         // check if name is taken
-        if self.players.values().any(|p| p.name == player_name) {
+        if self.is_name_taken(&player_name, Some(addr)) {
             let error_msg = ServerMessage::NameAlreadyTaken;
             self.send_message(addr, &error_msg).await;
             return;
         }
 
-        // Create new player with random color
-        let player_id = Uuid::new_v4().to_string();
-        let mut player = Player::new(player_id.clone(), player_name.clone());
+        // A spectator picking up the game stops watching first
+        self.remove_spectator(addr).await;
 
-        // Generate maze data if not already generated
-        if self.maze_data.is_none() {
-            if self.maze_seed.is_none() {
-                self.maze_seed = Some(
-                    std::time::SystemTime::now()
-                        .duration_since(std::time::UNIX_EPOCH)
-                        .unwrap()
-                        .as_secs() as u64,
-                );
-            }
-            let config = MazeConfig::new(self.maze_seed.unwrap(), 12, 12, &self.difficulty);
-            self.maze_data = Some(generate_maze_from_config(&config));
-        }
-
-        // Assign random spawn point to player
-        if let Some(spawn_point) = self.get_random_spawn_point() {
-            player.position = spawn_point.position;
-            player.rotation = spawn_point.rotation;
-        }
+        let player = self.create_player(player_name.clone());
+        let player_id = player.id.clone();
+        let sit_out_round = !player.is_alive;
 
         // Add player
         log_info(&format!("Player {} joined", player_name));
@@ -205,7 +674,9 @@ impl GameServer {
 
         // Send join confirmation
         log_info(&format!("sending GameJoined to {}", player_name));
-        let join_msg = ServerMessage::GameJoined { player_id };
+        let join_msg = ServerMessage::GameJoined {
+            player_id: player_id.clone(),
+        };
         self.send_message(addr, &join_msg).await;
 
         // Broadcast player joined to others
@@ -215,301 +686,233 @@ impl GameServer {
         };
         self.broadcast_to_others(addr, &joined_msg).await;
 
-        // Send current game state to new player FIRST
-        log_info(&format!("sending GameState to {}", player_name));
-        let state_msg = ServerMessage::GameState {
-            players: self.players.clone(),
-            state: self.state.clone(),
-            game_start_time: self.game_start_time,
-        };
+        self.send_match_to(addr).await;
+        self.send_ammo(&player_id).await;
+        if sit_out_round {
+            let wait_msg = ServerMessage::Error {
+                message: "Round in progress - you will join the next round".to_string(),
+            };
+            self.send_message(addr, &wait_msg).await;
+        }
+    }
+
+    // Everything a newcomer needs to see the match as it stands
+    async fn send_match_to(&self, addr: SocketAddr) {
+        // Send current game state FIRST
+        let state_msg = self.game_state_message();
         self.send_message(addr, &state_msg).await;
 
         // Add a small delay to ensure GameState is processed before GameStarted
         tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
 
-        // Then if game has already started, send maze info to new player
-        if matches!(self.state, GameState::GameStarted) {
-            if let Some(seed) = self.maze_seed {
-                log_info(&format!("sending GameStarted to {}", player_name));
-                let maze_msg = ServerMessage::GameStarted {
-                    seed,
-                    width: MAZE_WIDTH,
-                    height: MAZE_HEIGHT,
-                    difficulty: self.difficulty.clone(),
-                };
-                self.send_message(addr, &maze_msg).await;
-            }
+        // Then send maze info so the newcomer can play the warmup or join mid-match
+        if let Some(maze_msg) = self.maze_message() {
+            self.send_message(addr, &maze_msg).await;
         }
 
-        // Check if game can start (only if not already started)
-        if self.players.len() >= 1 && !matches!(self.state, GameState::GameStarted) {
-            self.state = GameState::GameStarted;
-            self.game_start_time = Some(
-                std::time::SystemTime::now()
-                    .duration_since(std::time::UNIX_EPOCH)
-                    .unwrap()
-                    .as_secs_f64(),
-            );
-
-            let start_msg = ServerMessage::GameStarted {
-                seed: self.maze_seed.unwrap(),
-                width: MAZE_WIDTH,
-                height: MAZE_HEIGHT,
-                difficulty: self.difficulty.clone(),
-            };
-            self.broadcast(&start_msg).await;
+        // Let the newcomer's HUD know where the match lifecycle is
+        let match_msg = self.match_state_message();
+        self.send_message(addr, &match_msg).await;
+
+        let pickups_msg = self.pickup_states_message();
+        self.send_message(addr, &pickups_msg).await;
+        if !self.flags.is_empty() {
+            let flags_msg = self.flag_states_message();
+            self.send_message(addr, &flags_msg).await;
+        }
+        if let Some(zone_msg) = self.zone_message(false) {
+            self.send_message(addr, &zone_msg).await;
         }
+        if self.config.mode == GameMode::Elimination && self.state == GameState::GameStarted {
+            let round_msg = ServerMessage::RoundStarted { round: self.round };
+            self.send_message(addr, &round_msg).await;
+        }
+        let spectators_msg = self.spectators_message();
+        self.send_message(addr, &spectators_msg).await;
     }
 
     async fn handle_leave_game(&mut self, addr: SocketAddr) {
         if let Some(player_id) = self.addr_to_id.remove(&addr) {
-            if let Some(player) = self.players.remove(&player_id) {
-                // Release the spawn point for reuse
-                self.release_spawn_point(player.position);
-
-                let left_msg = ServerMessage::PlayerLeft {
-                    player_id: player.id,
-                };
-                self.broadcast(&left_msg).await;
-            }
+            self.remove_player(&player_id).await;
+        }
+        self.remove_spectator(addr).await;
+        self.chat_sent.remove(&addr);
+    }
+
+    async fn remove_player(&mut self, player_id: &str) {
+        // Leave any carried flag where the player was standing
+        self.drop_flag(player_id).await;
+
+        self.pending_respawns.remove(player_id);
+        self.bots.remove(player_id);
+        self.histories.remove(player_id);
+        if let Some(player) = self.players.remove(player_id) {
+            // Release the spawn point for reuse
+            self.release_spawn_point(&player.id);
+
+            let left_msg = ServerMessage::PlayerLeft {
+                player_id: player.id,
+            };
+            self.broadcast(&left_msg).await;
         }
     }
 
     async fn handle_player_move(&mut self, addr: SocketAddr, position: Vec3, rotation: Quat) {
-        if let Some(player_id) = self.addr_to_id.get(&addr) {
-            if let Some(player) = self.players.get_mut(player_id) {
-                player.position = position;
-                player.rotation = rotation;
-
-                let move_msg = ServerMessage::PlayerMoved {
-                    player_id: player_id.clone(),
-                    position,
-                    rotation,
-                };
-                self.broadcast_to_others(addr, &move_msg).await;
-            }
+        if let Some(player_id) = self.addr_to_id.get(&addr)
+            && let Some(player) = self.players.get_mut(player_id)
+        {
+            player.position = position;
+            player.rotation = rotation;
+
+            let move_msg = ServerMessage::PlayerMoved {
+                player_id: player_id.clone(),
+                position,
+                rotation,
+            };
+            self.broadcast_to_others(addr, &move_msg).await;
         }
     }
 
     // Check if a ray from origin to target intersects any walls
     fn ray_intersects_wall(&self, origin: Vec3, target: Vec3) -> bool {
-        if let Some(maze_data) = &self.maze_data {
-            const TILE_SIZE: f32 = 4.0; // Same as client rendering
-            const WALL_HEIGHT: f32 = 8.0; // Wall height from client rendering
-            const PLAYER_RADIUS: f32 = 1.0; // Player hitbox radius
+        const PLAYER_RADIUS: f32 = 1.0; // Player hitbox radius
+
+        let Some(maze_data) = &self.maze_data else {
+            return false;
+        };
 
-            let grid = &maze_data.grid;
-            let grid_height = grid.len();
-            let grid_width = if grid_height > 0 { grid[0].len() } else { 0 };
+        let ray_dir = (target - origin).normalize_or_zero();
+        let ray_length = origin.distance(target);
 
-            if grid_width == 0 || grid_height == 0 {
-                return false;
+        // Ignore walls grazed right at the origin or already inside the target's hitbox
+        match wall_hit_distance(&maze_data.grid, origin, ray_dir, ray_length - PLAYER_RADIUS) {
+            Some(hit_distance) if hit_distance > 0.1 => {
+                log_info(&format!("Ray intersects wall at {}", hit_distance));
+                true
             }
-
-            // Calculate maze offset (same as client)
-            let maze_width_world = grid_width as f32 * TILE_SIZE;
-            let maze_height_world = grid_height as f32 * TILE_SIZE;
-            let offset_x = -maze_width_world / 2.0 + TILE_SIZE / 2.0;
-            let offset_z = -maze_height_world / 2.0 + TILE_SIZE / 2.0;
-
-            let ray_dir = (target - origin).normalize();
-            let ray_length = origin.distance(target);
-
-            // Check each wall tile for ray-box intersection
-            for (z, row) in grid.iter().enumerate() {
-                for (x, &is_wall) in row.iter().enumerate() {
-                    if is_wall {
-                        // Calculate wall box bounds (centered on tile, matching client positioning)
-                        let wall_center_x = x as f32 * TILE_SIZE + offset_x + TILE_SIZE / 2.0;
-                        let wall_center_z = z as f32 * TILE_SIZE + offset_z + TILE_SIZE / 2.0;
-
-                        // Create box bounds centered on the wall position
-                        let box_min = Vec3::new(
-                            wall_center_x - TILE_SIZE / 2.0,
-                            0.0,
-                            wall_center_z - TILE_SIZE / 2.0,
-                        );
-                        let box_max = Vec3::new(
-                            wall_center_x + TILE_SIZE / 2.0,
-                            WALL_HEIGHT,
-                            wall_center_z + TILE_SIZE / 2.0,
-                        );
-
-                        // Perform ray-box intersection test
-                        if let Some(hit_distance) =
-                            self.ray_box_intersection(origin, ray_dir, box_min, box_max)
-                        {
-                            // Check if intersection is within ray length and not too close to target
-                            if hit_distance > 0.1 && hit_distance < ray_length - PLAYER_RADIUS {
-                                log_info(&format!("Ray intersects wall at {}", hit_distance));
-                                return true;
-                            }
-                        }
-                    }
-                }
+            _ => {
+                log_info("Ray does not intersect any wall, shot confirmed");
+                false
             }
         }
+    }
 
-        log_info(&format!("Ray does not intersect any wall, shot confirmed"));
-        false // No wall intersection found
-    }
-
-    // Ray-box intersection using the slab method
-    fn ray_box_intersection(
-        &self,
-        ray_origin: Vec3,
-        ray_dir: Vec3,
-        box_min: Vec3,
-        box_max: Vec3,
-    ) -> Option<f32> {
-        let mut t_min: f32 = 0.0;
-        let mut t_max: f32 = f32::INFINITY;
-
-        // Check intersection with each axis
-        for i in 0..3 {
-            let origin_component = match i {
-                0 => ray_origin.x,
-                1 => ray_origin.y,
-                2 => ray_origin.z,
-                _ => unreachable!(),
-            };
-            let dir_component = match i {
-                0 => ray_dir.x,
-                1 => ray_dir.y,
-                2 => ray_dir.z,
-                _ => unreachable!(),
-            };
-            let box_min_component = match i {
-                0 => box_min.x,
-                1 => box_min.y,
-                2 => box_min.z,
-                _ => unreachable!(),
-            };
-            let box_max_component = match i {
-                0 => box_max.x,
-                1 => box_max.y,
-                2 => box_max.z,
-                _ => unreachable!(),
-            };
+    async fn handle_player_shoot(&mut self, addr: SocketAddr, origin: Vec3, direction: Vec3) {
+        if let Some(shooter_id) = self.addr_to_id.get(&addr).cloned() {
+            self.player_shoot(&shooter_id, origin, direction).await;
+        }
+    }
 
-            if dir_component.abs() < 1e-6_f32 {
-                // Ray is parallel to the slab
-                if origin_component < box_min_component || origin_component > box_max_component {
-                    return None; // Ray misses the box
-                }
-            } else {
-                // Calculate intersection distances
-                let t1 = (box_min_component - origin_component) / dir_component;
-                let t2 = (box_max_component - origin_component) / dir_component;
+    // Fire the shooter's weapon, shared by clients and bots
+    async fn player_shoot(&mut self, shooter_id: &str, origin: Vec3, direction: Vec3) {
+        // No combat while the match is counting down or in intermission
+        if matches!(self.state, GameState::Countdown | GameState::GameOver) {
+            return;
+        }
 
-                let (t_near, t_far) = if t1 < t2 { (t1, t2) } else { (t2, t1) };
+        let Some(shooter) = self.players.get_mut(shooter_id).filter(|p| p.is_alive) else {
+            return;
+        };
 
-                t_min = t_min.max(t_near);
-                t_max = t_max.min(t_far);
+        // Enforce the weapon's fire rate, leaving some slack for network jitter
+        let weapon = weapons().get(shooter.weapon);
+        let loadout = shooter.loadout;
+        let damage_scale = shooter.damage_multiplier();
+        let now = unix_time_secs();
+        let cooldown = (weapon.cooldown() / loadout.fire_rate) as f64;
+        if now - shooter.last_shot_time < cooldown * FIRE_RATE_TOLERANCE {
+            return;
+        }
 
-                if t_min > t_max {
-                    return None; // No intersection
-                }
-            }
+        // A shot costs one round however many pellets it fires
+        if !self.take_round(shooter_id).await {
+            return;
+        }
+        if let Some(shooter) = self.players.get_mut(shooter_id) {
+            shooter.last_shot_time = now;
         }
 
-        // Return the closest intersection distance (if positive)
-        if t_min > 0.0_f32 {
-            Some(t_min)
-        } else if t_max > 0.0_f32 {
-            Some(t_max)
-        } else {
-            None
+        // Projectile weapons hand the shot over to the tick loop
+        if let Some(projectile) = &weapon.projectile {
+            let (u, v) = {
+                let mut rng = rand::thread_rng();
+                (rng.gen_range(0.0..1.0), rng.gen_range(0.0..1.0))
+            };
+            let direction = spread_direction(direction, weapon.spread, u, v);
+            let reach = projectile.speed * projectile.fuse_secs * loadout.range.min(1.0);
+            self.record_shot(shooter_id, origin, direction, reach);
+            self.launch_projectile(shooter_id, origin, direction, weapon, loadout);
+            return;
         }
-    }
 
-    async fn handle_player_shoot(&mut self, addr: SocketAddr, origin: Vec3, direction: Vec3) {
-        if let Some(shooter_id) = self.addr_to_id.get(&addr) {
-            if let Some(_) = self.players.get(shooter_id) {
-                let weapon_config = WeaponConfig::default();
-                let mut hit_result = HitscanResult {
-                    hit: false,
-                    hit_position: None,
-                    hit_player_id: None,
-                    distance: weapon_config.range,
-                };
-
-                // Check for hits against other players
-                for (other_id, other_player) in self.players.iter() {
-                    if other_id != shooter_id && other_player.is_alive {
-                        let to_player = other_player.position - origin;
-                        let distance = to_player.length();
-
-                        // Check if player is within range and closer than any previous hit
-                        if distance <= weapon_config.range && distance < hit_result.distance {
-                            // Check if ray is pointing towards player (dot product > 0)
-                            let to_player_dir = to_player.normalize();
-                            let dot = direction.dot(to_player_dir);
-
-                            // Allow some tolerance for near-misses (dot > 0.9 means within ~25 degrees)
-                            if dot > 0.9 {
-                                // Check if there's a wall between shooter and target
-                                if !self.ray_intersects_wall(origin, other_player.position) {
-                                    hit_result.hit = true;
-                                    hit_result.hit_position = Some(other_player.position);
-                                    hit_result.hit_player_id = Some(other_id.clone());
-                                    hit_result.distance = distance;
-                                }
-                            }
-                        }
-                    }
+        // Every pellet is its own hitscan; damage is totalled per target
+        let range = weapon.range * loadout.range;
+        let mut damage_dealt: Vec<(String, DamageEvent)> = Vec::new();
+        for _ in 0..weapon.pellets.max(1) {
+            let (u, v) = {
+                let mut rng = rand::thread_rng();
+                (rng.gen_range(0.0..1.0), rng.gen_range(0.0..1.0))
+            };
+            let pellet_direction = spread_direction(direction, weapon.spread, u, v);
+            let hit_result = self.hitscan(shooter_id, origin, pellet_direction, range);
+            self.record_shot(shooter_id, origin, pellet_direction, hit_result.distance);
+
+            if let Some(hit_id) = &hit_result.hit_player_id {
+                let hit =
+                    DamageEvent::direct(shooter_id, weapon.kind, hit_result.distance, damage_scale);
+                match damage_dealt.iter_mut().find(|(id, _)| id == hit_id) {
+                    Some((_, total)) => total.amount += hit.amount,
+                    None => damage_dealt.push((hit_id.clone(), hit)),
                 }
+            }
 
-                // Apply damage if hit
-                if let Some(ref hit_player_id) = hit_result.hit_player_id {
-                    if let Some(hit_player) = self.players.get_mut(hit_player_id) {
-                        let died = hit_player.take_damage(weapon_config.damage);
-
-                        let damage_msg = ServerMessage::PlayerDamaged {
-                            player_id: hit_player_id.clone(),
-                            damage: weapon_config.damage,
-                            health: hit_player.health,
-                            damage_by: shooter_id.clone(),
-                        };
-                        self.send_message(addr, &damage_msg).await;
-
-                        if died {
-                            // Update killer stats
-                            if let Some(killer) = self.players.get_mut(shooter_id) {
-                                killer.kills += 1;
-                            }
-
-                            let death_msg = ServerMessage::PlayerDied {
-                                player_id: hit_player_id.clone(),
-                                killer_id: Some(shooter_id.clone()),
-                            };
-                            self.broadcast(&death_msg).await;
-
-                            // Start respawn timer
-                            self.pending_respawns
-                                .insert(hit_player_id.clone(), Instant::now());
-                        }
-                    }
-                }
+            let shot_msg = ServerMessage::PlayerShot {
+                player_id: shooter_id.to_string(),
+                origin,
+                direction: pellet_direction,
+                hit_result,
+            };
+            self.broadcast(&shot_msg).await;
+        }
 
-                let shot_msg = ServerMessage::PlayerShot {
-                    player_id: shooter_id.clone(),
-                    origin,
-                    direction,
-                    hit_result,
-                };
-                self.broadcast(&shot_msg).await;
-            }
+        for (target_id, damage) in damage_dealt {
+            self.apply_damage(&target_id, damage).await;
+        }
+    }
+
+    async fn handle_switch_weapon(&mut self, addr: SocketAddr, weapon: WeaponKind) {
+        if let Some(player_id) = self.addr_to_id.get(&addr).cloned()
+            && let Some(player) = self.players.get_mut(&player_id)
+            && player.weapon != weapon
+        {
+            // Switching away abandons a reload in progress
+            player.weapon = weapon;
+            player.reload_done_at = None;
+            let switched_msg = ServerMessage::WeaponSwitched {
+                player_id: player_id.clone(),
+                weapon,
+            };
+            self.broadcast(&switched_msg).await;
+            self.send_ammo(&player_id).await;
         }
     }
 
     async fn handle_respawn(&mut self, addr: SocketAddr) {
+        // Eliminated players spectate until the round is over
+        if self.is_round_in_progress() {
+            let error_msg = ServerMessage::Error {
+                message: "Eliminated - you will respawn next round".to_string(),
+            };
+            self.send_message(addr, &error_msg).await;
+            return;
+        }
+
         if let Some(player_id) = self.addr_to_id.get(&addr).cloned() {
             // Check if player is dead and respawn timer has expired first
             let should_respawn = if let Some(player) = self.players.get(&player_id) {
                 if !player.is_alive {
                     if let Some(respawn_time) = self.pending_respawns.get(&player_id) {
-                        Instant::now().duration_since(*respawn_time) >= Duration::from_secs(3)
+                        Instant::now().duration_since(*respawn_time) >= RESPAWN_DELAY
                     } else {
                         false
                     }
@@ -521,55 +924,56 @@ impl GameServer {
             };
 
             if should_respawn {
-                // Get spawn point before getting mutable reference to player
-                let spawn_point = self.get_random_spawn_point();
-
-                if let Some(player) = self.players.get_mut(&player_id) {
-                    // Respawn player at random maze spawn point
-                    if let Some(spawn_point) = spawn_point {
-                        player.position = spawn_point.position;
-                        player.rotation = spawn_point.rotation;
-                    } else {
-                        // Fallback to default spawn if no spawn points available
-                        log_info("falling back to default spawn point");
-                        player.position = Vec3::new(48.0, 2.5, 48.0);
-                    }
-                    player.health = player.max_health;
-                    player.is_alive = true;
-                    player.death_time = None;
-                    player.last_damage_time = None;
-                    player.last_damage_by = None;
-
-                    let respawn_msg = ServerMessage::PlayerRespawned {
-                        player_id: player_id.clone(),
-                        position: player.position,
-                    };
-                    self.broadcast(&respawn_msg).await;
-
-                    // Remove respawn timer
-                    self.pending_respawns.remove(&player_id);
-                }
+                self.respawn_player(&player_id).await;
             } else {
                 // Check if we should send error message for respawn timer
-                if let Some(player) = self.players.get(&player_id) {
-                    if !player.is_alive {
-                        if let Some(respawn_time) = self.pending_respawns.get(&player_id) {
-                            let remaining_time = Duration::from_secs(3)
-                                - Instant::now().duration_since(*respawn_time);
-                            let error_msg = ServerMessage::Error {
-                                message: format!(
-                                    "Respawn in {:.1} seconds",
-                                    remaining_time.as_secs_f32()
-                                ),
-                            };
-                            self.send_message(addr, &error_msg).await;
-                        }
-                    }
+                if let Some(player) = self.players.get(&player_id)
+                    && !player.is_alive
+                    && let Some(respawn_time) = self.pending_respawns.get(&player_id)
+                {
+                    let remaining_time =
+                        RESPAWN_DELAY - Instant::now().duration_since(*respawn_time);
+                    let error_msg = ServerMessage::Error {
+                        message: format!("Respawn in {:.1} seconds", remaining_time.as_secs_f32()),
+                    };
+                    self.send_message(addr, &error_msg).await;
                 }
             }
         }
     }
 
+    // Put a player back into play at a random maze spawn point
+    async fn respawn_player(&mut self, player_id: &str) {
+        // Get spawn point before getting mutable reference to player
+        let team = self.players.get(player_id).and_then(|p| p.team);
+        let spawn_point = self.get_spawn_point(player_id, team);
+
+        if let Some(player) = self.players.get_mut(player_id) {
+            // Fresh health, ammo and no lingering attackers to share the next kill
+            player.respawn();
+
+            // Respawn player at random maze spawn point
+            if let Some(spawn_point) = spawn_point {
+                player.position = spawn_point.position;
+                player.rotation = spawn_point.rotation;
+            } else if let Some(maze_data) = &self.maze_data {
+                // Every spawn point is taken, so use the open cell nearest the middle
+                log_info("falling back to default spawn point");
+                player.position = central_spawn(&maze_data.grid);
+            }
+
+            let respawn_msg = ServerMessage::PlayerRespawned {
+                player_id: player_id.to_string(),
+                position: player.position,
+            };
+            self.broadcast(&respawn_msg).await;
+
+            // Remove respawn timer
+            self.pending_respawns.remove(player_id);
+            self.send_ammo(player_id).await;
+        }
+    }
+
     pub async fn shutdown_gracefully(&self) {
         println!(
             "Sending shutdown notification to {} client{}...",
@@ -582,6 +986,7 @@ impl GameServer {
         };
 
         self.broadcast(&shutdown_msg).await;
+        self.finish_replay();
 
         // Give clients a moment to receive the message
         tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
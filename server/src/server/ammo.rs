@@ -0,0 +1,96 @@
+use shared::{Ammo, AmmoStatus, ServerMessage, weapons};
+use std::net::SocketAddr;
+
+use super::{GameServer, unix_time_secs};
+use crate::utils::log_info;
+
+impl GameServer {
+    // Ammo of the weapon in hand, only the owner needs to know it
+    pub(super) async fn send_ammo(&self, player_id: &str) {
+        let (Some(addr), Some(player)) = (self.player_addr(player_id), self.players.get(player_id))
+        else {
+            return;
+        };
+
+        let status = AmmoStatus {
+            weapon: player.weapon,
+            ammo: player.current_ammo(),
+            reload_remaining: player
+                .reload_done_at
+                .map(|done_at| (done_at - unix_time_secs()).max(0.0) as f32),
+        };
+        self.send_message(addr, &ServerMessage::AmmoUpdate { status })
+            .await;
+    }
+
+    // Use up a round for a shot. Emptying the magazine starts a reload straight
+    // away, since the client stops asking to shoot once it's empty.
+    pub(super) async fn take_round(&mut self, player_id: &str) -> bool {
+        let Some(player) = self.players.get_mut(player_id) else {
+            return false;
+        };
+        if player.reload_done_at.is_some() {
+            return false;
+        }
+
+        let mut ammo = player.current_ammo();
+        if ammo.magazine == 0 {
+            self.start_reload(player_id).await;
+            return false;
+        }
+
+        ammo.magazine -= 1;
+        player.ammo.insert(player.weapon, ammo);
+        self.send_ammo(player_id).await;
+        if ammo.magazine == 0 {
+            self.start_reload(player_id).await;
+        }
+        true
+    }
+
+    pub(super) async fn start_reload(&mut self, player_id: &str) {
+        let Some(player) = self.players.get_mut(player_id) else {
+            return;
+        };
+
+        let weapon = weapons().get(player.weapon);
+        if !player.is_alive
+            || player.reload_done_at.is_some()
+            || !player.current_ammo().can_reload(weapon)
+        {
+            return;
+        }
+
+        player.reload_done_at = Some(unix_time_secs() + weapon.reload_secs as f64);
+        self.send_ammo(player_id).await;
+    }
+
+    pub(super) async fn handle_reload(&mut self, addr: SocketAddr) {
+        if let Some(player_id) = self.addr_to_id.get(&addr).cloned() {
+            self.start_reload(&player_id).await;
+        }
+    }
+
+    // Per-tick ammo logic: finish reloads whose timer has run out
+    pub(super) async fn update_reloads(&mut self) {
+        let now = unix_time_secs();
+        let finished: Vec<String> = self
+            .players
+            .values()
+            .filter(|p| p.reload_done_at.is_some_and(|done_at| done_at <= now))
+            .map(|p| p.id.clone())
+            .collect();
+
+        for player_id in finished {
+            if let Some(player) = self.players.get_mut(&player_id) {
+                let weapon = weapons().get(player.weapon);
+                let mut ammo: Ammo = player.current_ammo();
+                ammo.reload(weapon);
+                player.ammo.insert(player.weapon, ammo);
+                player.reload_done_at = None;
+                log_info(&format!("{} reloaded their {}", player.name, weapon.name));
+            }
+            self.send_ammo(&player_id).await;
+        }
+    }
+}
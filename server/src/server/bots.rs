@@ -0,0 +1,293 @@
+use bevy::math::{Quat, Vec3};
+use rand::Rng;
+use rand::seq::SliceRandom;
+use shared::{
+    PowerUp, ServerMessage, TILE_SIZE, find_path, grid_to_world, spread_direction,
+    wall_hit_distance, weapons, world_to_grid,
+};
+use tokio::time::Instant;
+
+use super::{GameServer, RESPAWN_DELAY, TICK_INTERVAL};
+use crate::utils::log_info;
+
+const BOT_SPEED: f32 = 10.0; // A little slower than a human player's 15
+const SIGHT_RANGE: f32 = 60.0;
+const ENGAGE_RANGE: f32 = 0.8; // Share of weapon range a bot closes in to before firing
+
+// The enemy a bot is currently fighting
+struct Sighting {
+    player_id: String,
+    spotted_at: Instant,
+}
+
+// What a bot is thinking; its body is an ordinary entry in the player list
+#[derive(Default)]
+pub(super) struct Bot {
+    path: Vec<Vec3>, // Waypoints still to walk, the next one last
+    target: Option<Sighting>,
+    last_seen: Option<Vec3>, // Where the target was when the bot lost sight of it
+}
+
+// Rotation looking along a direction, turning only around the vertical axis
+fn facing(direction: Vec3) -> Quat {
+    Quat::from_rotation_y(f32::atan2(-direction.x, -direction.z))
+}
+
+impl GameServer {
+    // Per-tick bot logic: fill in for missing humans, then move and fight
+    pub(super) async fn update_bots(&mut self) {
+        self.balance_bots().await;
+
+        let bot_ids: Vec<String> = self.bots.keys().cloned().collect();
+        for bot_id in bot_ids {
+            self.update_bot(&bot_id).await;
+        }
+    }
+
+    // Forget plans made for a maze that no longer exists
+    pub(super) fn reset_bots(&mut self) {
+        for bot in self.bots.values_mut() {
+            *bot = Bot::default();
+        }
+    }
+
+    // Bots only play alongside humans, and give up their seat as more of them join
+    async fn balance_bots(&mut self) {
+        let humans = self.addr_to_id.len();
+        let wanted = if humans == 0 {
+            0
+        } else {
+            self.config.bots.saturating_sub(humans)
+        };
+
+        while self.bots.len() > wanted {
+            let Some(bot_id) = self.bots.keys().next().cloned() else {
+                break;
+            };
+            if let Some(bot) = self.players.get(&bot_id) {
+                log_info(&format!("{} left to make room", bot.name));
+            }
+            self.remove_player(&bot_id).await;
+        }
+
+        while self.bots.len() < wanted {
+            self.add_bot().await;
+        }
+    }
+
+    async fn add_bot(&mut self) {
+        let name = (1..)
+            .map(|n| format!("Bot {}", n))
+            .find(|name| !self.is_name_taken(name, None))
+            .unwrap_or_default();
+        let player = self.create_player(name);
+        log_info(&format!("{} joined", player.name));
+
+        self.bots.insert(player.id.clone(), Bot::default());
+        self.players.insert(player.id.clone(), player.clone());
+        let joined_msg = ServerMessage::PlayerJoined { player };
+        self.broadcast(&joined_msg).await;
+    }
+
+    async fn update_bot(&mut self, bot_id: &str) {
+        let Some(player) = self.players.get(bot_id) else {
+            return;
+        };
+
+        // Dead bots respawn as soon as a human would be allowed to
+        if !player.is_alive {
+            let ready = self
+                .pending_respawns
+                .get(bot_id)
+                .is_some_and(|died_at| died_at.elapsed() >= RESPAWN_DELAY);
+            if ready && !self.is_round_in_progress() {
+                self.bots.insert(bot_id.to_string(), Bot::default());
+                self.respawn_player(bot_id).await;
+            }
+            return;
+        }
+
+        match self.visible_enemy(bot_id) {
+            Some((target_id, target_position)) => {
+                self.engage(bot_id, target_id, target_position).await
+            }
+            None => self.roam(bot_id).await,
+        }
+    }
+
+    // Nearest enemy the bot has a clear line of sight to
+    fn visible_enemy(&self, bot_id: &str) -> Option<(String, Vec3)> {
+        let bot = self.players.get(bot_id)?;
+        let grid = &self.maze_data.as_ref()?.grid;
+
+        self.players
+            .values()
+            .filter(|p| p.id != bot_id && p.is_alive && !bot.is_teammate(p))
+            .filter(|p| !p.has_power_up(PowerUp::Invisibility))
+            .map(|p| (p, bot.position.distance(p.position)))
+            .filter(|(p, distance)| {
+                let direction = (p.position - bot.position).normalize_or_zero();
+                *distance <= SIGHT_RANGE
+                    && wall_hit_distance(grid, bot.position, direction, *distance)
+                        .is_none_or(|hit| hit <= 0.1)
+            })
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(p, _)| (p.id.clone(), p.position))
+    }
+
+    // Close in on a visible enemy and shoot once the bot has had time to react
+    async fn engage(&mut self, bot_id: &str, target_id: String, target_position: Vec3) {
+        let Some(player) = self.players.get(bot_id) else {
+            return;
+        };
+        let weapon = weapons().get(player.weapon);
+        let in_range = player.position.distance(target_position)
+            <= weapon.range * player.loadout.range * ENGAGE_RANGE;
+
+        let Some(bot) = self.bots.get_mut(bot_id) else {
+            return;
+        };
+        // A new target means reacting all over again
+        let spotted_at = match &bot.target {
+            Some(sighting) if sighting.player_id == target_id => sighting.spotted_at,
+            _ => {
+                let now = Instant::now();
+                bot.target = Some(Sighting {
+                    player_id: target_id,
+                    spotted_at: now,
+                });
+                now
+            }
+        };
+        bot.last_seen = Some(target_position);
+
+        if in_range {
+            bot.path.clear();
+        } else if bot
+            .path
+            .first()
+            .is_none_or(|goal| goal.distance(target_position) > TILE_SIZE)
+        {
+            self.plan_path(bot_id, target_position);
+        }
+        self.walk(bot_id);
+
+        let Some(player) = self.players.get_mut(bot_id) else {
+            return;
+        };
+        let origin = player.position;
+        let direction = (target_position - origin).normalize_or_zero();
+        player.rotation = facing(direction);
+        self.broadcast_bot_move(bot_id).await;
+
+        if in_range && spotted_at.elapsed() >= self.config.bot_skill.reaction_time() {
+            let (u, v) = {
+                let mut rng = rand::thread_rng();
+                (rng.gen_range(0.0..1.0), rng.gen_range(0.0..1.0))
+            };
+            let aim = spread_direction(direction, self.config.bot_skill.aim_error(), u, v);
+            self.player_shoot(bot_id, origin, aim).await;
+        }
+    }
+
+    // No enemy in sight: chase the last one to where it vanished, otherwise wander
+    async fn roam(&mut self, bot_id: &str) {
+        let Some(bot) = self.bots.get_mut(bot_id) else {
+            return;
+        };
+        bot.target = None;
+
+        if bot.path.is_empty() {
+            let destination = bot.last_seen.take().or_else(|| self.wander_destination());
+            if let Some(destination) = destination {
+                self.plan_path(bot_id, destination);
+            }
+        }
+
+        if self.walk(bot_id) {
+            self.broadcast_bot_move(bot_id).await;
+        }
+    }
+
+    // Somewhere worth going: a spawn point or a pickup that's there to take
+    fn wander_destination(&self) -> Option<Vec3> {
+        let maze = self.maze_data.as_ref()?;
+        let spots: Vec<Vec3> = maze
+            .spawn_points
+            .iter()
+            .map(|spawn| spawn.position)
+            .chain(
+                self.pickups
+                    .iter()
+                    .filter(|p| p.available)
+                    .map(|p| p.position),
+            )
+            .collect();
+        spots.choose(&mut rand::thread_rng()).copied()
+    }
+
+    fn plan_path(&mut self, bot_id: &str, destination: Vec3) {
+        let (Some(maze), Some(player), Some(bot)) = (
+            self.maze_data.as_ref(),
+            self.players.get(bot_id),
+            self.bots.get_mut(bot_id),
+        ) else {
+            return;
+        };
+        let grid = &maze.grid;
+
+        let cells = world_to_grid(grid, player.position)
+            .zip(world_to_grid(grid, destination))
+            .and_then(|(start, goal)| find_path(grid, start, goal))
+            .unwrap_or_default();
+        // The bot is already in the first cell
+        bot.path = cells
+            .into_iter()
+            .skip(1)
+            .rev()
+            .map(|(x, y)| {
+                let center = grid_to_world(grid, x, y);
+                Vec3::new(center.x, player.position.y, center.z)
+            })
+            .collect();
+    }
+
+    // Move a tick's worth along the planned path, returning whether the bot moved
+    fn walk(&mut self, bot_id: &str) -> bool {
+        let (Some(player), Some(bot)) = (self.players.get_mut(bot_id), self.bots.get_mut(bot_id))
+        else {
+            return false;
+        };
+
+        let speed = BOT_SPEED * player.speed_multiplier();
+        let mut step = speed * TICK_INTERVAL.as_secs_f32();
+        let moved = !bot.path.is_empty();
+        while step > 0.0
+            && let Some(&waypoint) = bot.path.last()
+        {
+            let to_waypoint = waypoint - player.position;
+            let distance = to_waypoint.length();
+            if distance <= step {
+                player.position = waypoint;
+                step -= distance;
+                bot.path.pop();
+            } else {
+                player.position += to_waypoint / distance * step;
+                player.rotation = facing(to_waypoint);
+                step = 0.0;
+            }
+        }
+        moved
+    }
+
+    async fn broadcast_bot_move(&self, bot_id: &str) {
+        if let Some(player) = self.players.get(bot_id) {
+            let move_msg = ServerMessage::PlayerMoved {
+                player_id: bot_id.to_string(),
+                position: player.position,
+                rotation: player.rotation,
+            };
+            self.broadcast(&move_msg).await;
+        }
+    }
+}
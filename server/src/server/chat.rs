@@ -0,0 +1,117 @@
+use shared::{ChatChannel, MAX_CHAT_LENGTH, ServerMessage};
+use std::net::SocketAddr;
+use tokio::time::{Duration, Instant};
+
+use super::GameServer;
+use crate::utils::log_info;
+
+const CHAT_BURST: usize = 5; // Messages anyone may send within the window
+const CHAT_WINDOW: Duration = Duration::from_secs(5);
+
+// Star out blocked words, matched whole and ignoring case
+fn filter_blocked_words(text: &str, blocked_words: &[String]) -> String {
+    let mut filtered = String::with_capacity(text.len());
+    let mut word = String::new();
+    let flush = |word: &mut String, filtered: &mut String| {
+        if blocked_words.contains(&word.to_lowercase()) {
+            filtered.extend(std::iter::repeat_n('*', word.chars().count()));
+        } else {
+            filtered.push_str(word);
+        }
+        word.clear();
+    };
+
+    for ch in text.chars() {
+        if ch.is_alphanumeric() {
+            word.push(ch);
+        } else {
+            flush(&mut word, &mut filtered);
+            filtered.push(ch);
+        }
+    }
+    flush(&mut word, &mut filtered);
+    filtered
+}
+
+impl GameServer {
+    pub(super) async fn handle_chat(
+        &mut self,
+        addr: SocketAddr,
+        channel: ChatChannel,
+        text: String,
+    ) {
+        let (sender_id, sender_name, team) = if let Some(player_id) = self.addr_to_id.get(&addr)
+            && let Some(player) = self.players.get(player_id)
+        {
+            (Some(player_id.clone()), player.name.clone(), player.team)
+        } else if let Some(name) = self.spectators.get(&addr) {
+            (None, name.clone(), None)
+        } else {
+            return;
+        };
+
+        let text: String = text
+            .chars()
+            .filter(|ch| !ch.is_control())
+            .take(MAX_CHAT_LENGTH)
+            .collect();
+        let text = text.trim();
+        if text.is_empty() {
+            return;
+        }
+
+        // Everyone gets a short burst, then has to wait for the oldest message to age out
+        let now = Instant::now();
+        let sent = self.chat_sent.entry(addr).or_default();
+        while sent
+            .front()
+            .is_some_and(|at| now.duration_since(*at) >= CHAT_WINDOW)
+        {
+            sent.pop_front();
+        }
+        if sent.len() >= CHAT_BURST {
+            let error_msg = ServerMessage::Error {
+                message: "You're sending messages too quickly".to_string(),
+            };
+            self.send_message(addr, &error_msg).await;
+            return;
+        }
+        sent.push_back(now);
+
+        let text = filter_blocked_words(text, &self.config.blocked_words);
+        log_info(&format!("[{:?}] {}: {}", channel, sender_name, text));
+        let chat_msg = ServerMessage::Chat {
+            sender_id: sender_id.clone(),
+            sender_name,
+            channel,
+            text,
+        };
+
+        match channel {
+            ChatChannel::All => self.broadcast(&chat_msg).await,
+            ChatChannel::Team => {
+                let recipients: Vec<SocketAddr> = match (&sender_id, team) {
+                    (None, _) => self.spectators.keys().copied().collect(),
+                    (Some(_), Some(team)) => self
+                        .addr_to_id
+                        .iter()
+                        .filter(|(_, id)| {
+                            self.players.get(*id).is_some_and(|p| p.team == Some(team))
+                        })
+                        .map(|(addr, _)| *addr)
+                        .collect(),
+                    (Some(_), None) => {
+                        let error_msg = ServerMessage::Error {
+                            message: "Team chat is only for team modes".to_string(),
+                        };
+                        self.send_message(addr, &error_msg).await;
+                        return;
+                    }
+                };
+                for recipient in recipients {
+                    self.send_message(recipient, &chat_msg).await;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,193 @@
+use bevy::math::Vec3;
+use shared::{
+    DamageEvent, HitscanResult, PLAYER_HIT_RADIUS, ServerMessage, ray_sphere_distance,
+    world_to_grid,
+};
+use tokio::time::Instant;
+
+use super::{GameServer, unix_time_secs};
+use crate::utils::log_info;
+
+// Health lost each tick by anyone outside the maze, e.g. after falling out of the world
+const OUT_OF_BOUNDS_DAMAGE: f32 = 25.0;
+
+impl GameServer {
+    // Nearest living player the ray reaches within range without passing through a wall
+    pub(super) fn hitscan(
+        &self,
+        shooter_id: &str,
+        origin: Vec3,
+        direction: Vec3,
+        range: f32,
+    ) -> HitscanResult {
+        let mut hit_result = HitscanResult {
+            hit: false,
+            hit_position: None,
+            hit_player_id: None,
+            distance: range,
+        };
+
+        for (other_id, other_player) in self.players.iter() {
+            if other_id == shooter_id || !other_player.is_alive {
+                continue;
+            }
+
+            let Some(distance) =
+                ray_sphere_distance(origin, direction, other_player.position, PLAYER_HIT_RADIUS)
+            else {
+                continue;
+            };
+
+            if distance <= range
+                && distance < hit_result.distance
+                && !self.ray_intersects_wall(origin, other_player.position)
+            {
+                hit_result.hit = true;
+                hit_result.hit_position = Some(origin + direction * distance);
+                hit_result.hit_player_id = Some(other_id.clone());
+                hit_result.distance = distance;
+            }
+        }
+
+        hit_result
+    }
+
+    // Mode rules that react to a kill, whatever caused it
+    async fn handle_kill(&mut self, killer_id: Option<&str>, victim_id: &str) {
+        if let Some(killer_id) = killer_id {
+            self.award_team_kill(killer_id, victim_id).await;
+        }
+        self.drop_flag(victim_id).await;
+        if let Some(killer_id) = killer_id {
+            self.handle_infection_kill(killer_id, victim_id).await;
+        }
+        self.check_score_limit().await;
+    }
+
+    // Hurt a player, crediting the kill and any assists if it was fatal.
+    // Returns true if the player died.
+    pub(super) async fn apply_damage(&mut self, target_id: &str, event: DamageEvent) -> bool {
+        // Teammates still stop the bullet, but only take damage with friendly fire on
+        let friendly_fire = event
+            .source_id
+            .as_deref()
+            .is_some_and(|source_id| self.is_friendly_fire(source_id, target_id));
+        if friendly_fire && !self.config.friendly_fire {
+            return false;
+        }
+
+        let now = unix_time_secs();
+        let Some(target) = self.players.get_mut(target_id) else {
+            return false;
+        };
+        let had_armor = target.armor > 0.0;
+        let died = target.take_damage(&event, now);
+        let assist_ids = if died {
+            target.assisters(event.source_id.as_deref(), now)
+        } else {
+            Vec::new()
+        };
+
+        let health = target.health;
+        let source_position = event
+            .source_id
+            .as_deref()
+            .and_then(|id| self.players.get(id))
+            .map(|source| source.position);
+        let damage_msg = ServerMessage::PlayerDamaged {
+            player_id: target_id.to_string(),
+            damage: event.amount,
+            health,
+            damage_by: event.source_id.clone(),
+            source_position,
+        };
+        self.record_event(&damage_msg);
+
+        // The shooter gets a hit marker, the victim sees where it came from
+        let source_addr = event
+            .source_id
+            .as_deref()
+            .and_then(|id| self.player_addr(id));
+        let target_addr = self.player_addr(target_id);
+        if let Some(source_addr) = source_addr {
+            self.send_message(source_addr, &damage_msg).await;
+        }
+        if let Some(target_addr) = target_addr
+            && Some(target_addr) != source_addr
+        {
+            self.send_message(target_addr, &damage_msg).await;
+        }
+
+        // Everyone sees armor wear down, e.g. for health bars
+        if had_armor {
+            self.broadcast_player_status(target_id).await;
+        }
+
+        if !died {
+            return false;
+        }
+
+        // Update killer stats (team kills and suicides don't count)
+        let killer_id = event.source_id.as_deref().filter(|id| *id != target_id);
+        let credited_killer = killer_id.filter(|_| !friendly_fire);
+        if let Some(killer) = credited_killer.and_then(|id| self.players.get_mut(id)) {
+            killer.kills += 1;
+        }
+
+        // Teammates who chipped in by friendly fire don't get an assist
+        let assist_ids: Vec<String> = assist_ids
+            .into_iter()
+            .filter(|id| !self.is_friendly_fire(id, target_id))
+            .collect();
+        for assist_id in &assist_ids {
+            if let Some(assister) = self.players.get_mut(assist_id) {
+                assister.assists += 1;
+            }
+        }
+
+        log_info(&format!(
+            "{} killed by {:?} ({:?}, {:?} at {:.1}), assists: {:?}",
+            target_id, killer_id, event.weapon, event.kind, event.distance, assist_ids
+        ));
+        let death_msg = ServerMessage::PlayerDied {
+            player_id: target_id.to_string(),
+            killer_id: killer_id.map(str::to_string),
+            assist_ids,
+            weapon: event.weapon,
+            kind: event.kind,
+        };
+        self.broadcast(&death_msg).await;
+        if let Some(killer_id) = killer_id {
+            self.send_killcam(killer_id, target_id, event.weapon).await;
+        }
+
+        // Start respawn timer
+        self.pending_respawns
+            .insert(target_id.to_string(), Instant::now());
+
+        self.end_streak(target_id, killer_id).await;
+        if let Some(killer_id) = credited_killer {
+            self.record_streak_kill(killer_id).await;
+        }
+        self.handle_kill(killer_id, target_id).await;
+        true
+    }
+
+    // Per-tick environmental damage: leaving the maze hurts with nobody to blame
+    pub(super) async fn update_hazards(&mut self) {
+        let Some(maze_data) = &self.maze_data else {
+            return;
+        };
+        let outside: Vec<String> = self
+            .players
+            .values()
+            .filter(|p| p.is_alive && world_to_grid(&maze_data.grid, p.position).is_none())
+            .map(|p| p.id.clone())
+            .collect();
+
+        for player_id in outside {
+            self.apply_damage(&player_id, DamageEvent::environmental(OUT_OF_BOUNDS_DAMAGE))
+                .await;
+        }
+    }
+}
@@ -0,0 +1,230 @@
+use bevy::math::{Vec2, Vec3};
+use shared::{
+    FLAG_RETURN_SECS, FLAG_TOUCH_RADIUS, FlagEvent, FlagState, FlagStatus, GameMode, GameState,
+    ServerMessage, Team,
+};
+use tokio::time::{Duration, Instant};
+
+use super::GameServer;
+use crate::utils::log_info;
+
+fn horizontal_distance(a: Vec3, b: Vec3) -> f32 {
+    Vec2::new(a.x - b.x, a.z - b.z).length()
+}
+
+impl GameServer {
+    // Put both flags back on the bases of the current maze
+    pub(super) fn reset_flags(&mut self) {
+        self.flags.clear();
+        self.flag_dropped_at.clear();
+
+        if self.config.mode != GameMode::CaptureTheFlag {
+            return;
+        }
+
+        if let Some(bases) = self.maze_data.as_ref().and_then(|m| m.flag_bases) {
+            for (team, base) in Team::ALL.into_iter().zip(bases) {
+                self.flags.insert(team, FlagState::at_base(team, base));
+            }
+        }
+    }
+
+    // Flag state as clients should see it, with the return timer filled in
+    fn flag_snapshot(&self, team: Team) -> Option<FlagState> {
+        let mut flag = self.flags.get(&team)?.clone();
+        if let FlagStatus::Dropped { return_in } = &mut flag.status
+            && let Some(dropped_at) = self.flag_dropped_at.get(&team)
+        {
+            *return_in = (FLAG_RETURN_SECS - dropped_at.elapsed().as_secs_f32()).max(0.0);
+        }
+        Some(flag)
+    }
+
+    pub(super) fn flag_states_message(&self) -> ServerMessage {
+        ServerMessage::FlagStates {
+            flags: Team::ALL
+                .into_iter()
+                .filter_map(|team| self.flag_snapshot(team))
+                .collect(),
+        }
+    }
+
+    async fn set_flag_status(
+        &mut self,
+        team: Team,
+        status: FlagStatus,
+        event: FlagEvent,
+        player_id: Option<&str>,
+    ) {
+        let Some(flag) = self.flags.get_mut(&team) else {
+            return;
+        };
+
+        flag.status = status;
+        match flag.status {
+            FlagStatus::AtBase => {
+                flag.position = flag.base;
+                self.flag_dropped_at.remove(&team);
+            }
+            FlagStatus::Dropped { .. } => {
+                self.flag_dropped_at.insert(team, Instant::now());
+            }
+            FlagStatus::Carried { .. } => {
+                self.flag_dropped_at.remove(&team);
+            }
+        }
+
+        log_info(&format!("{} flag {:?}", team.name(), event));
+        if let Some(flag) = self.flag_snapshot(team) {
+            let update_msg = ServerMessage::FlagUpdate {
+                flag,
+                event,
+                player_id: player_id.map(str::to_string),
+            };
+            self.broadcast(&update_msg).await;
+        }
+    }
+
+    // Drop whatever flag this player is carrying where they stand
+    pub(super) async fn drop_flag(&mut self, player_id: &str) {
+        let carried: Vec<Team> = self
+            .flags
+            .values()
+            .filter(|flag| flag.carrier_id() == Some(player_id))
+            .map(|flag| flag.team)
+            .collect();
+
+        for team in carried {
+            let status = FlagStatus::Dropped {
+                return_in: FLAG_RETURN_SECS,
+            };
+            self.set_flag_status(team, status, FlagEvent::Dropped, Some(player_id))
+                .await;
+        }
+    }
+
+    // Per-tick flag logic: carrying, pickups, returns and captures
+    pub(super) async fn update_flags(&mut self) {
+        if self.flags.is_empty() || self.state != GameState::GameStarted {
+            return;
+        }
+
+        for team in Team::ALL {
+            let Some(flag) = self.flags.get(&team).cloned() else {
+                continue;
+            };
+
+            match &flag.status {
+                FlagStatus::Carried { carrier_id } => {
+                    let carrier_position = self
+                        .players
+                        .get(carrier_id)
+                        .filter(|p| p.is_alive)
+                        .map(|p| p.position);
+                    match carrier_position {
+                        Some(position) => {
+                            if let Some(flag) = self.flags.get_mut(&team) {
+                                flag.position = position;
+                            }
+                        }
+                        None => {
+                            let carrier_id = carrier_id.clone();
+                            self.drop_flag(&carrier_id).await;
+                        }
+                    }
+                }
+                FlagStatus::Dropped { .. } => {
+                    let expired = self
+                        .flag_dropped_at
+                        .get(&team)
+                        .is_none_or(|at| at.elapsed() >= Duration::from_secs_f32(FLAG_RETURN_SECS));
+                    if expired {
+                        self.set_flag_status(team, FlagStatus::AtBase, FlagEvent::Returned, None)
+                            .await;
+                    } else if let Some(player_id) =
+                        self.player_touching(flag.position, |t| t == team)
+                    {
+                        // Defenders return their own flag before attackers can pick it up again
+                        self.set_flag_status(
+                            team,
+                            FlagStatus::AtBase,
+                            FlagEvent::Returned,
+                            Some(&player_id),
+                        )
+                        .await;
+                    } else if let Some(player_id) =
+                        self.player_touching(flag.position, |t| t != team)
+                    {
+                        self.pick_up_flag(team, &player_id).await;
+                    }
+                }
+                FlagStatus::AtBase => {
+                    if let Some(player_id) = self.player_touching(flag.position, |t| t != team) {
+                        self.pick_up_flag(team, &player_id).await;
+                    }
+                }
+            }
+        }
+
+        self.check_captures().await;
+    }
+
+    // Nearest living player on a matching team within touching distance of a position
+    fn player_touching(&self, position: Vec3, team: impl Fn(Team) -> bool) -> Option<String> {
+        self.players
+            .values()
+            .filter(|p| p.is_alive && p.team.is_some_and(&team))
+            .map(|p| (p, horizontal_distance(p.position, position)))
+            .filter(|(_, distance)| *distance <= FLAG_TOUCH_RADIUS)
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(p, _)| p.id.clone())
+    }
+
+    async fn pick_up_flag(&mut self, team: Team, player_id: &str) {
+        // One flag per carrier
+        if self
+            .flags
+            .values()
+            .any(|f| f.carrier_id() == Some(player_id))
+        {
+            return;
+        }
+
+        let status = FlagStatus::Carried {
+            carrier_id: player_id.to_string(),
+        };
+        self.set_flag_status(team, status, FlagEvent::PickedUp, Some(player_id))
+            .await;
+    }
+
+    // A carrier scores by reaching their own base while their flag is home
+    async fn check_captures(&mut self) {
+        let mut captures = Vec::new();
+        for flag in self.flags.values() {
+            let Some(carrier) = flag.carrier_id().and_then(|id| self.players.get(id)) else {
+                continue;
+            };
+            let Some(own_flag) = carrier.team.and_then(|team| self.flags.get(&team)) else {
+                continue;
+            };
+
+            if own_flag.status == FlagStatus::AtBase
+                && horizontal_distance(carrier.position, own_flag.base) <= FLAG_TOUCH_RADIUS
+            {
+                captures.push((flag.team, own_flag.team, carrier.id.clone()));
+            }
+        }
+
+        for (captured_team, scoring_team, carrier_id) in captures {
+            self.set_flag_status(
+                captured_team,
+                FlagStatus::AtBase,
+                FlagEvent::Captured,
+                Some(&carrier_id),
+            )
+            .await;
+            self.add_team_score(scoring_team).await;
+            self.check_score_limit().await;
+        }
+    }
+}
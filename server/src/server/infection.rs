@@ -0,0 +1,86 @@
+use rand::seq::IteratorRandom;
+use shared::{GameMode, GameState, ServerMessage, Team};
+
+use super::GameServer;
+use crate::utils::log_info;
+
+impl GameServer {
+    // Everyone back to being a healthy survivor
+    pub(super) fn reset_infection(&mut self) {
+        if self.config.mode != GameMode::Infection {
+            return;
+        }
+        for player in self.players.values_mut() {
+            player.cure();
+        }
+    }
+
+    // Infect a random player, returning who it was; an outbreak needs someone left to infect
+    pub(super) fn pick_patient_zero(&mut self) -> Option<String> {
+        if self.players.len() < 2 {
+            return None;
+        }
+        let player = self.players.values_mut().choose(&mut rand::thread_rng())?;
+        player.infect();
+        player.health = player.max_health;
+        log_info(&format!("{} is patient zero", player.name));
+        Some(player.id.clone())
+    }
+
+    pub(super) async fn infect_player(&mut self, player_id: &str, infected_by: Option<&str>) {
+        let Some(player) = self.players.get_mut(player_id) else {
+            return;
+        };
+        player.infect();
+
+        let infected_msg = ServerMessage::PlayerInfected {
+            player_id: player_id.to_string(),
+            infected_by: infected_by.map(str::to_string),
+        };
+        self.broadcast(&infected_msg).await;
+    }
+
+    // Survivors killed by the infected join them
+    pub(super) async fn handle_infection_kill(&mut self, killer_id: &str, victim_id: &str) {
+        if self.config.mode != GameMode::Infection || self.state != GameState::GameStarted {
+            return;
+        }
+
+        let killer_infected = self.players.get(killer_id).is_some_and(|p| p.is_infected());
+        let victim_infected = self.players.get(victim_id).is_some_and(|p| p.is_infected());
+        if killer_infected && !victim_infected {
+            self.infect_player(victim_id, Some(killer_id)).await;
+        }
+    }
+
+    pub(super) fn surviving_team(&self) -> Team {
+        if self.players.values().any(|p| !p.is_infected()) {
+            Team::Survivors
+        } else {
+            Team::Infected
+        }
+    }
+
+    // Per-tick infection checks: the infected win once nobody is left to infect
+    pub(super) async fn update_infection(&mut self) {
+        // A lone player has nobody to infect or be infected by
+        if self.config.mode != GameMode::Infection
+            || self.state != GameState::GameStarted
+            || self.players.len() < 2
+        {
+            return;
+        }
+
+        if self.surviving_team() == Team::Infected {
+            self.end_match("Everyone has been infected").await;
+            return;
+        }
+
+        // Keep the outbreak going if every infected player has left
+        if !self.players.values().any(|p| p.is_infected())
+            && let Some(patient_zero) = self.pick_patient_zero()
+        {
+            self.infect_player(&patient_zero, None).await;
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use bevy::math::{Quat, Vec3};
+use shared::{
+    KILLCAM_SECS, KillcamFrame, KillcamShot, ServerMessage, WeaponKind, wall_hit_distance,
+};
+use std::collections::VecDeque;
+use tokio::time::{Duration, Instant};
+
+use super::GameServer;
+
+// The last few seconds of a player's movement and shots, in case they get a kill
+#[derive(Default)]
+pub(super) struct History {
+    frames: VecDeque<(Instant, Vec3, Quat)>,
+    shots: VecDeque<(Instant, KillcamShot)>, // Timed when the killcam is sent
+}
+
+impl History {
+    fn forget_before(&mut self, cutoff: Instant) {
+        while self.frames.front().is_some_and(|(at, ..)| *at < cutoff) {
+            self.frames.pop_front();
+        }
+        while self.shots.front().is_some_and(|(at, _)| *at < cutoff) {
+            self.shots.pop_front();
+        }
+    }
+}
+
+fn killcam_window() -> Duration {
+    Duration::from_secs_f32(KILLCAM_SECS)
+}
+
+impl GameServer {
+    // Sample where every living player is, once a tick
+    pub(super) fn record_histories(&mut self) {
+        let now = Instant::now();
+        for player in self.players.values().filter(|p| p.is_alive) {
+            let history = self.histories.entry(player.id.clone()).or_default();
+            history
+                .frames
+                .push_back((now, player.position, player.rotation));
+        }
+
+        let cutoff = now.checked_sub(killcam_window()).unwrap_or(now);
+        for history in self.histories.values_mut() {
+            history.forget_before(cutoff);
+        }
+    }
+
+    // Remember a shot, cut short by the first wall in its way
+    pub(super) fn record_shot(
+        &mut self,
+        shooter_id: &str,
+        origin: Vec3,
+        direction: Vec3,
+        max_distance: f32,
+    ) {
+        let distance = self
+            .maze_data
+            .as_ref()
+            .and_then(|maze| wall_hit_distance(&maze.grid, origin, direction, max_distance))
+            .unwrap_or(max_distance);
+        let shot = KillcamShot {
+            time_ms: 0,
+            origin,
+            direction,
+            distance,
+        };
+        self.histories
+            .entry(shooter_id.to_string())
+            .or_default()
+            .shots
+            .push_back((Instant::now(), shot));
+    }
+
+    // Show the victim the moments before their death through the killer's eyes
+    pub(super) async fn send_killcam(
+        &self,
+        killer_id: &str,
+        victim_id: &str,
+        weapon: Option<WeaponKind>,
+    ) {
+        let (Some(victim_addr), Some(killer), Some(victim)) = (
+            self.player_addr(victim_id),
+            self.players.get(killer_id),
+            self.players.get(victim_id),
+        ) else {
+            return;
+        };
+
+        let now = Instant::now();
+        let start = now.checked_sub(killcam_window()).unwrap_or(now);
+        let time_ms = |at: Instant| at.saturating_duration_since(start).as_millis() as u32;
+        let frames = |id: &str, position: Vec3, rotation: Quat| {
+            let mut frames: Vec<KillcamFrame> = self
+                .histories
+                .get(id)
+                .into_iter()
+                .flat_map(|history| history.frames.iter())
+                .filter(|(at, ..)| *at >= start)
+                .map(|(at, position, rotation)| KillcamFrame {
+                    time_ms: time_ms(*at),
+                    position: *position,
+                    rotation: *rotation,
+                })
+                .collect();
+            // End exactly where everyone was at the moment of the kill
+            frames.push(KillcamFrame {
+                time_ms: time_ms(now),
+                position,
+                rotation,
+            });
+            frames
+        };
+        let shots = self
+            .histories
+            .get(killer_id)
+            .into_iter()
+            .flat_map(|history| history.shots.iter())
+            .filter(|(at, _)| *at >= start)
+            .map(|(at, shot)| KillcamShot {
+                time_ms: time_ms(*at),
+                ..*shot
+            })
+            .collect();
+
+        let killcam_msg = ServerMessage::Killcam {
+            killer_id: killer_id.to_string(),
+            killer_name: killer.name.clone(),
+            weapon,
+            killer_health: killer.health,
+            killer_frames: frames(killer_id, killer.position, killer.rotation),
+            victim_frames: frames(victim_id, victim.position, victim.rotation),
+            shots,
+        };
+        self.send_message(victim_addr, &killcam_msg).await;
+    }
+}
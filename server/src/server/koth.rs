@@ -0,0 +1,194 @@
+use bevy::math::Vec3;
+use rand::seq::SliceRandom;
+use shared::{
+    ControlZone, GameState, ServerMessage, ZONE_CAPTURE_SECS, ZONE_RADIUS, ZoneOwner,
+    control_zone_candidates,
+};
+use tokio::time::{Duration, Instant};
+
+use super::{GameServer, TICK_INTERVAL};
+use crate::utils::log_info;
+
+// How often the zone's timers and capture progress are sent while nothing else changes
+const ZONE_SYNC_INTERVAL: Duration = Duration::from_millis(250);
+
+impl GameServer {
+    // Pick a fresh spot for the hill in the current maze
+    pub(super) fn reset_zone(&mut self) {
+        self.zone = None;
+        self.zone_score_timer = 0.0;
+        self.zone_moved_at = Instant::now();
+
+        if !self.config.mode.has_control_zone() {
+            return;
+        }
+
+        if let Some(center) = self.pick_zone_center() {
+            self.zone = Some(ControlZone::new(center));
+        }
+    }
+
+    // Random roomy area, away from where the zone currently is when possible
+    fn pick_zone_center(&self) -> Option<Vec3> {
+        let maze = self.maze_data.as_ref()?;
+        let candidates = control_zone_candidates(&maze.grid);
+        let previous = self.zone.as_ref().map(|zone| zone.center);
+
+        let far_away: Vec<_> = candidates
+            .iter()
+            .filter(|c| previous.is_none_or(|p| c.distance(p) >= ZONE_RADIUS * 4.0))
+            .copied()
+            .collect();
+        let pool = if far_away.is_empty() {
+            &candidates
+        } else {
+            &far_away
+        };
+
+        pool.choose(&mut rand::thread_rng()).copied()
+    }
+
+    // Zone as clients should see it, with the relocation timer filled in
+    fn zone_snapshot(&self) -> Option<ControlZone> {
+        let mut zone = self.zone.clone()?;
+        if self.state == GameState::GameStarted {
+            zone.moves_in = Some(
+                self.config
+                    .hill_rotation
+                    .saturating_sub(self.zone_moved_at.elapsed())
+                    .as_secs_f32(),
+            );
+        }
+        Some(zone)
+    }
+
+    pub(super) fn zone_message(&self, moved: bool) -> Option<ServerMessage> {
+        self.zone_snapshot()
+            .map(|zone| ServerMessage::ZoneUpdate { zone, moved })
+    }
+
+    pub(super) async fn broadcast_zone(&mut self, moved: bool) {
+        self.zone_synced_at = Instant::now();
+        if let Some(zone_msg) = self.zone_message(moved) {
+            self.broadcast(&zone_msg).await;
+        }
+    }
+
+    // Per-tick hill logic: relocation, capturing and scoring
+    pub(super) async fn update_zone(&mut self) {
+        if self.zone.is_none() || self.state != GameState::GameStarted {
+            return;
+        }
+
+        if self.zone_moved_at.elapsed() >= self.config.hill_rotation {
+            self.reset_zone();
+            log_info("The hill has moved");
+            self.broadcast_zone(true).await;
+            return;
+        }
+
+        let sides = self.sides_in_zone();
+        let dt = TICK_INTERVAL.as_secs_f32();
+        let mut points_for = None;
+        let changed;
+
+        {
+            let Some(zone) = self.zone.as_mut() else {
+                return;
+            };
+            let before = (zone.owner.clone(), zone.capturing.clone(), zone.contested);
+
+            // Owners that left the server can't hold the hill
+            if let Some(ZoneOwner::Player(id)) = &zone.owner
+                && !self.players.contains_key(id)
+            {
+                zone.owner = None;
+            }
+
+            zone.contested = sides.len() > 1;
+            match sides.as_slice() {
+                [side] if zone.owner.as_ref() == Some(side) => {
+                    zone.capturing = None;
+                    zone.progress = 0.0;
+                    self.zone_score_timer += dt;
+                    if self.zone_score_timer >= 1.0 {
+                        self.zone_score_timer -= 1.0;
+                        points_for = Some(side.clone());
+                    }
+                }
+                [side] => {
+                    if zone.capturing.as_ref() != Some(side) {
+                        zone.capturing = Some(side.clone());
+                        zone.progress = 0.0;
+                    }
+                    zone.progress += dt / ZONE_CAPTURE_SECS;
+                    if zone.progress >= 1.0 {
+                        zone.owner = Some(side.clone());
+                        zone.capturing = None;
+                        zone.progress = 0.0;
+                        self.zone_score_timer = 0.0;
+                    }
+                }
+                [] => {
+                    // An abandoned capture slowly drains away
+                    zone.progress = (zone.progress - dt / ZONE_CAPTURE_SECS).max(0.0);
+                    if zone.progress == 0.0 {
+                        zone.capturing = None;
+                    }
+                }
+                _ => {} // Contested, nothing moves
+            }
+
+            changed = before != (zone.owner.clone(), zone.capturing.clone(), zone.contested);
+        }
+
+        if changed || self.zone_synced_at.elapsed() >= ZONE_SYNC_INTERVAL {
+            self.broadcast_zone(false).await;
+        }
+
+        if let Some(side) = points_for {
+            self.award_zone_point(side).await;
+        }
+    }
+
+    // Every side with a living player standing in the zone
+    fn sides_in_zone(&self) -> Vec<ZoneOwner> {
+        let Some(zone) = &self.zone else {
+            return Vec::new();
+        };
+
+        let mut sides = Vec::new();
+        for player in self.players.values() {
+            if !player.is_alive || !zone.contains(player.position) {
+                continue;
+            }
+            let side = match player.team {
+                Some(team) if self.config.mode.is_team_mode() => ZoneOwner::Team(team),
+                _ => ZoneOwner::Player(player.id.clone()),
+            };
+            if !sides.contains(&side) {
+                sides.push(side);
+            }
+        }
+        sides
+    }
+
+    async fn award_zone_point(&mut self, side: ZoneOwner) {
+        match side {
+            ZoneOwner::Team(team) => self.add_team_score(team).await,
+            ZoneOwner::Player(player_id) => {
+                let Some(player) = self.players.get_mut(&player_id) else {
+                    return;
+                };
+                player.score += 1;
+
+                let scored_msg = ServerMessage::PlayerScored {
+                    player_id,
+                    score: player.score,
+                };
+                self.broadcast(&scored_msg).await;
+            }
+        }
+        self.check_score_limit().await;
+    }
+}
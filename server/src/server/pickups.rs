@@ -0,0 +1,117 @@
+use shared::{GameState, PickupState, ServerMessage};
+use tokio::time::{Duration, Instant};
+
+use super::{GameServer, TICK_INTERVAL};
+use crate::utils::log_info;
+
+impl GameServer {
+    // Fill every spawner the maze generator placed in the current maze
+    pub(super) fn reset_pickups(&mut self) {
+        self.pickup_respawns.clear();
+        self.pickups = self
+            .maze_data
+            .as_ref()
+            .map(|maze| {
+                maze.pickup_spawns
+                    .iter()
+                    .enumerate()
+                    .map(|(id, spawn)| PickupState {
+                        id: id as u32,
+                        kind: spawn.kind,
+                        position: spawn.position,
+                        available: true,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+    }
+
+    pub(super) fn pickup_states_message(&self) -> ServerMessage {
+        ServerMessage::PickupStates {
+            pickups: self.pickups.clone(),
+        }
+    }
+
+    // Health, armor and power-ups of a player, for everyone to see
+    pub(super) async fn broadcast_player_status(&self, player_id: &str) {
+        if let Some(player) = self.players.get(player_id) {
+            let status_msg = ServerMessage::PlayerStatus {
+                player_id: player_id.to_string(),
+                health: player.health,
+                armor: player.armor,
+                power_ups: player.power_ups.clone(),
+            };
+            self.broadcast(&status_msg).await;
+        }
+    }
+
+    // Per-tick pickup logic: respawn timers, collection and power-up expiry
+    pub(super) async fn update_pickups(&mut self) {
+        self.expire_power_ups().await;
+
+        if matches!(self.state, GameState::Countdown | GameState::GameOver) {
+            return;
+        }
+
+        let respawned: Vec<u32> = self
+            .pickup_respawns
+            .iter()
+            .filter(|(_, at)| Instant::now() >= **at)
+            .map(|(id, _)| *id)
+            .collect();
+        for id in respawned {
+            self.pickup_respawns.remove(&id);
+            if let Some(pickup) = self.pickups.iter_mut().find(|p| p.id == id) {
+                pickup.available = true;
+                let update_msg = ServerMessage::PickupUpdate {
+                    pickup: pickup.clone(),
+                    collected_by: None,
+                };
+                self.broadcast(&update_msg).await;
+            }
+        }
+
+        // First living player in reach who can use it takes it
+        let mut collected = Vec::new();
+        for pickup in self.pickups.iter_mut().filter(|p| p.available) {
+            let Some(player) = self
+                .players
+                .values_mut()
+                .filter(|p| p.is_alive && pickup.in_reach(p.position))
+                .find_map(|p| pickup.kind.apply(p).then_some(p))
+            else {
+                continue;
+            };
+
+            log_info(&format!("{} picked up {}", player.name, pickup.kind.name()));
+            pickup.available = false;
+            self.pickup_respawns.insert(
+                pickup.id,
+                Instant::now() + Duration::from_secs_f32(pickup.kind.respawn_secs()),
+            );
+            collected.push((pickup.clone(), player.id.clone()));
+        }
+
+        for (pickup, player_id) in collected {
+            let update_msg = ServerMessage::PickupUpdate {
+                pickup,
+                collected_by: Some(player_id.clone()),
+            };
+            self.broadcast(&update_msg).await;
+            self.broadcast_player_status(&player_id).await;
+        }
+    }
+
+    async fn expire_power_ups(&mut self) {
+        let dt = TICK_INTERVAL.as_secs_f32();
+        let expired: Vec<String> = self
+            .players
+            .values_mut()
+            .filter_map(|p| p.tick_power_ups(dt).then(|| p.id.clone()))
+            .collect();
+
+        for player_id in expired {
+            self.broadcast_player_status(&player_id).await;
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use shared::ServerMessage;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use tokio::time::{Duration, Instant};
+
+use super::GameServer;
+
+const PING_INTERVAL: Duration = Duration::from_secs(1);
+
+impl GameServer {
+    // Ping every player once a second, sharing the round trips from the last time
+    pub(super) async fn update_pings(&mut self) {
+        let (seq, sent_at) = self.ping_round;
+        if sent_at.elapsed() < PING_INTERVAL {
+            return;
+        }
+
+        let pings: HashMap<String, u32> = self
+            .players
+            .values()
+            .filter_map(|player| Some((player.id.clone(), player.ping_ms?)))
+            .collect();
+        if !pings.is_empty() {
+            self.broadcast(&ServerMessage::Pings { pings }).await;
+        }
+
+        let seq = seq.wrapping_add(1);
+        self.ping_round = (seq, Instant::now());
+        let ping_msg = ServerMessage::Ping { seq };
+        let addrs: Vec<SocketAddr> = self.addr_to_id.keys().copied().collect();
+        for addr in addrs {
+            self.send_message(addr, &ping_msg).await;
+        }
+    }
+
+    pub(super) fn handle_pong(&mut self, addr: SocketAddr, seq: u32) {
+        // A late reply to an earlier round would overstate the ping
+        let (round, sent_at) = self.ping_round;
+        if seq != round {
+            return;
+        }
+
+        if let Some(player_id) = self.addr_to_id.get(&addr)
+            && let Some(player) = self.players.get_mut(player_id)
+        {
+            player.ping_ms = Some(sent_at.elapsed().as_millis() as u32);
+        }
+    }
+}
@@ -0,0 +1,183 @@
+use bevy::math::Vec3;
+use shared::{
+    DamageEvent, DamageKind, GameState, Loadout, PLAYER_HIT_RADIUS, ProjectileState,
+    ProjectileStep, ServerMessage, WeaponDef, ray_sphere_distance, weapons,
+};
+
+use super::{GameServer, TICK_INTERVAL};
+use crate::utils::log_info;
+
+// A projectile in flight along with how long until it blows up on its own
+pub(super) struct LiveProjectile {
+    state: ProjectileState,
+    fuse: f32,
+}
+
+impl GameServer {
+    // Fire a travelling shot; the tick loop takes it from here
+    pub(super) fn launch_projectile(
+        &mut self,
+        shooter_id: &str,
+        origin: Vec3,
+        direction: Vec3,
+        weapon: &WeaponDef,
+        loadout: Loadout,
+    ) {
+        let Some(projectile) = &weapon.projectile else {
+            return;
+        };
+
+        self.next_projectile_id = self.next_projectile_id.wrapping_add(1);
+        self.projectiles.push(LiveProjectile {
+            state: ProjectileState {
+                id: self.next_projectile_id,
+                owner_id: shooter_id.to_string(),
+                weapon: weapon.kind,
+                position: origin,
+                velocity: direction.normalize_or_zero() * projectile.speed,
+            },
+            // Short-ranged loadouts get short fuses, the same way they shorten hitscan range
+            fuse: projectile.fuse_secs * loadout.range.min(1.0),
+        });
+    }
+
+    // Remove every projectile, telling clients to drop theirs too
+    pub(super) async fn clear_projectiles(&mut self) {
+        if self.projectiles.is_empty() {
+            return;
+        }
+
+        self.projectiles.clear();
+        let states_msg = ServerMessage::ProjectileStates {
+            projectiles: Vec::new(),
+        };
+        self.broadcast(&states_msg).await;
+    }
+
+    // Per-tick projectile logic: fly, bounce, hit players and explode
+    pub(super) async fn update_projectiles(&mut self) {
+        if matches!(self.state, GameState::Countdown | GameState::GameOver) {
+            self.clear_projectiles().await;
+            return;
+        }
+        if self.projectiles.is_empty() {
+            return;
+        }
+        let Some(grid) = self.maze_data.as_ref().map(|m| m.grid.clone()) else {
+            return;
+        };
+
+        let dt = TICK_INTERVAL.as_secs_f32();
+        let mut explosions: Vec<(ProjectileState, Vec3, Option<String>)> = Vec::new();
+        let mut in_flight = Vec::new();
+
+        for mut projectile in std::mem::take(&mut self.projectiles) {
+            let Some(def) = &weapons().get(projectile.state.weapon).projectile else {
+                continue;
+            };
+
+            let start = projectile.state.position;
+            let step = projectile.state.advance(&grid, def, dt);
+            projectile.fuse -= dt;
+
+            // A direct hit on a player detonates the projectile where it touched them
+            let travelled = projectile.state.position - start;
+            let direct_hit = self
+                .players
+                .values()
+                .filter(|p| p.is_alive && p.id != projectile.state.owner_id)
+                .filter_map(|p| {
+                    ray_sphere_distance(
+                        start,
+                        travelled.normalize_or_zero(),
+                        p.position,
+                        PLAYER_HIT_RADIUS + def.radius,
+                    )
+                    .map(|distance| (p.id.clone(), distance))
+                })
+                .filter(|(_, distance)| *distance <= travelled.length())
+                .min_by(|a, b| a.1.total_cmp(&b.1));
+
+            if let Some((hit_id, distance)) = direct_hit {
+                let position = start + travelled.normalize_or_zero() * distance;
+                explosions.push((projectile.state, position, Some(hit_id)));
+            } else if step == ProjectileStep::Impact || projectile.fuse <= 0.0 {
+                let position = projectile.state.position;
+                explosions.push((projectile.state, position, None));
+            } else {
+                in_flight.push(projectile);
+            }
+        }
+        self.projectiles = in_flight;
+
+        let states_msg = ServerMessage::ProjectileStates {
+            projectiles: self.projectiles.iter().map(|p| p.state.clone()).collect(),
+        };
+        self.broadcast(&states_msg).await;
+
+        for (projectile, position, hit_id) in explosions {
+            self.explode(projectile, position, hit_id).await;
+        }
+    }
+
+    // Splash damage to everyone in the blast who isn't behind a wall.
+    // The player it struck, if any, takes the full blast as a direct hit.
+    async fn explode(
+        &mut self,
+        projectile: ProjectileState,
+        position: Vec3,
+        hit_id: Option<String>,
+    ) {
+        let exploded_msg = ServerMessage::ProjectileExploded {
+            id: projectile.id,
+            weapon: projectile.weapon,
+            position,
+        };
+        self.broadcast(&exploded_msg).await;
+
+        // Shots from players who have since left fizzle out harmlessly
+        if !self.players.contains_key(&projectile.owner_id) {
+            return;
+        }
+        let weapon = weapons().get(projectile.weapon);
+        let Some(def) = &weapon.projectile else {
+            return;
+        };
+        let damage_scale = self
+            .players
+            .get(&projectile.owner_id)
+            .map_or(1.0, |p| p.damage_multiplier());
+
+        // The blast never hurts the player who fired it
+        let victims: Vec<(String, DamageEvent)> = self
+            .players
+            .values()
+            .filter(|p| p.is_alive && p.id != projectile.owner_id)
+            .filter_map(|p| {
+                let distance = (p.position.distance(position) - PLAYER_HIT_RADIUS).max(0.0);
+                (distance <= def.splash_radius && !self.ray_intersects_wall(position, p.position))
+                    .then(|| {
+                        let direct = hit_id.as_ref() == Some(&p.id);
+                        let mut damage = DamageEvent::splash(
+                            &projectile.owner_id,
+                            weapon.kind,
+                            if direct { 0.0 } else { distance },
+                            damage_scale,
+                        );
+                        if direct {
+                            damage.kind = DamageKind::Direct;
+                        }
+                        (p.id.clone(), damage)
+                    })
+            })
+            .collect();
+
+        for (target_id, damage) in victims {
+            log_info(&format!(
+                "{} splash hit {} for {:.1}",
+                weapon.name, target_id, damage.amount
+            ));
+            self.apply_damage(&target_id, damage).await;
+        }
+    }
+}
@@ -0,0 +1,171 @@
+use shared::{
+    MazeConfig, PlayerSnapshot, REPLAY_EXTENSION, REPLAY_VERSION, ReplayEntry, ReplayHeader,
+    ReplayRecord, ReplayWriter, ServerMessage,
+};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufWriter};
+use std::path::PathBuf;
+use tokio::time::{Duration, Instant};
+
+use super::{GameServer, TICK_INTERVAL, unix_time_secs};
+use crate::utils::{log_error, log_info};
+
+const KEYFRAME_INTERVAL: Duration = Duration::from_secs(5); // Longest stretch a seek has to fast-forward
+
+// One replay file being written, covering a single maze
+pub(super) struct Recorder {
+    writer: ReplayWriter<BufWriter<File>>,
+    started_at: Instant,
+    players: HashMap<String, PlayerSnapshot>, // As of the last snapshot written
+    keyframe_at: Option<Instant>,
+}
+
+impl Recorder {
+    fn create(path: &PathBuf, header: &ReplayHeader) -> io::Result<Self> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let file = BufWriter::new(File::create(path)?);
+        Ok(Self {
+            writer: ReplayWriter::new(file, header)?,
+            started_at: Instant::now(),
+            players: HashMap::new(),
+            keyframe_at: None,
+        })
+    }
+
+    fn write(&mut self, record: ReplayRecord) -> io::Result<()> {
+        let entry = ReplayEntry {
+            time_ms: self.started_at.elapsed().as_millis() as u64,
+            record,
+        };
+        self.writer.write(&entry)
+    }
+}
+
+impl GameServer {
+    // Close the last replay and, if recording is on, start a new one for the maze
+    pub(super) fn start_replay(&mut self, maze: &MazeConfig) {
+        let replay = self.replay.get_mut().unwrap();
+        if let Some(recorder) = replay.as_mut() {
+            let _ = recorder.writer.flush();
+        }
+        *replay = None;
+
+        let Some(dir) = &self.config.replay_dir else {
+            return;
+        };
+        let recorded_at = unix_time_secs();
+        let path = dir.join(format!(
+            "replay-{}-{}.{}",
+            recorded_at as u64, maze.seed, REPLAY_EXTENSION
+        ));
+        let header = ReplayHeader {
+            version: REPLAY_VERSION,
+            recorded_at,
+            mode: self.config.mode,
+            maze: maze.clone(),
+            tick_rate: (1000 / TICK_INTERVAL.as_millis()) as u32,
+        };
+
+        match Recorder::create(&path, &header) {
+            Ok(recorder) => {
+                log_info(&format!("Recording replay to {}", path.display()));
+                *replay = Some(recorder);
+            }
+            Err(e) => log_error(&format!(
+                "ERROR: Failed to create replay {}: {}",
+                path.display(),
+                e
+            )),
+        }
+    }
+
+    // Log a gameplay event, called for everything broadcast
+    pub(super) fn record_event(&self, msg: &ServerMessage) {
+        let mut replay = self.replay.lock().unwrap();
+        if let Some(recorder) = replay.as_mut()
+            && let Err(e) = recorder.write(ReplayRecord::Event(Box::new(msg.clone())))
+        {
+            log_error(&format!("ERROR: Failed to write replay, stopping: {}", e));
+            *replay = None;
+        }
+    }
+
+    // Everything needed to rebuild the match as it stands, like a join does
+    fn keyframe_messages(&self) -> Vec<ServerMessage> {
+        let mut messages = vec![
+            self.game_state_message(),
+            self.match_state_message(),
+            self.pickup_states_message(),
+        ];
+        if !self.flags.is_empty() {
+            messages.push(self.flag_states_message());
+        }
+        messages.extend(self.zone_message(false));
+        messages
+    }
+
+    // Write where everyone ended up this tick, skipping players that didn't change.
+    // Every few seconds a keyframe goes first and the snapshot covers everyone.
+    pub(super) fn record_snapshot(&mut self) {
+        let keyframe_due = self
+            .replay
+            .get_mut()
+            .unwrap()
+            .as_ref()
+            .is_some_and(|recorder| {
+                recorder
+                    .keyframe_at
+                    .is_none_or(|at| at.elapsed() >= KEYFRAME_INTERVAL)
+            });
+        let keyframe = keyframe_due.then(|| self.keyframe_messages());
+
+        let replay = self.replay.get_mut().unwrap();
+        let Some(recorder) = replay.as_mut() else {
+            return;
+        };
+        if let Some(messages) = keyframe {
+            if let Err(e) = recorder.write(ReplayRecord::Keyframe { messages }) {
+                log_error(&format!("ERROR: Failed to write replay, stopping: {}", e));
+                *replay = None;
+                return;
+            }
+            recorder.keyframe_at = Some(Instant::now());
+            recorder.players.clear();
+        }
+
+        recorder
+            .players
+            .retain(|player_id, _| self.players.contains_key(player_id));
+        let changed: Vec<PlayerSnapshot> = self
+            .players
+            .values()
+            .map(PlayerSnapshot::of)
+            .filter(|snapshot| recorder.players.get(&snapshot.player_id) != Some(snapshot))
+            .collect();
+        for snapshot in &changed {
+            recorder
+                .players
+                .insert(snapshot.player_id.clone(), snapshot.clone());
+        }
+
+        let result = if changed.is_empty() {
+            Ok(())
+        } else {
+            recorder.write(ReplayRecord::Snapshot { players: changed })
+        };
+        // Flushed every tick so a crash loses at most one tick of the match
+        if let Err(e) = result.and_then(|_| recorder.writer.flush()) {
+            log_error(&format!("ERROR: Failed to write replay, stopping: {}", e));
+            *replay = None;
+        }
+    }
+
+    pub(super) fn finish_replay(&self) {
+        if let Some(recorder) = self.replay.lock().unwrap().as_mut() {
+            let _ = recorder.writer.flush();
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use shared::{GameMode, GameState, ServerMessage};
+use tokio::time::{Duration, Instant};
+
+use super::GameServer;
+use crate::utils::log_info;
+
+// Pause between the last kill of a round and the next round's maze
+const ROUND_END_DELAY: Duration = Duration::from_secs(4);
+
+impl GameServer {
+    // An elimination round is being fought or wrapping up, so the dead stay dead until the next
+    pub(super) fn is_round_in_progress(&self) -> bool {
+        self.config.mode == GameMode::Elimination && self.state == GameState::GameStarted
+    }
+
+    // Per-tick elimination logic: end the round when one player is left, then start the next
+    pub(super) async fn update_round(&mut self) {
+        if self.config.mode != GameMode::Elimination || self.state != GameState::GameStarted {
+            return;
+        }
+
+        if let Some(ended_at) = self.round_ended_at {
+            if ended_at.elapsed() >= ROUND_END_DELAY {
+                self.start_next_elimination_round().await;
+            }
+            return;
+        }
+
+        // A lone player on the server has nobody to outlast
+        if self.players.len() < 2 {
+            return;
+        }
+
+        let mut alive = self.players.values().filter(|p| p.is_alive);
+        let survivor = alive.next().map(|p| p.id.clone());
+        if alive.next().is_some() {
+            return;
+        }
+
+        self.round_ended_at = Some(Instant::now());
+        log_info(&format!("Round {} over", self.round));
+
+        if let Some(winner_id) = &survivor
+            && let Some(winner) = self.players.get_mut(winner_id)
+        {
+            winner.score += 1;
+            let scored_msg = ServerMessage::PlayerScored {
+                player_id: winner_id.clone(),
+                score: winner.score,
+            };
+            self.broadcast(&scored_msg).await;
+        }
+
+        let round_msg = ServerMessage::RoundOver {
+            round: self.round,
+            winner_id: survivor,
+        };
+        self.broadcast(&round_msg).await;
+
+        self.check_score_limit().await;
+    }
+
+    async fn start_next_elimination_round(&mut self) {
+        self.round += 1;
+        self.round_ended_at = None;
+        log_info(&format!("Round {} starting", self.round));
+
+        self.load_new_maze().await;
+
+        let round_msg = ServerMessage::RoundStarted { round: self.round };
+        self.broadcast(&round_msg).await;
+    }
+}
@@ -0,0 +1,78 @@
+use shared::ServerMessage;
+use std::net::SocketAddr;
+
+use super::GameServer;
+use crate::utils::log_info;
+
+impl GameServer {
+    // Names are unique across players and spectators, though whoever is connected
+    // from the address (bots have none) may keep theirs when switching between the two
+    pub(super) fn is_name_taken(&self, name: &str, addr: Option<SocketAddr>) -> bool {
+        let own_player = addr.and_then(|addr| self.addr_to_id.get(&addr));
+        let taken_by_player = self
+            .players
+            .values()
+            .any(|p| p.name == name && Some(&p.id) != own_player);
+        let taken_by_spectator = self
+            .spectators
+            .iter()
+            .any(|(spectator_addr, spectator)| spectator == name && Some(*spectator_addr) != addr);
+        taken_by_player || taken_by_spectator
+    }
+
+    // Answer a client checking its name before joining, without a player or spectator
+    pub(super) async fn handle_check_name(&mut self, addr: SocketAddr, name: String) {
+        let answer_msg = if self.is_name_taken(&name, Some(addr)) {
+            ServerMessage::NameAlreadyTaken
+        } else {
+            ServerMessage::NameAvailable
+        };
+        self.send_message(addr, &answer_msg).await;
+    }
+
+    pub(super) fn spectators_message(&self) -> ServerMessage {
+        let mut names: Vec<String> = self.spectators.values().cloned().collect();
+        names.sort();
+        ServerMessage::Spectators { names }
+    }
+
+    // Watch the match without a player, so no spawn point and no lobby slot
+    pub(super) async fn handle_join_as_spectator(&mut self, addr: SocketAddr, name: String) {
+        if self.spectators.contains_key(&addr) {
+            let error_msg = ServerMessage::Error {
+                message: "Already spectating".to_string(),
+            };
+            self.send_message(addr, &error_msg).await;
+            return;
+        }
+
+        if self.is_name_taken(&name, Some(addr)) {
+            let error_msg = ServerMessage::NameAlreadyTaken;
+            self.send_message(addr, &error_msg).await;
+            return;
+        }
+
+        // A player switching to spectating leaves the game first
+        if let Some(player_id) = self.addr_to_id.remove(&addr) {
+            self.remove_player(&player_id).await;
+        }
+
+        log_info(&format!("Spectator {} joined", name));
+        self.ensure_maze();
+        self.spectators.insert(addr, name);
+        self.send_message(addr, &ServerMessage::SpectatorJoined)
+            .await;
+        self.send_match_to(addr).await;
+
+        let spectators_msg = self.spectators_message();
+        self.broadcast_to_others(addr, &spectators_msg).await;
+    }
+
+    pub(super) async fn remove_spectator(&mut self, addr: SocketAddr) {
+        if let Some(name) = self.spectators.remove(&addr) {
+            log_info(&format!("Spectator {} left", name));
+            let spectators_msg = self.spectators_message();
+            self.broadcast(&spectators_msg).await;
+        }
+    }
+}
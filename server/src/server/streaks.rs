@@ -0,0 +1,72 @@
+use shared::{
+    MIN_ANNOUNCED_STREAK, MULTI_KILL_WINDOW_SECS, ServerMessage, StreakKind, kill_streak_name,
+    streak_reward,
+};
+
+use super::{GameServer, unix_time_secs};
+use crate::utils::log_info;
+
+impl GameServer {
+    // Count a kill towards the killer's streak and multi-kill chain
+    pub(super) async fn record_streak_kill(&mut self, killer_id: &str) {
+        let now = unix_time_secs();
+        let Some(killer) = self.players.get_mut(killer_id) else {
+            return;
+        };
+
+        killer.streak += 1;
+        let (chain, last_kill) = killer.multi_kill;
+        let chain = if now - last_kill <= MULTI_KILL_WINDOW_SECS {
+            chain + 1
+        } else {
+            1
+        };
+        killer.multi_kill = (chain, now);
+        let streak = killer.streak;
+
+        if chain >= 2 {
+            let multi_msg = ServerMessage::Streak {
+                player_id: killer_id.to_string(),
+                kind: StreakKind::MultiKill,
+                count: chain,
+                reward: None,
+            };
+            self.broadcast(&multi_msg).await;
+        }
+
+        let reward = streak_reward(streak);
+        if kill_streak_name(streak).is_some() || reward.is_some() {
+            log_info(&format!("{} is on a {} kill streak", killer_id, streak));
+            let streak_msg = ServerMessage::Streak {
+                player_id: killer_id.to_string(),
+                kind: StreakKind::KillStreak,
+                count: streak,
+                reward,
+            };
+            self.broadcast(&streak_msg).await;
+        }
+    }
+
+    // A death ends the victim's streak, announced if it was a notable one
+    pub(super) async fn end_streak(&mut self, victim_id: &str, killer_id: Option<&str>) {
+        let Some(victim) = self.players.get_mut(victim_id) else {
+            return;
+        };
+
+        let streak = std::mem::take(&mut victim.streak);
+        victim.multi_kill = (0, 0.0);
+        if streak < MIN_ANNOUNCED_STREAK {
+            return;
+        }
+
+        let ended_msg = ServerMessage::Streak {
+            player_id: victim_id.to_string(),
+            kind: StreakKind::Ended {
+                ended_by: killer_id.map(str::to_string),
+            },
+            count: streak,
+            reward: None,
+        };
+        self.broadcast(&ended_msg).await;
+    }
+}
@@ -0,0 +1,107 @@
+use bevy::math::Vec3;
+use shared::{GameMode, ServerMessage, SpawnPoint, Team};
+
+use super::GameServer;
+
+impl GameServer {
+    // Put new players on the team with fewer members, breaking ties by score
+    pub(super) fn pick_balanced_team(&self) -> Team {
+        Team::ALL
+            .into_iter()
+            .min_by_key(|team| {
+                let members = self
+                    .players
+                    .values()
+                    .filter(|p| p.team == Some(*team))
+                    .count();
+                let score = self.team_scores.get(team).copied().unwrap_or(0);
+                (members, score)
+            })
+            .unwrap_or(Team::Red)
+    }
+
+    pub(super) fn is_friendly_fire(&self, shooter_id: &str, target_id: &str) -> bool {
+        match (self.players.get(shooter_id), self.players.get(target_id)) {
+            (Some(shooter), Some(target)) => shooter.is_teammate(target),
+            _ => false,
+        }
+    }
+
+    // Credit a kill to the killer's team in Team Deathmatch, ignoring team kills
+    pub(super) async fn award_team_kill(&mut self, killer_id: &str, victim_id: &str) {
+        if self.config.mode != GameMode::TeamDeathmatch
+            || self.is_friendly_fire(killer_id, victim_id)
+        {
+            return;
+        }
+
+        if let Some(team) = self.players.get(killer_id).and_then(|p| p.team) {
+            self.add_team_score(team).await;
+        }
+    }
+
+    pub(super) async fn add_team_score(&mut self, team: Team) {
+        *self.team_scores.entry(team).or_insert(0) += 1;
+
+        let scores_msg = ServerMessage::TeamScores {
+            team_scores: self.team_scores.clone(),
+        };
+        self.broadcast(&scores_msg).await;
+    }
+
+    pub(super) fn reset_team_scores(&mut self) {
+        self.team_scores = Team::ALL.into_iter().map(|team| (team, 0)).collect();
+    }
+
+    // The team with the strictly highest score, if any
+    pub(super) fn leading_team(&self) -> Option<Team> {
+        let mut scores: Vec<(Team, u32)> = self
+            .team_scores
+            .iter()
+            .map(|(team, score)| (*team, *score))
+            .collect();
+        scores.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
+
+        match scores.as_slice() {
+            [(team, best), (_, second), ..] if best > second => Some(*team),
+            [(team, _)] => Some(*team),
+            _ => None,
+        }
+    }
+
+    // Pick the free spawn point furthest from living enemies, if there are any
+    pub(super) fn get_team_spawn_point(
+        &mut self,
+        player_id: &str,
+        team: Team,
+    ) -> Option<SpawnPoint> {
+        let maze_data = self.maze_data.as_ref()?;
+
+        let enemy_positions: Vec<Vec3> = self
+            .players
+            .values()
+            .filter(|p| p.is_alive && p.team.is_some() && p.team != Some(team))
+            .map(|p| p.position)
+            .collect();
+        if enemy_positions.is_empty() {
+            return None;
+        }
+
+        let selected_idx = (0..maze_data.spawn_points.len())
+            .filter(|i| !self.is_spawn_point_used(*i))
+            .max_by(|a, b| {
+                let nearest_enemy = |idx: &usize| {
+                    let position = maze_data.spawn_points[*idx].position;
+                    enemy_positions
+                        .iter()
+                        .map(|enemy| enemy.distance(position))
+                        .fold(f32::INFINITY, f32::min)
+                };
+                nearest_enemy(a).total_cmp(&nearest_enemy(b))
+            })?;
+
+        self.used_spawn_points
+            .insert(player_id.to_string(), selected_idx);
+        Some(maze_data.spawn_points[selected_idx].clone())
+    }
+}
@@ -0,0 +1,1505 @@
+use bevy::math::{Quat, Vec3};
+use shared::{
+    Ammo, ChatChannel, ClientMessage, DamageKind, FLAG_TOUCH_RADIUS, FlagEvent, GameMode,
+    GameState, KILLCAM_SECS, MAX_CHAT_LENGTH, MazeConfig, MazeGrid, MemoryNetwork,
+    NetworkConditions, PLAYER_HIT_RADIUS, PickupKind, REPLAY_VERSION, ReplayReader, ReplayRecord,
+    ServerMessage, SimulatedTransport, StreakKind, Team, Transport, WeaponKind, ZONE_CAPTURE_SECS,
+    ZONE_RADIUS, ZoneOwner, generate_maze_from_config, grid_to_world, weapons,
+};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+use tokio::time::{Duration, Instant};
+
+use super::{GameServer, RESPAWN_DELAY};
+use crate::config::ServerConfig;
+
+const RECV_TIMEOUT: Duration = Duration::from_secs(2);
+const QUIET_PERIOD: Duration = Duration::from_millis(300); // How long "nothing arrives" is waited for
+const SHOT_INTERVAL: Duration = Duration::from_millis(450); // Just over the pistol's cooldown
+const EYE_HEIGHT: f32 = 1.0; // Height the server places players at
+
+// A game server running in-process on an in-memory network
+struct TestServer {
+    network: MemoryNetwork,
+    addr: SocketAddr,
+    shutdown: Option<oneshot::Sender<()>>,
+    handle: JoinHandle<()>,
+}
+
+impl TestServer {
+    // Warmup never ends by default, so nothing but the test drives the match
+    async fn start() -> Self {
+        Self::start_with(ServerConfig {
+            min_players: 99,
+            ..ServerConfig::default()
+        })
+        .await
+    }
+
+    async fn start_with(config: ServerConfig) -> Self {
+        let network = MemoryNetwork::new();
+        let transport = network.endpoint();
+        let addr = transport.local_addr().unwrap();
+        let (shutdown, shutdown_rx) = oneshot::channel();
+
+        let mut server = GameServer::new(Box::new(transport), config);
+        let handle = tokio::spawn(async move {
+            tokio::select! {
+                _ = server.listen_and_serve() => {}
+                _ = shutdown_rx => server.shutdown_gracefully().await,
+            }
+        });
+
+        Self {
+            network,
+            addr,
+            shutdown: Some(shutdown),
+            handle,
+        }
+    }
+
+    async fn shutdown(mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+        self.handle.await.unwrap();
+    }
+}
+
+// A scripted player talking to the server through its own transport
+struct TestClient {
+    transport: Box<dyn Transport>,
+    server_addr: SocketAddr,
+    player_id: String,
+    seed: u64,
+    grid: Option<MazeGrid>,
+}
+
+impl TestClient {
+    fn connect(server: &TestServer) -> Self {
+        Self::connect_over(server, Box::new(server.network.endpoint()))
+    }
+
+    fn connect_over(server: &TestServer, transport: Box<dyn Transport>) -> Self {
+        Self {
+            transport,
+            server_addr: server.addr,
+            player_id: String::new(),
+            seed: 0,
+            grid: None,
+        }
+    }
+
+    // Connect and join, waiting for the maze so positions can be planned
+    async fn join(server: &TestServer, name: &str) -> Self {
+        let mut client = Self::connect(server);
+        client
+            .send(ClientMessage::JoinGame {
+                player_name: name.to_string(),
+            })
+            .await;
+
+        client.player_id = client
+            .expect(|msg| match msg {
+                ServerMessage::GameJoined { player_id } => Some(player_id.clone()),
+                _ => None,
+            })
+            .await;
+        let (seed, grid) = client.expect_maze().await;
+        client.seed = seed;
+        client.grid = Some(grid);
+        client
+    }
+
+    // Wait for the maze of the current or next round
+    async fn expect_maze(&self) -> (u64, MazeGrid) {
+        self.expect(|msg| match msg {
+            ServerMessage::GameStarted {
+                seed,
+                width,
+                height,
+                difficulty,
+            } => Some((
+                *seed,
+                generate_maze_from_config(&MazeConfig::new(*seed, *width, *height, difficulty))
+                    .grid,
+            )),
+            _ => None,
+        })
+        .await
+    }
+
+    // Wait for the match to move into a state, returning the seconds it lasts
+    async fn expect_state(&self, expected: GameState) -> Option<f32> {
+        self.expect(|msg| match msg {
+            ServerMessage::MatchStateChanged {
+                state,
+                time_remaining,
+                ..
+            } if *state == expected => Some(*time_remaining),
+            _ => None,
+        })
+        .await
+    }
+
+    async fn send(&self, msg: ClientMessage) {
+        let encoded = bincode::serde::encode_to_vec(&msg, bincode::config::standard()).unwrap();
+        self.transport.send_to(&encoded, self.server_addr).unwrap();
+    }
+
+    async fn recv_within(&self, timeout: Duration) -> Option<ServerMessage> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            if let Some((packet, _)) = self.transport.try_recv_from().unwrap() {
+                let (msg, _) =
+                    bincode::serde::decode_from_slice(&packet, bincode::config::standard())
+                        .unwrap();
+                return Some(msg);
+            }
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            match tokio::time::timeout(remaining, self.transport.readable()).await {
+                Ok(ready) => ready.unwrap(),
+                Err(_) => return None,
+            }
+        }
+    }
+
+    // Skip messages until one matches, failing the test if none arrives in time
+    async fn expect<T>(&self, mut matches: impl FnMut(&ServerMessage) -> Option<T>) -> T {
+        let deadline = Instant::now() + RECV_TIMEOUT;
+        let mut skipped = Vec::new();
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            let Some(msg) = self.recv_within(remaining).await else {
+                panic!("expected message never arrived, got: {:#?}", skipped);
+            };
+            if let Some(value) = matches(&msg) {
+                return value;
+            }
+            skipped.push(msg);
+        }
+    }
+
+    // Fail the test if a matching message arrives within the quiet period
+    async fn expect_none(&self, mut matches: impl FnMut(&ServerMessage) -> bool) {
+        let deadline = Instant::now() + QUIET_PERIOD;
+        while let Some(msg) = self
+            .recv_within(deadline.saturating_duration_since(Instant::now()))
+            .await
+        {
+            assert!(!matches(&msg), "unexpected message: {:#?}", msg);
+        }
+    }
+
+    async fn move_to(&self, position: Vec3) {
+        self.send(ClientMessage::PlayerMove {
+            position,
+            rotation: Quat::IDENTITY,
+        })
+        .await;
+    }
+
+    async fn shoot_at(&self, origin: Vec3, target: Vec3) {
+        self.send(ClientMessage::PlayerShoot {
+            origin,
+            direction: (target - origin).normalize(),
+        })
+        .await;
+    }
+
+    fn grid(&self) -> &MazeGrid {
+        self.grid.as_ref().unwrap()
+    }
+}
+
+fn cell_position(grid: &MazeGrid, x: usize, y: usize) -> Vec3 {
+    grid_to_world(grid, x, y) + Vec3::Y * EYE_HEIGHT
+}
+
+// Every two side by side open cells, for players standing face to face
+fn open_pairs(grid: &MazeGrid) -> impl Iterator<Item = (Vec3, Vec3)> + '_ {
+    grid.iter().enumerate().flat_map(move |(y, row)| {
+        (0..row.len() - 1)
+            .filter(|&x| !row[x] && !row[x + 1])
+            .map(move |x| (cell_position(grid, x, y), cell_position(grid, x + 1, y)))
+    })
+}
+
+fn open_pair(grid: &MazeGrid) -> (Vec3, Vec3) {
+    open_pairs(grid)
+        .next()
+        .expect("maze has no two open cells side by side")
+}
+
+// Two open cells with a single wall cell between them
+fn walled_pair(grid: &MazeGrid) -> (Vec3, Vec3) {
+    for (y, row) in grid.iter().enumerate() {
+        for x in 0..row.len() - 2 {
+            if !row[x] && row[x + 1] && !row[x + 2] {
+                return (cell_position(grid, x, y), cell_position(grid, x + 2, y));
+            }
+        }
+    }
+    panic!("maze has no wall between two open cells");
+}
+
+// Open cells in a straight line along a row, for shots with some way to travel
+fn open_run(grid: &MazeGrid, cells: usize) -> Vec<Vec3> {
+    for (y, row) in grid.iter().enumerate() {
+        for x in 0..=row.len() - cells {
+            if row[x..x + cells].iter().all(|wall| !wall) {
+                return (x..x + cells).map(|x| cell_position(grid, x, y)).collect();
+            }
+        }
+    }
+    panic!("maze has no {} open cells in a row", cells);
+}
+
+async fn kill(shooter: &TestClient, target: &TestClient) {
+    let (origin, position) = open_pair(shooter.grid());
+    kill_from(shooter, target, origin, position).await;
+}
+
+// Put the shooter and target face to face and keep shooting until the target dies
+async fn kill_from(shooter: &TestClient, target: &TestClient, origin: Vec3, position: Vec3) {
+    shooter.move_to(origin).await;
+    target.move_to(position).await;
+
+    let deadline = Instant::now() + Duration::from_secs(10);
+    loop {
+        assert!(Instant::now() < deadline, "target never died");
+        shooter.shoot_at(origin, position).await;
+
+        let shot_until = Instant::now() + SHOT_INTERVAL;
+        while let Some(msg) = shooter
+            .recv_within(shot_until.saturating_duration_since(Instant::now()))
+            .await
+        {
+            if let ServerMessage::PlayerDied { player_id, .. } = msg
+                && player_id == target.player_id
+            {
+                return;
+            }
+        }
+    }
+}
+
+#[tokio::test]
+async fn join_sends_welcome_and_announces_player() {
+    let server = TestServer::start().await;
+    let alice = TestClient::connect(&server);
+    alice
+        .send(ClientMessage::JoinGame {
+            player_name: "alice".to_string(),
+        })
+        .await;
+
+    let alice_id = alice
+        .expect(|msg| match msg {
+            ServerMessage::GameJoined { player_id } => Some(player_id.clone()),
+            _ => None,
+        })
+        .await;
+    let players = alice
+        .expect(|msg| match msg {
+            ServerMessage::GameState { players, .. } => Some(players.clone()),
+            _ => None,
+        })
+        .await;
+    assert_eq!(players[&alice_id].name, "alice");
+    alice
+        .expect(|msg| matches!(msg, ServerMessage::GameStarted { .. }).then_some(()))
+        .await;
+
+    let bob = TestClient::join(&server, "bob").await;
+    let joined = alice
+        .expect(|msg| match msg {
+            ServerMessage::PlayerJoined { player } => Some(player.clone()),
+            _ => None,
+        })
+        .await;
+    assert_eq!(joined.id, bob.player_id);
+    assert_eq!(joined.name, "bob");
+}
+
+#[tokio::test]
+async fn duplicate_names_are_rejected() {
+    let server = TestServer::start().await;
+    let _alice = TestClient::join(&server, "alice").await;
+
+    let impostor = TestClient::connect(&server);
+    impostor
+        .send(ClientMessage::JoinGame {
+            player_name: "alice".to_string(),
+        })
+        .await;
+    impostor
+        .expect(|msg| matches!(msg, ServerMessage::NameAlreadyTaken).then_some(()))
+        .await;
+}
+
+#[tokio::test]
+async fn movement_is_relayed_to_other_players() {
+    let server = TestServer::start().await;
+    let alice = TestClient::join(&server, "alice").await;
+    let bob = TestClient::join(&server, "bob").await;
+
+    let (position, _) = open_pair(alice.grid());
+    alice.move_to(position).await;
+
+    let moved = bob
+        .expect(|msg| match msg {
+            ServerMessage::PlayerMoved {
+                player_id,
+                position,
+                ..
+            } => Some((player_id.clone(), *position)),
+            _ => None,
+        })
+        .await;
+    assert_eq!(moved, (alice.player_id.clone(), position));
+
+    // Players aren't told about their own movement
+    alice
+        .expect_none(|msg| matches!(msg, ServerMessage::PlayerMoved { .. }))
+        .await;
+}
+
+#[tokio::test]
+async fn shots_hit_in_the_open_but_not_through_walls() {
+    let server = TestServer::start().await;
+    let alice = TestClient::join(&server, "alice").await;
+    let bob = TestClient::join(&server, "bob").await;
+
+    let (origin, target) = walled_pair(alice.grid());
+    alice.move_to(origin).await;
+    bob.move_to(target).await;
+    alice.shoot_at(origin, target).await;
+    let hit = alice
+        .expect(|msg| match msg {
+            ServerMessage::PlayerShot { hit_result, .. } => Some(hit_result.hit),
+            _ => None,
+        })
+        .await;
+    assert!(!hit, "shot went through a wall");
+    bob.expect_none(|msg| matches!(msg, ServerMessage::PlayerDamaged { .. }))
+        .await;
+
+    let (origin, target) = open_pair(alice.grid());
+    alice.move_to(origin).await;
+    bob.move_to(target).await;
+    tokio::time::sleep(SHOT_INTERVAL).await;
+    alice.shoot_at(origin, target).await;
+    let hit_id = alice
+        .expect(|msg| match msg {
+            ServerMessage::PlayerShot { hit_result, .. } => Some(hit_result.hit_player_id.clone()),
+            _ => None,
+        })
+        .await;
+    assert_eq!(hit_id, Some(bob.player_id.clone()));
+}
+
+#[tokio::test]
+async fn shots_faster_than_the_fire_rate_are_dropped() {
+    let server = TestServer::start().await;
+    let alice = TestClient::join(&server, "alice").await;
+    let shot = |msg: &ServerMessage| matches!(msg, ServerMessage::PlayerShot { .. });
+
+    let (origin, _) = open_pair(alice.grid());
+    let sky = origin + Vec3::Y * 10.0;
+    alice.move_to(origin).await;
+    alice.shoot_at(origin, sky).await;
+    alice.shoot_at(origin, sky).await;
+    alice.expect(|msg| shot(msg).then_some(())).await;
+    alice.expect_none(shot).await;
+
+    // Once the cooldown has passed the next shot goes through
+    tokio::time::sleep(SHOT_INTERVAL).await;
+    alice.shoot_at(origin, sky).await;
+    alice.expect(|msg| shot(msg).then_some(())).await;
+}
+
+#[tokio::test]
+async fn empty_magazine_reloads_from_the_reserve() {
+    let server = TestServer::start().await;
+    let alice = TestClient::join(&server, "alice").await;
+    let pistol = weapons().get(WeaponKind::Pistol);
+    let ammo_update = |msg: &ServerMessage| match msg {
+        ServerMessage::AmmoUpdate { status } => Some((status.ammo, status.reload_remaining)),
+        _ => None,
+    };
+
+    let (origin, _) = open_pair(alice.grid());
+    let sky = origin + Vec3::Y * 10.0;
+    alice.move_to(origin).await;
+    for fired in 1..=pistol.magazine_size {
+        alice.shoot_at(origin, sky).await;
+        let magazine = pistol.magazine_size - fired;
+        alice
+            .expect(|msg| ammo_update(msg).filter(|(ammo, _)| ammo.magazine == magazine))
+            .await;
+        tokio::time::sleep(SHOT_INTERVAL).await;
+    }
+
+    // The last round starts a reload without waiting for the trigger or R
+    let (_, reload_remaining) = alice.expect(ammo_update).await;
+    assert!(reload_remaining.is_some_and(|secs| secs <= pistol.reload_secs));
+    let (ammo, reload_remaining) = alice.expect(ammo_update).await;
+    assert_eq!(reload_remaining, None);
+    assert_eq!(
+        ammo,
+        Ammo {
+            magazine: pistol.magazine_size,
+            reserve: pistol.reserve_ammo - pistol.magazine_size,
+        }
+    );
+}
+
+#[tokio::test]
+async fn projectiles_fly_and_splash_on_impact() {
+    let server = TestServer::start().await;
+    let alice = TestClient::join(&server, "alice").await;
+    let bob = TestClient::join(&server, "bob").await;
+    let carol = TestClient::join(&server, "carol").await;
+    let plasma = weapons().get(WeaponKind::PlasmaRifle);
+
+    // Bob stands down the corridor with carol right behind him
+    let run = open_run(alice.grid(), 4);
+    let (origin, target) = (run[0], run[3]);
+    alice.move_to(origin).await;
+    bob.move_to(target).await;
+    carol
+        .move_to(target + (target - origin).normalize() * 1.0)
+        .await;
+    alice
+        .send(ClientMessage::SwitchWeapon {
+            weapon: WeaponKind::PlasmaRifle,
+        })
+        .await;
+    tokio::time::sleep(QUIET_PERIOD).await;
+    alice.shoot_at(origin, target).await;
+
+    // The shot travels over several ticks before blowing up on bob
+    let mut flight = Vec::new();
+    let exploded_at = loop {
+        match alice.recv_within(RECV_TIMEOUT).await {
+            Some(ServerMessage::ProjectileStates { projectiles }) => {
+                flight.extend(projectiles.iter().map(|p| p.position.distance(origin)));
+            }
+            Some(ServerMessage::ProjectileExploded { position, .. }) => break position,
+            Some(_) => {}
+            None => panic!("projectile never exploded"),
+        }
+    };
+    assert!(flight.len() > 1, "projectile didn't travel: {:?}", flight);
+    assert!(flight.is_sorted());
+    assert!(exploded_at.distance(target) <= PLAYER_HIT_RADIUS + 1.0);
+
+    // Bob takes the direct hit, carol only the splash
+    let mut damage = HashMap::new();
+    while damage.len() < 2 {
+        let (player_id, amount) = alice
+            .expect(|msg| match msg {
+                ServerMessage::PlayerDamaged {
+                    player_id, damage, ..
+                } => Some((player_id.clone(), *damage)),
+                _ => None,
+            })
+            .await;
+        damage.insert(player_id, amount);
+    }
+    assert_eq!(damage[&bob.player_id], plasma.damage);
+    assert!(damage[&carol.player_id] > 0.0);
+    assert!(damage[&carol.player_id] < plasma.damage);
+}
+
+#[tokio::test]
+async fn damage_reaches_shooter_and_victim() {
+    let server = TestServer::start().await;
+    let alice = TestClient::join(&server, "alice").await;
+    let bob = TestClient::join(&server, "bob").await;
+
+    let (origin, target) = open_pair(alice.grid());
+    alice.move_to(origin).await;
+    bob.move_to(target).await;
+    tokio::time::sleep(SHOT_INTERVAL).await;
+    alice.shoot_at(origin, target).await;
+
+    // The shooter learns it landed, for a hit marker
+    let shooter_health = alice
+        .expect(|msg| match msg {
+            ServerMessage::PlayerDamaged {
+                player_id, health, ..
+            } if *player_id == bob.player_id => Some(*health),
+            _ => None,
+        })
+        .await;
+
+    // The victim learns who hit them and from where
+    let (damage_by, source_position, victim_health) = bob
+        .expect(|msg| match msg {
+            ServerMessage::PlayerDamaged {
+                damage_by,
+                source_position,
+                health,
+                ..
+            } => Some((damage_by.clone(), *source_position, *health)),
+            _ => None,
+        })
+        .await;
+    assert_eq!(damage_by, Some(alice.player_id.clone()));
+    assert!(source_position.is_some_and(|p| p.distance(origin) < 0.01));
+    assert_eq!(shooter_health, victim_health);
+}
+
+#[tokio::test]
+async fn death_is_broadcast_with_killer() {
+    let server = TestServer::start().await;
+    let alice = TestClient::join(&server, "alice").await;
+    let bob = TestClient::join(&server, "bob").await;
+
+    kill(&alice, &bob).await;
+    let killer = bob
+        .expect(|msg| match msg {
+            ServerMessage::PlayerDied {
+                player_id,
+                killer_id,
+                ..
+            } if *player_id == bob.player_id => Some(killer_id.clone()),
+            _ => None,
+        })
+        .await;
+    assert_eq!(killer, Some(alice.player_id.clone()));
+}
+
+#[tokio::test]
+async fn assists_reset_when_the_victim_respawns() {
+    let server = TestServer::start().await;
+    let alice = TestClient::join(&server, "alice").await;
+    let bob = TestClient::join(&server, "bob").await;
+    let carol = TestClient::join(&server, "carol").await;
+    let assists = |msg: &ServerMessage| match msg {
+        ServerMessage::PlayerDied {
+            player_id,
+            assist_ids,
+            ..
+        } if *player_id == bob.player_id => Some(assist_ids.clone()),
+        _ => None,
+    };
+
+    // Carol softens bob up, then alice finishes him off
+    let (origin, target) = open_pair(alice.grid());
+    carol.move_to(origin).await;
+    bob.move_to(target).await;
+    carol.shoot_at(origin, target).await;
+    carol
+        .expect(|msg| match msg {
+            ServerMessage::PlayerDamaged { player_id, .. } if *player_id == bob.player_id => {
+                Some(())
+            }
+            _ => None,
+        })
+        .await;
+    carol.move_to(origin + Vec3::Y * 20.0).await;
+    kill(&alice, &bob).await;
+    assert_eq!(bob.expect(assists).await, vec![carol.player_id.clone()]);
+
+    // Carol's hit died with the old life, however recent it was
+    tokio::time::sleep(RESPAWN_DELAY).await;
+    bob.send(ClientMessage::Respawn).await;
+    bob.expect(|msg| matches!(msg, ServerMessage::PlayerRespawned { .. }).then_some(()))
+        .await;
+    kill(&alice, &bob).await;
+    assert_eq!(bob.expect(assists).await, Vec::<String>::new());
+}
+
+#[tokio::test]
+async fn leaving_the_maze_hurts_with_nobody_to_blame() {
+    let server = TestServer::start().await;
+    let alice = TestClient::join(&server, "alice").await;
+
+    alice.move_to(Vec3::new(1000.0, EYE_HEIGHT, 1000.0)).await;
+    let damage_by = alice
+        .expect(|msg| match msg {
+            ServerMessage::PlayerDamaged { damage_by, .. } => Some(damage_by.clone()),
+            _ => None,
+        })
+        .await;
+    assert_eq!(damage_by, None);
+
+    let (killer_id, kind) = alice
+        .expect(|msg| match msg {
+            ServerMessage::PlayerDied {
+                killer_id, kind, ..
+            } => Some((killer_id.clone(), *kind)),
+            _ => None,
+        })
+        .await;
+    assert_eq!(killer_id, None);
+    assert_eq!(kind, DamageKind::Environmental);
+}
+
+#[tokio::test]
+async fn streaks_and_multi_kills_are_announced() {
+    let server = TestServer::start().await;
+    let alice = TestClient::join(&server, "alice").await;
+    let bob = TestClient::join(&server, "bob").await;
+    let carol = TestClient::join(&server, "carol").await;
+    let streak = |msg: &ServerMessage| match msg {
+        ServerMessage::Streak {
+            player_id,
+            kind,
+            count,
+            ..
+        } => Some((player_id.clone(), kind.clone(), *count)),
+        _ => None,
+    };
+
+    // Two kills in quick succession, then a third once bob is back, as carol hears it
+    kill(&alice, &bob).await;
+    kill(&alice, &carol).await;
+    tokio::time::sleep(RESPAWN_DELAY).await;
+    bob.send(ClientMessage::Respawn).await;
+    bob.expect(|msg| matches!(msg, ServerMessage::PlayerRespawned { .. }).then_some(()))
+        .await;
+    kill(&alice, &bob).await;
+    assert_eq!(
+        carol.expect(streak).await,
+        (alice.player_id.clone(), StreakKind::MultiKill, 2)
+    );
+    assert_eq!(
+        carol.expect(streak).await,
+        (alice.player_id.clone(), StreakKind::KillStreak, 3)
+    );
+
+    // Whoever ends a spree gets the credit
+    tokio::time::sleep(RESPAWN_DELAY).await;
+    bob.send(ClientMessage::Respawn).await;
+    kill(&bob, &alice).await;
+    let ended = StreakKind::Ended {
+        ended_by: Some(bob.player_id.clone()),
+    };
+    assert_eq!(
+        carol.expect(streak).await,
+        (alice.player_id.clone(), ended, 3)
+    );
+}
+
+#[tokio::test]
+async fn victim_gets_killcam_from_killers_view() {
+    let server = TestServer::start().await;
+    let alice = TestClient::join(&server, "alice").await;
+    let bob = TestClient::join(&server, "bob").await;
+
+    kill(&alice, &bob).await;
+    let (killer_name, killer_frames, victim_frames, shots) = bob
+        .expect(|msg| match msg {
+            ServerMessage::Killcam {
+                killer_id,
+                killer_name,
+                killer_frames,
+                victim_frames,
+                shots,
+                ..
+            } if *killer_id == alice.player_id => Some((
+                killer_name.clone(),
+                killer_frames.clone(),
+                victim_frames.clone(),
+                shots.clone(),
+            )),
+            _ => None,
+        })
+        .await;
+    assert_eq!(killer_name, "alice");
+
+    // Both tracks end where the players stood at the kill
+    let (origin, position) = open_pair(alice.grid());
+    assert_eq!(killer_frames.last().unwrap().position, origin);
+    assert_eq!(victim_frames.last().unwrap().position, position);
+    let window_ms = (KILLCAM_SECS * 1000.0) as u32;
+    assert!(killer_frames.is_sorted_by_key(|f| f.time_ms));
+    assert!(killer_frames.iter().all(|f| f.time_ms <= window_ms));
+    assert!(!shots.is_empty());
+    assert!(shots.iter().all(|s| s.origin == origin));
+
+    // Only the victim gets one
+    alice
+        .expect_none(|msg| matches!(msg, ServerMessage::Killcam { .. }))
+        .await;
+}
+
+#[tokio::test]
+async fn pings_are_measured_and_shared() {
+    let server = TestServer::start().await;
+    let alice = TestClient::join(&server, "alice").await;
+    let bob = TestClient::join(&server, "bob").await;
+
+    let seq = alice
+        .expect(|msg| match msg {
+            ServerMessage::Ping { seq } => Some(*seq),
+            _ => None,
+        })
+        .await;
+    alice.send(ClientMessage::Pong { seq }).await;
+
+    // Everyone hears about alice's round trip; bob never answered so has none
+    let pings = bob
+        .expect(|msg| match msg {
+            ServerMessage::Pings { pings } => Some(pings.clone()),
+            _ => None,
+        })
+        .await;
+    assert!(pings[&alice.player_id] < RECV_TIMEOUT.as_millis() as u32);
+    assert!(!pings.contains_key(&bob.player_id));
+}
+
+#[tokio::test]
+async fn chat_is_trimmed_filtered_and_rate_limited() {
+    let server = TestServer::start_with(ServerConfig {
+        blocked_words: vec!["darn".to_string()],
+        ..ServerConfig::default()
+    })
+    .await;
+    let alice = TestClient::join(&server, "alice").await;
+    let bob = TestClient::join(&server, "bob").await;
+    let chat_text = |msg: &ServerMessage| match msg {
+        ServerMessage::Chat {
+            sender_name, text, ..
+        } if sender_name == "alice" => Some(text.clone()),
+        _ => None,
+    };
+    let error_text = |msg: &ServerMessage| match msg {
+        ServerMessage::Error { message } => Some(message.clone()),
+        _ => None,
+    };
+    let say = |channel, text: &str| ClientMessage::Chat {
+        channel,
+        text: text.to_string(),
+    };
+
+    alice
+        .send(say(ChatChannel::All, "  DARN it\u{7}, darned  "))
+        .await;
+    assert_eq!(bob.expect(chat_text).await, "**** it, darned");
+    alice.send(say(ChatChannel::All, &"a".repeat(500))).await;
+    assert_eq!(bob.expect(chat_text).await.len(), MAX_CHAT_LENGTH);
+
+    // Nobody has a team in deathmatch
+    alice.send(say(ChatChannel::Team, "hello team")).await;
+    assert!(alice.expect(error_text).await.contains("team modes"));
+    bob.expect_none(|msg| chat_text(msg).is_some()).await;
+
+    for _ in 0..3 {
+        alice.send(say(ChatChannel::All, "spam")).await;
+    }
+    assert!(alice.expect(error_text).await.contains("too quickly"));
+}
+
+#[tokio::test]
+async fn match_runs_from_warmup_to_the_next_round() {
+    let server = TestServer::start_with(ServerConfig {
+        countdown: Duration::from_millis(200),
+        time_limit: None,
+        frag_limit: Some(1),
+        intermission: Duration::from_millis(300),
+        ..ServerConfig::default()
+    })
+    .await;
+
+    // Warmup until the second player arrives, then a countdown into the match
+    let alice = TestClient::join(&server, "alice").await;
+    alice.expect_state(GameState::WaitingForPlayers).await;
+    let bob = TestClient::join(&server, "bob").await;
+    let countdown = alice.expect_state(GameState::Countdown).await;
+    assert!(countdown.is_some_and(|secs| secs <= 0.2));
+    alice.expect_state(GameState::GameStarted).await;
+
+    // The first kill reaches the frag limit
+    kill(&alice, &bob).await;
+    alice.expect_state(GameState::GameOver).await;
+    let (winner_id, reason) = alice
+        .expect(|msg| match msg {
+            ServerMessage::MatchResults {
+                winner_id, reason, ..
+            } => Some((winner_id.clone(), reason.clone())),
+            _ => None,
+        })
+        .await;
+    assert_eq!(winner_id, Some(alice.player_id.clone()));
+    assert_eq!(reason, "Frag limit reached");
+
+    // After the intermission a new round starts on a fresh maze with clean stats
+    let (seed, _) = alice.expect_maze().await;
+    assert_ne!(seed, alice.seed, "new round reused the maze");
+    alice.expect_state(GameState::WaitingForPlayers).await;
+    alice.expect_state(GameState::Countdown).await;
+    alice.expect_state(GameState::GameStarted).await;
+    let players = alice
+        .expect(|msg| match msg {
+            ServerMessage::GameState { players, .. } => Some(players.clone()),
+            _ => None,
+        })
+        .await;
+    assert!(players.values().all(|p| p.kills == 0 && p.deaths == 0));
+    assert!(players.values().all(|p| p.is_alive));
+}
+
+#[tokio::test]
+async fn match_ends_at_the_time_limit() {
+    let server = TestServer::start_with(ServerConfig {
+        min_players: 1,
+        countdown: Duration::ZERO,
+        time_limit: Some(Duration::from_millis(300)),
+        ..ServerConfig::default()
+    })
+    .await;
+    let alice = TestClient::join(&server, "alice").await;
+
+    let time_limit = alice.expect_state(GameState::GameStarted).await;
+    assert!(time_limit.is_some_and(|secs| secs <= 0.3));
+    let reason = alice
+        .expect(|msg| match msg {
+            ServerMessage::MatchResults { reason, .. } => Some(reason.clone()),
+            _ => None,
+        })
+        .await;
+    assert_eq!(reason, "Time limit reached");
+}
+
+#[tokio::test]
+async fn flags_are_picked_up_returned_and_captured() {
+    let server = TestServer::start_with(ServerConfig {
+        mode: GameMode::CaptureTheFlag,
+        countdown: Duration::ZERO,
+        time_limit: None,
+        ..ServerConfig::default()
+    })
+    .await;
+    let alice = TestClient::join(&server, "alice").await;
+    let bob = TestClient::join(&server, "bob").await;
+    alice.expect_state(GameState::GameStarted).await;
+    let players = alice
+        .expect(|msg| match msg {
+            ServerMessage::GameState { players, .. } => Some(players.clone()),
+            _ => None,
+        })
+        .await;
+    let flags = alice
+        .expect(|msg| match msg {
+            ServerMessage::FlagStates { flags } => Some(flags.clone()),
+            _ => None,
+        })
+        .await;
+    let alice_team = players[&alice.player_id].team.unwrap();
+    let base = |team: Team| flags.iter().find(|f| f.team == team).unwrap().base + Vec3::Y;
+    let (own_base, enemy_base) = (
+        base(alice_team),
+        base(players[&bob.player_id].team.unwrap()),
+    );
+    let flag_event = |msg: &ServerMessage| match msg {
+        ServerMessage::FlagUpdate {
+            event, player_id, ..
+        } => Some((*event, player_id.clone())),
+        _ => None,
+    };
+
+    // A defender standing on their own flag doesn't stop an attacker taking it
+    bob.move_to(enemy_base).await;
+    tokio::time::sleep(QUIET_PERIOD).await;
+    alice.move_to(enemy_base).await;
+    let picked_up = (FlagEvent::PickedUp, Some(alice.player_id.clone()));
+    assert_eq!(alice.expect(flag_event).await, picked_up);
+
+    // The carrier drops it on death away from both bases, and the defender takes it home
+    let (origin, dropped_at) = open_pairs(alice.grid())
+        .find(|(a, b)| {
+            [own_base, enemy_base]
+                .iter()
+                .all(|base| a.distance(*base).min(b.distance(*base)) > FLAG_TOUCH_RADIUS * 2.0)
+        })
+        .unwrap();
+    kill_from(&bob, &alice, origin, dropped_at).await;
+    assert_eq!(
+        alice.expect(flag_event).await,
+        (FlagEvent::Dropped, Some(alice.player_id.clone()))
+    );
+    bob.move_to(dropped_at).await;
+    assert_eq!(
+        alice.expect(flag_event).await,
+        (FlagEvent::Returned, Some(bob.player_id.clone()))
+    );
+
+    // Back to life, the attacker brings it to their own base for a capture
+    tokio::time::sleep(RESPAWN_DELAY).await;
+    alice.send(ClientMessage::Respawn).await;
+    alice
+        .expect(|msg| matches!(msg, ServerMessage::PlayerRespawned { .. }).then_some(()))
+        .await;
+    alice.move_to(enemy_base).await;
+    assert_eq!(alice.expect(flag_event).await, picked_up);
+    alice.move_to(own_base).await;
+    assert_eq!(
+        alice.expect(flag_event).await,
+        (FlagEvent::Captured, Some(alice.player_id.clone()))
+    );
+    let team_scores = alice
+        .expect(|msg| match msg {
+            ServerMessage::TeamScores { team_scores } => Some(team_scores.clone()),
+            _ => None,
+        })
+        .await;
+    assert_eq!(team_scores[&alice_team], 1);
+}
+
+#[tokio::test]
+async fn hill_is_captured_and_scores_for_its_owner() {
+    let server = TestServer::start_with(ServerConfig {
+        mode: GameMode::KingOfTheHill,
+        countdown: Duration::ZERO,
+        time_limit: None,
+        hill_limit: Some(2),
+        ..ServerConfig::default()
+    })
+    .await;
+    let alice = TestClient::join(&server, "alice").await;
+    let bob = TestClient::join(&server, "bob").await;
+    alice.expect_state(GameState::GameStarted).await;
+    let zone_update = |msg: &ServerMessage| match msg {
+        ServerMessage::ZoneUpdate { zone, .. } => Some(zone.clone()),
+        _ => None,
+    };
+    let hill = alice.expect(zone_update).await.center + Vec3::Y * EYE_HEIGHT;
+
+    // Two players on the hill contest it, so nobody makes progress
+    alice.move_to(hill).await;
+    bob.move_to(hill).await;
+    alice
+        .expect(|msg| zone_update(msg).filter(|zone| zone.contested))
+        .await;
+
+    // Left alone, alice takes the hill and then scores a point a second
+    bob.move_to(hill + Vec3::X * ZONE_RADIUS * 3.0).await;
+    tokio::time::sleep(Duration::from_secs_f32(ZONE_CAPTURE_SECS)).await;
+    let owner = Some(ZoneOwner::Player(alice.player_id.clone()));
+    alice
+        .expect(|msg| zone_update(msg).filter(|zone| zone.owner == owner))
+        .await;
+    for expected in 1..=2 {
+        let (player_id, score) = alice
+            .expect(|msg| match msg {
+                ServerMessage::PlayerScored { player_id, score } => {
+                    Some((player_id.clone(), *score))
+                }
+                _ => None,
+            })
+            .await;
+        assert_eq!((player_id, score), (alice.player_id.clone(), expected));
+    }
+    let (winner_id, reason) = alice
+        .expect(|msg| match msg {
+            ServerMessage::MatchResults {
+                winner_id, reason, ..
+            } => Some((winner_id.clone(), reason.clone())),
+            _ => None,
+        })
+        .await;
+    assert_eq!(winner_id, Some(alice.player_id.clone()));
+    assert_eq!(reason, "Hill limit reached");
+}
+
+#[tokio::test]
+async fn eliminated_players_wait_for_the_next_round() {
+    let server = TestServer::start_with(ServerConfig {
+        mode: GameMode::Elimination,
+        countdown: Duration::ZERO,
+        time_limit: None,
+        ..ServerConfig::default()
+    })
+    .await;
+    let alice = TestClient::join(&server, "alice").await;
+    let bob = TestClient::join(&server, "bob").await;
+    alice.expect_state(GameState::GameStarted).await;
+
+    // The last one standing wins the round
+    kill(&alice, &bob).await;
+    let winner_id = alice
+        .expect(|msg| match msg {
+            ServerMessage::RoundOver {
+                round: 1,
+                winner_id,
+            } => Some(winner_id.clone()),
+            _ => None,
+        })
+        .await;
+    assert_eq!(winner_id, Some(alice.player_id.clone()));
+
+    // Past the usual respawn delay, but the round is still wrapping up
+    tokio::time::sleep(RESPAWN_DELAY).await;
+    bob.send(ClientMessage::Respawn).await;
+    let error = bob
+        .expect(|msg| match msg {
+            ServerMessage::Error { message } => Some(message.clone()),
+            _ => None,
+        })
+        .await;
+    assert!(
+        error.starts_with("Eliminated"),
+        "unexpected error: {}",
+        error
+    );
+
+    // Everybody is back for the next round
+    let players = bob
+        .expect(|msg| match msg {
+            ServerMessage::GameState { players, .. } => Some(players.clone()),
+            _ => None,
+        })
+        .await;
+    assert!(players.values().all(|p| p.is_alive));
+    bob.expect(|msg| matches!(msg, ServerMessage::RoundStarted { round: 2 }).then_some(()))
+        .await;
+}
+
+#[tokio::test]
+async fn infection_spreads_until_nobody_survives() {
+    let server = TestServer::start_with(ServerConfig {
+        mode: GameMode::Infection,
+        min_players: 1,
+        countdown: Duration::ZERO,
+        time_limit: None,
+        ..ServerConfig::default()
+    })
+    .await;
+    let infected = |msg: &ServerMessage| match msg {
+        ServerMessage::PlayerInfected {
+            player_id,
+            infected_by,
+        } => Some((player_id.clone(), infected_by.clone())),
+        _ => None,
+    };
+
+    // A lone player has nobody to infect, so the match doesn't end at once
+    let alice = TestClient::join(&server, "alice").await;
+    alice.expect_state(GameState::GameStarted).await;
+    alice
+        .expect_none(|msg| {
+            infected(msg).is_some() || matches!(msg, ServerMessage::MatchResults { .. })
+        })
+        .await;
+
+    // The latecomer joins the outbreak, and the last survivor falling to it ends the match
+    let bob = TestClient::join(&server, "bob").await;
+    let joined = alice
+        .expect(|msg| match msg {
+            ServerMessage::PlayerJoined { player } => Some(player.clone()),
+            _ => None,
+        })
+        .await;
+    assert!(joined.is_infected());
+    kill(&bob, &alice).await;
+    assert_eq!(
+        bob.expect(infected).await,
+        (alice.player_id.clone(), Some(bob.player_id.clone()))
+    );
+    let (winning_team, reason) = bob
+        .expect(|msg| match msg {
+            ServerMessage::MatchResults {
+                winning_team,
+                reason,
+                ..
+            } => Some((*winning_team, reason.clone())),
+            _ => None,
+        })
+        .await;
+    assert_eq!(winning_team, Some(Team::Infected));
+    assert_eq!(reason, "Everyone has been infected");
+}
+
+// Paused time skips straight through the long respawn timers
+#[tokio::test(start_paused = true)]
+async fn collected_pickups_come_back_after_their_timer() {
+    let server = TestServer::start().await;
+    let alice = TestClient::join(&server, "alice").await;
+    let pickups = alice
+        .expect(|msg| match msg {
+            ServerMessage::PickupStates { pickups } => Some(pickups.clone()),
+            _ => None,
+        })
+        .await;
+    let pickup_update = |msg: &ServerMessage| match msg {
+        ServerMessage::PickupUpdate {
+            pickup,
+            collected_by,
+        } => Some((pickup.clone(), collected_by.clone())),
+        _ => None,
+    };
+
+    // Anything but a health pack is wanted at full health
+    let wanted = pickups
+        .iter()
+        .find(|p| p.kind != PickupKind::HealthPack)
+        .expect("maze has only health packs");
+    alice.move_to(wanted.position + Vec3::Y * EYE_HEIGHT).await;
+    let (pickup, collected_by) = alice.expect(pickup_update).await;
+    assert_eq!((pickup.id, pickup.available), (wanted.id, false));
+    assert_eq!(collected_by, Some(alice.player_id.clone()));
+
+    // Gone until its respawn time is up, then back for anyone to take
+    let (origin, _) = open_pair(alice.grid());
+    alice.move_to(origin).await;
+    let respawn = Duration::from_secs_f32(wanted.kind.respawn_secs());
+    tokio::time::sleep(respawn - QUIET_PERIOD * 2).await;
+    alice.expect_none(|msg| pickup_update(msg).is_some()).await;
+    let (pickup, collected_by) = alice.expect(pickup_update).await;
+    assert_eq!((pickup.id, pickup.available), (wanted.id, true));
+    assert_eq!(collected_by, None);
+}
+
+#[tokio::test]
+async fn respawn_waits_for_the_delay() {
+    let server = TestServer::start().await;
+    let alice = TestClient::join(&server, "alice").await;
+    let bob = TestClient::join(&server, "bob").await;
+
+    kill(&alice, &bob).await;
+    let died_at = Instant::now();
+
+    bob.send(ClientMessage::Respawn).await;
+    let error = bob
+        .expect(|msg| match msg {
+            ServerMessage::Error { message } => Some(message.clone()),
+            _ => None,
+        })
+        .await;
+    assert!(
+        error.starts_with("Respawn in"),
+        "unexpected error: {}",
+        error
+    );
+    bob.expect_none(|msg| matches!(msg, ServerMessage::PlayerRespawned { .. }))
+        .await;
+
+    tokio::time::sleep_until(died_at + RESPAWN_DELAY).await;
+    bob.send(ClientMessage::Respawn).await;
+    let respawned = bob
+        .expect(|msg| match msg {
+            ServerMessage::PlayerRespawned { player_id, .. } => Some(player_id.clone()),
+            _ => None,
+        })
+        .await;
+    assert_eq!(respawned, bob.player_id);
+}
+
+#[tokio::test]
+async fn bots_fill_in_and_give_up_their_seats() {
+    let server = TestServer::start_with(ServerConfig {
+        min_players: 99,
+        bots: 3,
+        ..ServerConfig::default()
+    })
+    .await;
+    let bot_joined = |msg: &ServerMessage| match msg {
+        ServerMessage::PlayerJoined { player } if player.name.starts_with("Bot") => {
+            Some(player.id.clone())
+        }
+        _ => None,
+    };
+    let player_left = |msg: &ServerMessage| match msg {
+        ServerMessage::PlayerLeft { player_id } => Some(player_id.clone()),
+        _ => None,
+    };
+
+    // The first human gets company up to the configured headcount
+    let alice = TestClient::join(&server, "alice").await;
+    let mut bots = vec![
+        alice.expect(bot_joined).await,
+        alice.expect(bot_joined).await,
+    ];
+    alice.expect_none(|msg| bot_joined(msg).is_some()).await;
+
+    // Checking a name doesn't count as joining
+    let probe = TestClient::connect(&server);
+    probe
+        .send(ClientMessage::CheckName {
+            name: "bob".to_string(),
+        })
+        .await;
+    probe
+        .expect(|msg| matches!(msg, ServerMessage::NameAvailable).then_some(()))
+        .await;
+    alice
+        .expect_none(|msg| player_left(msg).is_some() || bot_joined(msg).is_some())
+        .await;
+
+    // A human joining for real takes a bot's place
+    let bob = TestClient::join(&server, "bob").await;
+    let left = alice.expect(player_left).await;
+    assert!(bots.contains(&left), "a human left instead of a bot");
+    bots.retain(|id| *id != left);
+    probe
+        .send(ClientMessage::CheckName {
+            name: "bob".to_string(),
+        })
+        .await;
+    probe
+        .expect(|msg| matches!(msg, ServerMessage::NameAlreadyTaken).then_some(()))
+        .await;
+
+    bob.send(ClientMessage::LeaveGame).await;
+    let rejoined = alice.expect(bot_joined).await;
+    assert!(!bots.contains(&rejoined));
+}
+
+#[tokio::test]
+async fn bots_skip_names_spectators_hold() {
+    let server = TestServer::start_with(ServerConfig {
+        min_players: 99,
+        bots: 2,
+        ..ServerConfig::default()
+    })
+    .await;
+    let watcher = TestClient::connect(&server);
+    watcher
+        .send(ClientMessage::JoinAsSpectator {
+            spectator_name: "Bot 1".to_string(),
+        })
+        .await;
+    watcher
+        .expect(|msg| matches!(msg, ServerMessage::SpectatorJoined).then_some(()))
+        .await;
+
+    let alice = TestClient::join(&server, "alice").await;
+    let bot_name = alice
+        .expect(|msg| match msg {
+            ServerMessage::PlayerJoined { player } if player.id != alice.player_id => {
+                Some(player.name.clone())
+            }
+            _ => None,
+        })
+        .await;
+    assert_eq!(bot_name, "Bot 2");
+}
+
+#[tokio::test]
+async fn shutdown_notifies_every_player() {
+    let server = TestServer::start().await;
+    let alice = TestClient::join(&server, "alice").await;
+    let bob = TestClient::join(&server, "bob").await;
+
+    server.shutdown().await;
+    for client in [&alice, &bob] {
+        client
+            .expect(|msg| matches!(msg, ServerMessage::GameEnded { .. }).then_some(()))
+            .await;
+    }
+}
+
+#[tokio::test]
+async fn duplicated_join_adds_one_player() {
+    let server = TestServer::start_with(ServerConfig {
+        min_players: 99,
+        expose_stats: true,
+        ..ServerConfig::default()
+    })
+    .await;
+    let conditions = NetworkConditions {
+        latency: Duration::from_millis(20),
+        duplication: 1.0,
+        ..NetworkConditions::default()
+    };
+    let lossy = SimulatedTransport::new(server.network.endpoint(), conditions);
+    let alice = TestClient::connect_over(&server, Box::new(lossy));
+
+    alice
+        .send(ClientMessage::JoinGame {
+            player_name: "alice".to_string(),
+        })
+        .await;
+    alice
+        .expect(|msg| matches!(msg, ServerMessage::GameJoined { .. }).then_some(()))
+        .await;
+    alice
+        .expect(|msg| match msg {
+            ServerMessage::Error { message } => Some(message.clone()),
+            _ => None,
+        })
+        .await;
+
+    alice.send(ClientMessage::QueryStats).await;
+    let stats = alice
+        .expect(|msg| match msg {
+            ServerMessage::ServerStats { stats } => Some(stats.clone()),
+            _ => None,
+        })
+        .await;
+    assert_eq!(stats.players, 1);
+    assert_eq!(stats.connections, 1);
+}
+
+#[tokio::test]
+async fn replay_records_maze_and_kill() {
+    let dir = std::env::temp_dir().join(format!("sphere-wars-replays-{}", uuid::Uuid::new_v4()));
+    let server = TestServer::start_with(ServerConfig {
+        min_players: 99,
+        replay_dir: Some(dir.clone()),
+        ..ServerConfig::default()
+    })
+    .await;
+    let alice = TestClient::join(&server, "alice").await;
+    let bob = TestClient::join(&server, "bob").await;
+    kill(&alice, &bob).await;
+    server.shutdown().await;
+
+    let path = std::fs::read_dir(&dir)
+        .unwrap()
+        .next()
+        .expect("no replay was written")
+        .unwrap()
+        .path();
+    let mut reader = ReplayReader::new(std::fs::File::open(&path).unwrap()).unwrap();
+    assert_eq!(reader.header().version, REPLAY_VERSION);
+    let maze = &reader.header().maze;
+    let maze_grid = generate_maze_from_config(&MazeConfig::new(
+        maze.seed,
+        maze.width,
+        maze.height,
+        &maze.difficulty,
+    ))
+    .grid;
+    assert_eq!(&maze_grid, alice.grid());
+
+    let (mut keyframes, mut snapshots) = (0, 0);
+    let (mut joins, mut moves, mut shots, mut damage, mut deaths) = (0, 0, 0, 0, 0);
+    let mut last_time = 0;
+    while let Some(entry) = reader.next_entry().unwrap() {
+        assert!(entry.time_ms >= last_time, "replay entries out of order");
+        last_time = entry.time_ms;
+        let msg = match entry.record {
+            ReplayRecord::Event(msg) => msg,
+            ReplayRecord::Snapshot { .. } => {
+                snapshots += 1;
+                continue;
+            }
+            ReplayRecord::Keyframe { messages } => {
+                assert!(matches!(messages[0], ServerMessage::GameState { .. }));
+                keyframes += 1;
+                continue;
+            }
+        };
+        match *msg {
+            ServerMessage::PlayerJoined { .. } => joins += 1,
+            ServerMessage::PlayerMoved { .. } => moves += 1,
+            ServerMessage::PlayerShot { .. } => shots += 1,
+            ServerMessage::PlayerDamaged { .. } => damage += 1,
+            ServerMessage::PlayerDied { player_id, .. } => {
+                assert_eq!(player_id, bob.player_id);
+                deaths += 1;
+            }
+            _ => {}
+        }
+    }
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert!(keyframes > 0, "no keyframes recorded");
+    assert!(snapshots > 0, "no snapshots recorded");
+    assert_eq!(joins, 2);
+    assert!(moves >= 2);
+    assert!(shots > 0);
+    assert!(damage > 0);
+    assert_eq!(deaths, 1);
+}
+
+#[tokio::test]
+async fn stats_are_only_answered_when_exposed() {
+    let server = TestServer::start().await;
+    let alice = TestClient::join(&server, "alice").await;
+    let stranger = TestClient::connect(&server);
+    let is_stats = |msg: &ServerMessage| matches!(msg, ServerMessage::ServerStats { .. });
+
+    for client in [&alice, &stranger] {
+        client.send(ClientMessage::QueryStats).await;
+        client.expect_none(is_stats).await;
+    }
+}
+
+#[tokio::test]
+async fn spectators_watch_without_playing() {
+    let server = TestServer::start_with(ServerConfig {
+        min_players: 1,
+        expose_stats: true,
+        ..ServerConfig::default()
+    })
+    .await;
+    let watcher = TestClient::connect(&server);
+    watcher
+        .send(ClientMessage::JoinAsSpectator {
+            spectator_name: "watcher".to_string(),
+        })
+        .await;
+    watcher
+        .expect(|msg| matches!(msg, ServerMessage::SpectatorJoined).then_some(()))
+        .await;
+    watcher
+        .expect(|msg| matches!(msg, ServerMessage::GameStarted { .. }).then_some(()))
+        .await;
+    // A lone spectator doesn't fill the lobby
+    watcher
+        .expect_none(|msg| {
+            matches!(
+                msg,
+                ServerMessage::MatchStateChanged {
+                    state: GameState::Countdown,
+                    ..
+                }
+            )
+        })
+        .await;
+
+    let impostor = TestClient::connect(&server);
+    impostor
+        .send(ClientMessage::JoinGame {
+            player_name: "watcher".to_string(),
+        })
+        .await;
+    impostor
+        .expect(|msg| matches!(msg, ServerMessage::NameAlreadyTaken).then_some(()))
+        .await;
+
+    let alice = TestClient::join(&server, "alice").await;
+    let spectators = alice
+        .expect(|msg| match msg {
+            ServerMessage::Spectators { names } => Some(names.clone()),
+            _ => None,
+        })
+        .await;
+    assert_eq!(spectators, vec!["watcher".to_string()]);
+    let joined = watcher
+        .expect(|msg| match msg {
+            ServerMessage::PlayerJoined { player } => Some(player.id.clone()),
+            _ => None,
+        })
+        .await;
+    assert_eq!(joined, alice.player_id);
+
+    watcher.send(ClientMessage::QueryStats).await;
+    let stats = watcher
+        .expect(|msg| match msg {
+            ServerMessage::ServerStats { stats } => Some(stats.clone()),
+            _ => None,
+        })
+        .await;
+    assert_eq!(stats.players, 1);
+    assert_eq!(stats.spectators, 1);
+    assert_eq!(stats.used_spawn_points, 1);
+
+    watcher.send(ClientMessage::LeaveGame).await;
+    let spectators = alice
+        .expect(|msg| match msg {
+            ServerMessage::Spectators { names } => Some(names.clone()),
+            _ => None,
+        })
+        .await;
+    assert!(spectators.is_empty());
+}
@@ -1,9 +1,12 @@
 #![allow(dead_code)]
 
 use crate::cli;
+use crate::config::ServerConfig;
+use shared::{GameMode, UdpTransport};
+use std::path::Path;
 use tokio::net::UdpSocket;
 
-pub fn print_info(args: &cli::Cli) {
+pub fn print_info(args: &cli::Cli, config: &ServerConfig) {
     println!("🎮 Sphere Wars UDP Server");
     println!("Host: {}", args.host);
     println!("Port: {}", args.port);
@@ -16,17 +19,75 @@ pub fn print_info(args: &cli::Cli) {
     };
     println!("Difficulty: {} - {}", args.difficulty, difficulty_info);
     println!("Maze Size: 12x12 with randomized spawn points");
+    println!(
+        "Mode: {}{}",
+        args.mode,
+        if args.friendly_fire {
+            " (friendly fire on)"
+        } else {
+            ""
+        }
+    );
+    let (score_limit, score_name) = match config.mode {
+        GameMode::CaptureTheFlag => (args.capture_limit, "capture"),
+        GameMode::KingOfTheHill | GameMode::TeamKingOfTheHill => (args.hill_limit, "hill point"),
+        GameMode::Elimination => (args.round_limit, "round"),
+        GameMode::Infection => (0, "score"),
+        _ => (args.frag_limit, "frag"),
+    };
+    println!(
+        "Match: {} player(s) to start, {} time limit, {} {} limit",
+        args.min_players,
+        if args.time_limit > 0 {
+            format!("{}s", args.time_limit)
+        } else {
+            "no".to_string()
+        },
+        if score_limit > 0 {
+            score_limit.to_string()
+        } else {
+            "no".to_string()
+        },
+        score_name
+    );
+    if args.bots > 0 {
+        println!("Bots: up to {} ({} skill)", args.bots, args.bot_skill);
+    }
+    if let Some(dir) = &args.record_replays {
+        println!("Replays: recording to {}", dir.display());
+    }
+    if let Some(path) = &args.chat_filter {
+        println!(
+            "Chat filter: {} blocked words from {}",
+            config.blocked_words.len(),
+            path.display()
+        );
+    }
+    if config.expose_stats {
+        println!("Stats: answering queries from anyone");
+    }
+    let conditions = args.network_conditions();
+    if !conditions.is_perfect() {
+        println!(
+            "Simulated network: {}ms latency, {}ms jitter, {:.0}% loss, {:.0}% duplication, {:.0}% reordering",
+            args.sim_latency,
+            args.sim_jitter,
+            conditions.loss * 100.0,
+            conditions.duplication * 100.0,
+            conditions.reordering * 100.0
+        );
+    }
     println!("=====================================");
 }
 
-pub async fn create_udp_server_socket(host: &str, port: u16) -> UdpSocket {
+pub fn create_udp_server_transport(host: &str, port: u16) -> UdpTransport {
     let addr = format!("{}:{}", host, port);
 
     // Check if port is available before binding
-    match UdpSocket::bind(&addr).await {
-        Ok(socket) => {
+    match UdpTransport::bind(&addr) {
+        Ok(transport) => {
             println!("✅ Successfully bound to {}", addr);
-            socket
+            transport
         }
         Err(e) => {
             eprintln!("❌ Failed to bind to {}: {}", addr, e);
@@ -76,3 +137,14 @@ pub async fn get_local_ip() -> String {
     socket.connect("8.8.8.8:80").await.unwrap();
     socket.local_addr().unwrap().ip().to_string()
 }
+
+// One blocked chat word per line, skipping blank lines and # comments
+pub fn load_blocked_words(path: &Path) -> std::io::Result<Vec<String>> {
+    let contents = std::fs::read_to_string(path)?;
+    Ok(contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_lowercase)
+        .collect())
+}
@@ -0,0 +1,10 @@
+use serde::{Deserialize, Serialize};
+
+// Longest chat message in characters, longer ones are cut short
+pub const MAX_CHAT_LENGTH: usize = 160;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ChatChannel {
+    All,
+    Team, // Teammates only, or other spectators when spectating
+}
@@ -0,0 +1,95 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{WeaponKind, weapons};
+
+// Anyone who hurt the victim this recently shares credit for the kill
+pub const ASSIST_WINDOW_SECS: f64 = 10.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DamageKind {
+    Direct,        // A hitscan round or projectile hitting its target
+    Splash,        // Caught in an explosion
+    Environmental, // The world itself, with no player to blame
+}
+
+// One instance of a player being hurt, and who or what did it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DamageEvent {
+    pub source_id: Option<String>,
+    pub weapon: Option<WeaponKind>,
+    pub distance: f32,
+    pub kind: DamageKind,
+    pub amount: f32,
+}
+
+impl DamageEvent {
+    // A weapon hit, using the weapon's falloff curve for the distance travelled
+    pub fn direct(source_id: &str, weapon: WeaponKind, distance: f32, scale: f32) -> Self {
+        Self {
+            source_id: Some(source_id.to_string()),
+            weapon: Some(weapon),
+            distance,
+            kind: DamageKind::Direct,
+            amount: weapons().get(weapon).damage_at(distance) * scale,
+        }
+    }
+
+    // Blast damage, falling off with distance from the explosion
+    pub fn splash(source_id: &str, weapon: WeaponKind, distance: f32, scale: f32) -> Self {
+        Self {
+            source_id: Some(source_id.to_string()),
+            weapon: Some(weapon),
+            distance,
+            kind: DamageKind::Splash,
+            amount: weapons().get(weapon).splash_damage_at(distance) * scale,
+        }
+    }
+
+    pub fn environmental(amount: f32) -> Self {
+        Self {
+            source_id: None,
+            weapon: None,
+            distance: 0.0,
+            kind: DamageKind::Environmental,
+            amount,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splash_weakens_away_from_the_blast() {
+        let launcher = weapons().get(WeaponKind::GrenadeLauncher);
+        let splash_radius = launcher.projectile.as_ref().unwrap().splash_radius;
+
+        let direct = DamageEvent::direct("alice", WeaponKind::GrenadeLauncher, 0.0, 1.0);
+        assert_eq!(direct.kind, DamageKind::Direct);
+        assert_eq!(direct.amount, launcher.damage);
+
+        let centre = DamageEvent::splash("alice", WeaponKind::GrenadeLauncher, 0.0, 1.0);
+        let halfway = DamageEvent::splash(
+            "alice",
+            WeaponKind::GrenadeLauncher,
+            splash_radius / 2.0,
+            1.0,
+        );
+        let outside = DamageEvent::splash(
+            "alice",
+            WeaponKind::GrenadeLauncher,
+            splash_radius + 0.1,
+            1.0,
+        );
+        assert_eq!(centre.kind, DamageKind::Splash);
+        assert_eq!(centre.amount, direct.amount);
+        assert!(halfway.amount > 0.0 && halfway.amount < centre.amount);
+        assert_eq!(outside.amount, 0.0);
+
+        // Damage boosts scale both the same way
+        let boosted = DamageEvent::splash("alice", WeaponKind::GrenadeLauncher, 0.0, 2.0);
+        assert_eq!(boosted.amount, centre.amount * 2.0);
+        assert_eq!(boosted.source_id.as_deref(), Some("alice"));
+    }
+}
@@ -0,0 +1,39 @@
+use bevy::math::{Quat, Vec3};
+use serde::{Deserialize, Serialize};
+
+// How much of the killer's view the victim gets to see
+pub const KILLCAM_SECS: f32 = 3.0;
+
+// Where a player was, in milliseconds from the start of the killcam
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct KillcamFrame {
+    pub time_ms: u32,
+    pub position: Vec3,
+    pub rotation: Quat,
+}
+
+// A shot the killer fired during the killcam, drawn as a tracer
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct KillcamShot {
+    pub time_ms: u32,
+    pub origin: Vec3,
+    pub direction: Vec3,
+    pub distance: f32, // Until it hit a player or a wall
+}
+
+// Position and rotation at a point in the killcam, blending the frames either side
+pub fn killcam_pose(frames: &[KillcamFrame], time_ms: f32) -> Option<(Vec3, Quat)> {
+    let after = frames.partition_point(|f| (f.time_ms as f32) <= time_ms);
+    match (after.checked_sub(1).map(|i| &frames[i]), frames.get(after)) {
+        (Some(before), Some(after)) => {
+            let span = (after.time_ms - before.time_ms).max(1) as f32;
+            let t = ((time_ms - before.time_ms as f32) / span).clamp(0.0, 1.0);
+            Some((
+                before.position.lerp(after.position, t),
+                before.rotation.slerp(after.rotation, t),
+            ))
+        }
+        (Some(frame), None) | (None, Some(frame)) => Some((frame.position, frame.rotation)),
+        (None, None) => None,
+    }
+}
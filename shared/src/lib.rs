@@ -1,37 +1,79 @@
 use bevy::math::Vec3;
 use serde::{Deserialize, Serialize};
 
+mod chat;
+mod damage;
+mod killcam;
 mod maze;
 mod messages;
+mod netsim;
+mod objective;
+mod pickup;
 mod player;
+mod projectile;
+mod replay;
+mod streak;
+mod transport;
+mod weapon;
 
+pub use chat::*;
+pub use damage::*;
+pub use killcam::*;
 pub use maze::*;
 pub use messages::*;
+pub use netsim::*;
+pub use objective::*;
+pub use pickup::*;
 pub use player::*;
+pub use projectile::*;
+pub use replay::*;
+pub use streak::*;
+pub use transport::*;
+pub use weapon::*;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+// Match lifecycle: warmup -> countdown -> match -> intermission -> next round
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum GameState {
-    WaitingForPlayers,
+    WaitingForPlayers, // Warmup, free play until enough players join
+    Countdown,
     GameStarted,
-    GameOver,
+    GameOver, // Intermission showing final results
 }
 
-// Weapon configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct WeaponConfig {
-    pub damage: f32,
-    pub range: f32,
-    pub fire_rate: f32, // shots per second
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GameMode {
+    Deathmatch,
+    TeamDeathmatch,
+    CaptureTheFlag,
+    KingOfTheHill,
+    TeamKingOfTheHill,
+    Elimination,
+    Infection,
 }
 
-impl Default for WeaponConfig {
-    fn default() -> Self {
-        Self {
-            damage: 50.0,
-            range: 100.0,
-            fire_rate: 4.0,
+impl GameMode {
+    pub fn name(&self) -> &'static str {
+        match self {
+            GameMode::Deathmatch => "Deathmatch",
+            GameMode::TeamDeathmatch => "Team Deathmatch",
+            GameMode::CaptureTheFlag => "Capture the Flag",
+            GameMode::KingOfTheHill => "King of the Hill",
+            GameMode::TeamKingOfTheHill => "Team King of the Hill",
+            GameMode::Elimination => "Elimination",
+            GameMode::Infection => "Infection",
         }
     }
+
+    pub fn is_team_mode(&self) -> bool {
+        matches!(
+            self,
+            GameMode::TeamDeathmatch | GameMode::CaptureTheFlag | GameMode::TeamKingOfTheHill
+        )
+    }
+
+    pub fn has_control_zone(&self) -> bool {
+        matches!(self, GameMode::KingOfTheHill | GameMode::TeamKingOfTheHill)
+    }
 }
 
 // Hitscan result
@@ -1,12 +1,18 @@
 use bevy::math::{Quat, Vec3};
-use rand::{Rng, SeedableRng};
+use rand::{Rng, SeedableRng, seq::SliceRandom};
 use rand_chacha::ChaCha8Rng;
 use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+
+use crate::{PickupKind, PickupSpawn};
 
 pub type MazeGrid = Vec<Vec<bool>>;
 
 pub const MAZE_WIDTH: usize = 12;
 pub const MAZE_HEIGHT: usize = 12;
+pub const TILE_SIZE: f32 = 4.0; // World units per grid cell, as rendered by the client
+pub const WALL_HEIGHT: f32 = 8.0;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MazeConfig {
@@ -54,10 +60,16 @@ pub fn generate_maze_from_config(config: &MazeConfig) -> MazeData {
     let grid =
         generate_maze_with_seed(config.width, config.height, &config.difficulty, config.seed);
     let spawn_points = generate_spawn_points(&grid, config.width, config.height, &mut rng);
+    let flag_bases =
+        find_flag_bases(&grid).map(|bases| bases.map(|(x, y)| grid_to_world(&grid, x, y)));
+    let pickup_spawns =
+        generate_pickup_spawns(&grid, config.width, config.height, &spawn_points, &mut rng);
 
     MazeData {
         grid,
         spawn_points,
+        flag_bases,
+        pickup_spawns,
         width: config.width,
         height: config.height,
     }
@@ -86,25 +98,25 @@ pub fn generate_maze_with_seed(
         let mut directions = Vec::new();
 
         // Find unvisited neighbors
-        if let Some(north_pos) = neighbors.north {
-            if !nodes[north_pos].visited {
-                directions.push(('n', north_pos));
-            }
+        if let Some(north_pos) = neighbors.north
+            && !nodes[north_pos].visited
+        {
+            directions.push(('n', north_pos));
         }
-        if let Some(south_pos) = neighbors.south {
-            if !nodes[south_pos].visited {
-                directions.push(('s', south_pos));
-            }
+        if let Some(south_pos) = neighbors.south
+            && !nodes[south_pos].visited
+        {
+            directions.push(('s', south_pos));
         }
-        if let Some(west_pos) = neighbors.west {
-            if !nodes[west_pos].visited {
-                directions.push(('w', west_pos));
-            }
+        if let Some(west_pos) = neighbors.west
+            && !nodes[west_pos].visited
+        {
+            directions.push(('w', west_pos));
         }
-        if let Some(east_pos) = neighbors.east {
-            if !nodes[east_pos].visited {
-                directions.push(('e', east_pos));
-            }
+        if let Some(east_pos) = neighbors.east
+            && !nodes[east_pos].visited
+        {
+            directions.push(('e', east_pos));
         }
 
         if !directions.is_empty() {
@@ -195,12 +207,12 @@ fn get_neighbors(pos: usize, width: usize, height: usize) -> Neighbors {
         } else {
             None
         },
-        west: if pos > 0 && pos % width != 0 {
+        west: if pos > 0 && !pos.is_multiple_of(width) {
             Some(pos - 1)
         } else {
             None
         },
-        east: if (pos + 1) % width != 0 {
+        east: if !(pos + 1).is_multiple_of(width) {
             Some(pos + 1)
         } else {
             None
@@ -209,6 +221,7 @@ fn get_neighbors(pos: usize, width: usize, height: usize) -> Neighbors {
 }
 
 // Add extra connections between nodes
+#[allow(clippy::needless_range_loop)]
 fn add_extra_connections(
     nodes: &mut [MazeNode],
     width: usize,
@@ -221,25 +234,29 @@ fn add_extra_connections(
         let mut wall_directions = Vec::new();
 
         // Find neighbors where walls still exist (to avoid over-connecting)
-        if let Some(north_pos) = neighbors.north {
-            if nodes[i].north && nodes[north_pos].south {
-                wall_directions.push(('n', north_pos));
-            }
+        if let Some(north_pos) = neighbors.north
+            && nodes[i].north
+            && nodes[north_pos].south
+        {
+            wall_directions.push(('n', north_pos));
         }
-        if let Some(south_pos) = neighbors.south {
-            if nodes[i].south && nodes[south_pos].north {
-                wall_directions.push(('s', south_pos));
-            }
+        if let Some(south_pos) = neighbors.south
+            && nodes[i].south
+            && nodes[south_pos].north
+        {
+            wall_directions.push(('s', south_pos));
         }
-        if let Some(west_pos) = neighbors.west {
-            if nodes[i].west && nodes[west_pos].east {
-                wall_directions.push(('w', west_pos));
-            }
+        if let Some(west_pos) = neighbors.west
+            && nodes[i].west
+            && nodes[west_pos].east
+        {
+            wall_directions.push(('w', west_pos));
         }
-        if let Some(east_pos) = neighbors.east {
-            if nodes[i].east && nodes[east_pos].west {
-                wall_directions.push(('e', east_pos));
-            }
+        if let Some(east_pos) = neighbors.east
+            && nodes[i].east
+            && nodes[east_pos].west
+        {
+            wall_directions.push(('e', east_pos));
         }
 
         for (direction, next_position) in wall_directions {
@@ -270,6 +287,7 @@ fn add_extra_connections(
 }
 
 // Remove dead ends
+#[allow(clippy::needless_range_loop)]
 fn remove_dead_ends(
     nodes: &mut [MazeNode],
     width: usize,
@@ -282,25 +300,25 @@ fn remove_dead_ends(
         let mut directions = Vec::new();
 
         // Find unvisited neighbors
-        if let Some(north_pos) = neighbors.north {
-            if !nodes[north_pos].visited {
-                directions.push(('n', north_pos));
-            }
+        if let Some(north_pos) = neighbors.north
+            && !nodes[north_pos].visited
+        {
+            directions.push(('n', north_pos));
         }
-        if let Some(south_pos) = neighbors.south {
-            if !nodes[south_pos].visited {
-                directions.push(('s', south_pos));
-            }
+        if let Some(south_pos) = neighbors.south
+            && !nodes[south_pos].visited
+        {
+            directions.push(('s', south_pos));
         }
-        if let Some(west_pos) = neighbors.west {
-            if !nodes[west_pos].visited {
-                directions.push(('w', west_pos));
-            }
+        if let Some(west_pos) = neighbors.west
+            && !nodes[west_pos].visited
+        {
+            directions.push(('w', west_pos));
         }
-        if let Some(east_pos) = neighbors.east {
-            if !nodes[east_pos].visited {
-                directions.push(('e', east_pos));
-            }
+        if let Some(east_pos) = neighbors.east
+            && !nodes[east_pos].visited
+        {
+            directions.push(('e', east_pos));
         }
 
         if directions.len() == 1 && rng.random_bool(chance.into()) {
@@ -337,7 +355,7 @@ fn nodes_to_simple_grid(nodes: &[MazeNode], width: usize, height: usize) -> Maze
     let grid_height = height * 3 + 2;
     let mut grid = vec![vec![true; grid_width]; grid_height]; // Start with all walls
 
-    for i in 0..nodes.len() {
+    for (i, node) in nodes.iter().enumerate() {
         let node_x = i % width;
         let node_y = i / width;
 
@@ -352,28 +370,28 @@ fn nodes_to_simple_grid(nodes: &[MazeNode], width: usize, height: usize) -> Maze
         grid[grid_y + 1][grid_x + 1] = false;
 
         // Create wider passages between nodes based on removed walls
-        if !nodes[i].north && node_y > 0 {
+        if !node.north && node_y > 0 {
             // Create 2-wide passage going north
             grid[grid_y - 1][grid_x] = false;
             grid[grid_y - 1][grid_x + 1] = false;
             grid[grid_y - 2][grid_x] = false;
             grid[grid_y - 2][grid_x + 1] = false;
         }
-        if !nodes[i].south && node_y < height - 1 {
+        if !node.south && node_y < height - 1 {
             // Create 2-wide passage going south
             grid[grid_y + 2][grid_x] = false;
             grid[grid_y + 2][grid_x + 1] = false;
             grid[grid_y + 3][grid_x] = false;
             grid[grid_y + 3][grid_x + 1] = false;
         }
-        if !nodes[i].west && node_x > 0 {
+        if !node.west && node_x > 0 {
             // Create 2-wide passage going west
             grid[grid_y][grid_x - 1] = false;
             grid[grid_y + 1][grid_x - 1] = false;
             grid[grid_y][grid_x - 2] = false;
             grid[grid_y + 1][grid_x - 2] = false;
         }
-        if !nodes[i].east && node_x < width - 1 {
+        if !node.east && node_x < width - 1 {
             // Create 2-wide passage going east
             grid[grid_y][grid_x + 2] = false;
             grid[grid_y + 1][grid_x + 2] = false;
@@ -383,13 +401,11 @@ fn nodes_to_simple_grid(nodes: &[MazeNode], width: usize, height: usize) -> Maze
     }
 
     // Ensure all border cells are walls (maze is fully enclosed)
-    for x in 0..grid_width {
-        grid[0][x] = true; // Top border
-        grid[grid_height - 1][x] = true; // Bottom border
-    }
-    for y in 0..grid_height {
-        grid[y][0] = true; // Left border
-        grid[y][grid_width - 1] = true; // Right border
+    grid[0].fill(true); // Top border
+    grid[grid_height - 1].fill(true); // Bottom border
+    for row in grid.iter_mut() {
+        row[0] = true; // Left border
+        row[grid_width - 1] = true; // Right border
     }
 
     grid
@@ -405,6 +421,9 @@ pub struct SpawnPoint {
 pub struct MazeData {
     pub grid: MazeGrid,
     pub spawn_points: Vec<SpawnPoint>,
+    pub flag_bases: Option<[Vec3; 2]>, // Capture the Flag bases, one per team
+    pub pickup_spawns: Vec<PickupSpawn>,
+
     pub width: usize,
     pub height: usize,
 }
@@ -434,13 +453,12 @@ fn generate_spawn_points(
     while !candidates.is_empty() && spawn_points.len() < 16 {
         let idx = rng.random_range(0..candidates.len());
         let (x, y) = candidates[idx];
+        let position = spawn_position(grid, x, y);
 
         // Check if this location is far enough from existing spawn points
         let mut valid = true;
         for existing in &spawn_points {
-            let distance = ((x as f32 - existing.position.x).powi(2)
-                + (y as f32 - existing.position.z).powi(2))
-            .sqrt();
+            let distance = position.distance(existing.position) / TILE_SIZE;
             if distance < min_distance {
                 valid = false;
                 break;
@@ -449,7 +467,7 @@ fn generate_spawn_points(
 
         if valid {
             spawn_points.push(SpawnPoint {
-                position: Vec3::new(x as f32 * 4.0, 1.0, y as f32 * 4.0),
+                position,
                 rotation: Quat::from_rotation_y(std::f32::consts::PI * rng.random_range(0.0..2.0)),
             });
 
@@ -470,7 +488,7 @@ fn generate_spawn_points(
             for x in (2..grid_width - 2).step_by(6) {
                 if !grid[y][x] && spawn_points.len() < 8 {
                     spawn_points.push(SpawnPoint {
-                        position: Vec3::new(x as f32 * 4.0, 1.0, y as f32 * 4.0),
+                        position: spawn_position(grid, x, y),
                         rotation: Quat::from_rotation_y(
                             std::f32::consts::PI * rng.random_range(0.0..2.0),
                         ),
@@ -483,6 +501,186 @@ fn generate_spawn_points(
     spawn_points
 }
 
+// Pickups go where they are worth the detour: power-ups and armor at the end of
+// dead ends, health packs in the rooms where three or more corridors meet
+fn generate_pickup_spawns(
+    grid: &MazeGrid,
+    width: usize,
+    height: usize,
+    spawn_points: &[SpawnPoint],
+    rng: &mut impl Rng,
+) -> Vec<PickupSpawn> {
+    const DEAD_END_KINDS: [PickupKind; 5] = [
+        PickupKind::Armor,
+        PickupKind::SpeedBoost,
+        PickupKind::DamageBoost,
+        PickupKind::Invisibility,
+        PickupKind::Armor,
+    ];
+    const ROOM_PICKUPS: usize = 4;
+    let min_spacing = 4.0 * TILE_SIZE; // Between pickups
+    let spawn_clearance = 2.0 * TILE_SIZE; // Nobody should spawn on top of a power-up
+
+    // Each maze node is a 2x2 block of cells starting at (2 + 3i, 2 + 3j)
+    let open = |x: usize, y: usize| y < grid.len() && x < grid[y].len() && !grid[y][x];
+    let mut dead_ends = Vec::new();
+    let mut rooms = Vec::new();
+    for j in 0..height {
+        for i in 0..width {
+            let (x, y) = (2 + 3 * i, 2 + 3 * j);
+            let exits = [
+                open(x, y - 1),
+                open(x, y + 2),
+                open(x - 1, y),
+                open(x + 2, y),
+            ]
+            .iter()
+            .filter(|exit| **exit)
+            .count();
+
+            // The centre of the node, between its four cells
+            let position =
+                grid_to_world(grid, x, y) + Vec3::new(TILE_SIZE / 2.0, 0.0, TILE_SIZE / 2.0);
+            if exits == 1 {
+                dead_ends.push(position);
+            } else if exits >= 3 {
+                rooms.push(position);
+            }
+        }
+    }
+    dead_ends.shuffle(rng);
+    rooms.shuffle(rng);
+
+    let mut pickups: Vec<PickupSpawn> = Vec::new();
+    let mut place = |kind: PickupKind, candidates: &mut Vec<Vec3>| {
+        while let Some(position) = candidates.pop() {
+            let crowded = pickups
+                .iter()
+                .any(|p| p.position.distance(position) < min_spacing)
+                || spawn_points.iter().any(|s| {
+                    Vec3::new(s.position.x, 0.0, s.position.z).distance(position) < spawn_clearance
+                });
+            if !crowded {
+                pickups.push(PickupSpawn { kind, position });
+                return;
+            }
+        }
+    };
+
+    for kind in DEAD_END_KINDS {
+        place(kind, &mut dead_ends);
+    }
+    for _ in 0..ROOM_PICKUPS {
+        place(PickupKind::HealthPack, &mut rooms);
+    }
+
+    pickups
+}
+
+fn spawn_position(grid: &MazeGrid, x: usize, y: usize) -> Vec3 {
+    let center = grid_to_world(grid, x, y);
+    Vec3::new(center.x, 1.0, center.z)
+}
+
+// Open cell nearest the middle of the maze, for when no spawn point is free
+pub fn central_spawn(grid: &MazeGrid) -> Vec3 {
+    let (mid_x, mid_y) = (grid[0].len() / 2, grid.len() / 2);
+    let (x, y) = (0..grid.len())
+        .flat_map(|y| (0..grid[y].len()).map(move |x| (x, y)))
+        .filter(|&(x, y)| !grid[y][x])
+        .min_by_key(|(x, y)| x.abs_diff(mid_x) + y.abs_diff(mid_y))
+        .unwrap_or((mid_x, mid_y));
+    spawn_position(grid, x, y)
+}
+
+// World-space centre of a grid cell (at floor level), matching the client's centred maze layout
+pub fn grid_to_world(grid: &MazeGrid, x: usize, y: usize) -> Vec3 {
+    let maze_width = grid[0].len() as f32 * TILE_SIZE;
+    let maze_height = grid.len() as f32 * TILE_SIZE;
+    Vec3::new(
+        x as f32 * TILE_SIZE - maze_width / 2.0 + TILE_SIZE / 2.0,
+        0.0,
+        y as f32 * TILE_SIZE - maze_height / 2.0 + TILE_SIZE / 2.0,
+    )
+}
+
+// Grid cell containing a world-space position, if it lies inside the maze
+pub fn world_to_grid(grid: &MazeGrid, position: Vec3) -> Option<(usize, usize)> {
+    let maze_width = grid[0].len() as f32 * TILE_SIZE;
+    let maze_height = grid.len() as f32 * TILE_SIZE;
+    let x = ((position.x + maze_width / 2.0) / TILE_SIZE).floor();
+    let y = ((position.z + maze_height / 2.0) / TILE_SIZE).floor();
+    if x < 0.0 || y < 0.0 {
+        return None;
+    }
+
+    let (x, y) = (x as usize, y as usize);
+    (y < grid.len() && x < grid[0].len()).then_some((x, y))
+}
+
+// Whether a world-space point is inside a wall; anywhere outside the maze counts as solid
+pub fn is_wall_at(grid: &MazeGrid, position: Vec3) -> bool {
+    if position.y >= WALL_HEIGHT {
+        return false;
+    }
+    world_to_grid(grid, position).is_none_or(|(x, y)| grid[y][x])
+}
+
+// Distance along a ray to the first wall it enters within max_distance.
+// Walls the ray starts inside are ignored so shots from against a wall still count.
+pub fn wall_hit_distance(
+    grid: &MazeGrid,
+    origin: Vec3,
+    direction: Vec3,
+    max_distance: f32,
+) -> Option<f32> {
+    let half = Vec3::new(TILE_SIZE / 2.0, 0.0, TILE_SIZE / 2.0);
+    let mut nearest: Option<f32> = None;
+
+    for (y, row) in grid.iter().enumerate() {
+        for (x, _) in row.iter().enumerate().filter(|(_, is_wall)| **is_wall) {
+            let center = grid_to_world(grid, x, y);
+            let box_min = center - half;
+            let box_max = center + half + Vec3::Y * WALL_HEIGHT;
+
+            if let Some(distance) = ray_box_distance(origin, direction, box_min, box_max)
+                && distance <= max_distance
+                && nearest.is_none_or(|n| distance < n)
+            {
+                nearest = Some(distance);
+            }
+        }
+    }
+
+    nearest
+}
+
+// Slab test returning where a ray enters an axis-aligned box, if in front of the origin
+fn ray_box_distance(origin: Vec3, direction: Vec3, box_min: Vec3, box_max: Vec3) -> Option<f32> {
+    let mut t_min = f32::NEG_INFINITY;
+    let mut t_max = f32::INFINITY;
+
+    for axis in 0..3 {
+        if direction[axis].abs() < 1e-6 {
+            // Parallel to this slab, so the origin must already lie within it
+            if origin[axis] < box_min[axis] || origin[axis] > box_max[axis] {
+                return None;
+            }
+            continue;
+        }
+
+        let t1 = (box_min[axis] - origin[axis]) / direction[axis];
+        let t2 = (box_max[axis] - origin[axis]) / direction[axis];
+        t_min = t_min.max(t1.min(t2));
+        t_max = t_max.min(t1.max(t2));
+        if t_min > t_max {
+            return None;
+        }
+    }
+
+    (t_min > 0.0).then_some(t_min)
+}
+
 fn is_safe_spawn_location(grid: &MazeGrid, x: usize, y: usize) -> bool {
     // Check if there's enough open space around this position
     let mut open_count = 0;
@@ -497,3 +695,197 @@ fn is_safe_spawn_location(grid: &MazeGrid, x: usize, y: usize) -> bool {
     }
     open_count >= 5 // Need at least 5 open cells in 3x3 area
 }
+
+// World positions of open areas roomy enough to hold a control zone
+pub fn control_zone_candidates(grid: &MazeGrid) -> Vec<Vec3> {
+    let open_area = |x: usize, y: usize| {
+        (y.saturating_sub(1)..=(y + 1).min(grid.len() - 1))
+            .flat_map(|ny| {
+                (x.saturating_sub(1)..=(x + 1).min(grid[ny].len() - 1)).map(move |nx| (nx, ny))
+            })
+            .filter(|&(nx, ny)| !grid[ny][nx])
+            .count()
+    };
+
+    let mut roomy = Vec::new();
+    let mut open = Vec::new();
+    for (y, row) in grid.iter().enumerate() {
+        for (x, &is_wall) in row.iter().enumerate() {
+            if is_wall {
+                continue;
+            }
+            open.push(grid_to_world(grid, x, y));
+            if open_area(x, y) >= 7 {
+                roomy.push(grid_to_world(grid, x, y));
+            }
+        }
+    }
+
+    // Tight mazes may have no rooms at all, fall back to any open cell
+    if roomy.is_empty() { open } else { roomy }
+}
+
+// Open cells directly north, south, west and east of a cell
+fn open_neighbors(grid: &MazeGrid, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> {
+    let candidates = [
+        (Some(x), y.checked_sub(1)),
+        (Some(x), Some(y + 1)),
+        (x.checked_sub(1), Some(y)),
+        (Some(x + 1), Some(y)),
+    ];
+    candidates.into_iter().filter_map(move |(nx, ny)| {
+        let (nx, ny) = (nx?, ny?);
+        (ny < grid.len() && nx < grid[ny].len() && !grid[ny][nx]).then_some((nx, ny))
+    })
+}
+
+// Walking distance in cells from start to every reachable open cell
+pub fn path_distances(grid: &MazeGrid, start: (usize, usize)) -> Vec<Vec<Option<u32>>> {
+    let mut distances = vec![vec![None; grid[0].len()]; grid.len()];
+    if grid[start.1][start.0] {
+        return distances;
+    }
+
+    let mut queue = VecDeque::new();
+    distances[start.1][start.0] = Some(0);
+    queue.push_back(start);
+
+    while let Some((x, y)) = queue.pop_front() {
+        let next_distance = distances[y][x].unwrap_or(0) + 1;
+        for (nx, ny) in open_neighbors(grid, x, y) {
+            if distances[ny][nx].is_none() {
+                distances[ny][nx] = Some(next_distance);
+                queue.push_back((nx, ny));
+            }
+        }
+    }
+
+    distances
+}
+
+// Shortest walk between two open cells using A*, start and goal included
+pub fn find_path(
+    grid: &MazeGrid,
+    start: (usize, usize),
+    goal: (usize, usize),
+) -> Option<Vec<(usize, usize)>> {
+    if grid[start.1][start.0] || grid[goal.1][goal.0] {
+        return None;
+    }
+
+    // Manhattan distance never overestimates on a 4-connected grid
+    let heuristic = |(x, y): (usize, usize)| (x.abs_diff(goal.0) + y.abs_diff(goal.1)) as u32;
+    let mut open = BinaryHeap::new();
+    let mut came_from: HashMap<(usize, usize), (usize, usize)> = HashMap::new();
+    let mut cost = HashMap::from([(start, 0u32)]);
+    open.push(Reverse((heuristic(start), start)));
+
+    while let Some(Reverse((_, cell))) = open.pop() {
+        if cell == goal {
+            let mut path = vec![cell];
+            let mut current = cell;
+            while let Some(&previous) = came_from.get(&current) {
+                path.push(previous);
+                current = previous;
+            }
+            path.reverse();
+            return Some(path);
+        }
+
+        let next_cost = cost[&cell] + 1;
+        for neighbor in open_neighbors(grid, cell.0, cell.1) {
+            if cost.get(&neighbor).is_none_or(|&known| next_cost < known) {
+                cost.insert(neighbor, next_cost);
+                came_from.insert(neighbor, cell);
+                open.push(Reverse((next_cost + heuristic(neighbor), neighbor)));
+            }
+        }
+    }
+
+    None
+}
+
+// Pick two roomy cells far apart from each other whose walking distances to
+// the middle of the maze are as even as possible, so neither base is favoured
+fn find_flag_bases(grid: &MazeGrid) -> Option<[(usize, usize); 2]> {
+    let mut candidates = Vec::new();
+    for (y, row) in grid.iter().enumerate() {
+        for (x, &is_wall) in row.iter().enumerate() {
+            if !is_wall && is_safe_spawn_location(grid, x, y) {
+                candidates.push((x, y));
+            }
+        }
+    }
+
+    let (mid_x, mid_y) = (grid[0].len() / 2, grid.len() / 2);
+    let center = *candidates
+        .iter()
+        .min_by_key(|(x, y)| x.abs_diff(mid_x) + y.abs_diff(mid_y))?;
+    let from_center = path_distances(grid, center);
+    candidates.retain(|(x, y)| from_center[*y][*x].is_some());
+
+    // Bases belong out on the edges, so only the farthest quarter is worth pairing up
+    let center_distance = |(x, y): (usize, usize)| from_center[y][x].unwrap_or(0) as i64;
+    candidates.sort_by_key(|&cell| Reverse(center_distance(cell)));
+    candidates.truncate(candidates.len().div_ceil(4).max(2));
+
+    let mut best = None;
+    let mut best_score = i64::MIN;
+    for (i, &a) in candidates.iter().enumerate() {
+        let from_a = path_distances(grid, a);
+        for &b in &candidates[i + 1..] {
+            let Some(apart) = from_a[b.1][b.0] else {
+                continue;
+            };
+
+            // Reward separation, penalise one base being closer to the middle
+            let imbalance = (center_distance(a) - center_distance(b)).abs();
+            let score = apart as i64 - 2 * imbalance;
+            if score > best_score {
+                best_score = score;
+                best = Some([a, b]);
+            }
+        }
+    }
+
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flag_bases_are_even_and_apart() {
+        for difficulty in ["easy", "medium", "hard"] {
+            for seed in 0..10 {
+                let grid = generate_maze_with_seed(MAZE_WIDTH, MAZE_HEIGHT, difficulty, seed);
+                let [a, b] = find_flag_bases(&grid).unwrap();
+
+                // Same walk to the roomy cell nearest the middle from either base
+                let (mid_x, mid_y) = (grid[0].len() / 2, grid.len() / 2);
+                let center = (0..grid.len())
+                    .flat_map(|y| (0..grid[0].len()).map(move |x| (x, y)))
+                    .filter(|&(x, y)| !grid[y][x] && is_safe_spawn_location(&grid, x, y))
+                    .min_by_key(|(x, y)| x.abs_diff(mid_x) + y.abs_diff(mid_y))
+                    .unwrap();
+                let from_center = path_distances(&grid, center);
+                assert_eq!(from_center[a.1][a.0], from_center[b.1][b.0]);
+
+                let apart = path_distances(&grid, a)[b.1][b.0].unwrap();
+                assert!(apart as usize >= grid.len() / 2, "bases {} apart", apart);
+            }
+        }
+    }
+
+    #[test]
+    fn central_spawn_is_open_and_inside_the_maze() {
+        for seed in 0..10 {
+            let grid = generate_maze_with_seed(MAZE_WIDTH, MAZE_HEIGHT, "hard", seed);
+            let spawn = central_spawn(&grid);
+            assert!(world_to_grid(&grid, spawn).is_some());
+            assert!(!is_wall_at(&grid, spawn));
+            assert!(spawn.x.abs() < TILE_SIZE * 2.0 && spawn.z.abs() < TILE_SIZE * 2.0);
+        }
+    }
+}
@@ -1,5 +1,9 @@
-use crate::player::Player;
-use crate::{GameState, HitscanResult};
+use crate::player::{Player, Team};
+use crate::{
+    ActivePowerUp, AmmoStatus, ChatChannel, ControlZone, DamageKind, FlagEvent, FlagState,
+    GameMode, GameState, HitscanResult, KillcamFrame, KillcamShot, PickupState, ProjectileState,
+    StreakKind, StreakReward, WeaponKind,
+};
 use bevy::math::{Quat, Vec3};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -8,11 +12,19 @@ use std::collections::HashMap;
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ClientMessage {
     TestHealth,
+    QueryStats, // Server bookkeeping, for load and soak testing
     JoinGame { player_name: String },
     LeaveGame,
     PlayerMove { position: Vec3, rotation: Quat },
     PlayerShoot { origin: Vec3, direction: Vec3 },
+    SwitchWeapon { weapon: WeaponKind },
+    Reload,
     Respawn,
+    // New messages go at the end, so existing ones keep their numbering
+    JoinAsSpectator { spectator_name: String }, // Watch without a player or spawn point
+    Pong { seq: u32 },                          // Answer to the server's Ping with the same seq
+    Chat { channel: ChatChannel, text: String },
+    CheckName { name: String }, // Whether a name is free, without joining
 }
 
 // Server to Client Messages
@@ -25,6 +37,10 @@ pub enum ServerMessage {
         players: HashMap<String, Player>,
         state: GameState,
         game_start_time: Option<f64>,
+        team_scores: HashMap<Team, u32>,
+    },
+    TeamScores {
+        team_scores: HashMap<Team, u32>,
     },
     PlayerUpdate {
         player: Player,
@@ -57,18 +73,95 @@ pub enum ServerMessage {
     PlayerDied {
         player_id: String,
         killer_id: Option<String>,
+        assist_ids: Vec<String>, // Others who damaged the victim shortly before the kill
+        weapon: Option<WeaponKind>,
+        kind: DamageKind,
     },
     PlayerDamaged {
         player_id: String,
         damage: f32,
         health: f32,
-        damage_by: String,
+        damage_by: Option<String>,
+        source_position: Option<Vec3>, // Where the attacker stood, for damage direction
     },
     ShotFired {
         shooter_id: String,
         hit_position: Vec3,
         hit_player: Option<String>,
     },
+    MatchStateChanged {
+        state: GameState,
+        mode: GameMode,
+        time_remaining: Option<f32>, // Seconds until the next transition, if timed
+        score_limit: Option<u32>,    // Kills, captures, hill points or rounds depending on the mode
+    },
+    MatchResults {
+        winner_id: Option<String>,
+        winning_team: Option<Team>, // Set in team modes unless the scores are tied
+        reason: String,
+        players: HashMap<String, Player>,
+    },
+    FlagStates {
+        flags: Vec<FlagState>,
+    },
+    FlagUpdate {
+        flag: FlagState,
+        event: FlagEvent,
+        player_id: Option<String>,
+    },
+    ZoneUpdate {
+        zone: ControlZone,
+        moved: bool, // The zone just relocated
+    },
+    PlayerScored {
+        player_id: String,
+        score: u32,
+    },
+    AmmoUpdate {
+        status: AmmoStatus,
+    },
+    WeaponSwitched {
+        player_id: String,
+        weapon: WeaponKind,
+    },
+    PickupStates {
+        pickups: Vec<PickupState>,
+    },
+    PickupUpdate {
+        pickup: PickupState,
+        collected_by: Option<String>, // Set when a player grabbed it, None when it respawned
+    },
+    Streak {
+        player_id: String,
+        kind: StreakKind,
+        count: u32,
+        reward: Option<StreakReward>,
+    },
+    PlayerStatus {
+        player_id: String,
+        health: f32,
+        armor: f32,
+        power_ups: Vec<ActivePowerUp>,
+    },
+    ProjectileStates {
+        projectiles: Vec<ProjectileState>, // Every projectile in flight, sent each tick
+    },
+    ProjectileExploded {
+        id: u32,
+        weapon: WeaponKind,
+        position: Vec3,
+    },
+    PlayerInfected {
+        player_id: String,
+        infected_by: Option<String>, // None for the first infected of a match
+    },
+    RoundStarted {
+        round: u32,
+    },
+    RoundOver {
+        round: u32,
+        winner_id: Option<String>, // None when nobody survived
+    },
     GameStarted {
         seed: u64,
         width: usize,
@@ -80,7 +173,51 @@ pub enum ServerMessage {
     },
     NameAlreadyTaken,
     HealthCheck,
+    ServerStats {
+        stats: ServerStats,
+    },
     GameEnded {
         reason: String,
     },
+    // New messages go at the end, so recorded replays keep their numbering
+    SpectatorJoined,
+    Spectators {
+        names: Vec<String>, // Everyone watching, sent whenever someone starts or stops
+    },
+    Killcam {
+        // Sent only to the victim, the last few seconds through the killer's eyes
+        killer_id: String,
+        killer_name: String,
+        weapon: Option<WeaponKind>,
+        killer_health: f32, // Left after the kill
+        killer_frames: Vec<KillcamFrame>,
+        victim_frames: Vec<KillcamFrame>,
+        shots: Vec<KillcamShot>,
+    },
+    Ping {
+        seq: u32, // Echoed back in a Pong to measure the round trip
+    },
+    Pings {
+        pings: HashMap<String, u32>, // Latest round trip in milliseconds by player id
+    },
+    Chat {
+        sender_id: Option<String>, // None for spectators
+        sender_name: String,
+        channel: ChatChannel,
+        text: String, // Already cut to length and filtered
+    },
+    NameAvailable,
+}
+
+// Sizes of the server's per-player bookkeeping; under a steady load these should
+// track the number of players instead of growing over time
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ServerStats {
+    pub players: usize,
+    pub connections: usize,
+    pub bots: usize,
+    pub spectators: usize,
+    pub used_spawn_points: usize,
+    pub pending_respawns: usize,
+    pub projectiles: usize,
 }
@@ -0,0 +1,349 @@
+use rand::Rng;
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+use std::io;
+use std::net::SocketAddr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::{Readable, Transport};
+
+// Extra hold-up for a reordered packet, on top of the worst normal delay
+const REORDER_DELAY: Duration = Duration::from_millis(50);
+
+// Bad network to simulate, applied separately to packets going each way
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct NetworkConditions {
+    pub latency: Duration,
+    pub jitter: Duration, // Random extra delay of up to this much
+    pub loss: f32,        // Chance a packet never arrives
+    pub duplication: f32, // Chance a packet arrives twice
+    pub reordering: f32,  // Chance a packet is held back until later ones overtake it
+}
+
+impl NetworkConditions {
+    pub fn is_perfect(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        for (name, chance) in [
+            ("loss", self.loss),
+            ("duplication", self.duplication),
+            ("reordering", self.reordering),
+        ] {
+            if !(0.0..=1.0).contains(&chance) {
+                return Err(format!(
+                    "Simulated {} must be between 0 and 1, got {}",
+                    name, chance
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    // When a packet sent now should arrive
+    fn arrival(&self, rng: &mut impl Rng) -> Instant {
+        let mut delay = self.latency + self.jitter.mul_f32(rng.random::<f32>());
+        if rng.random::<f32>() < self.reordering {
+            delay += self.latency + self.jitter + REORDER_DELAY;
+        }
+        Instant::now() + delay
+    }
+}
+
+// A packet waiting out its simulated delay
+struct Delayed {
+    due: Instant,
+    seq: u64, // Keeps packets due at the same moment in sending order
+    packet: Vec<u8>,
+    addr: SocketAddr,
+}
+
+impl PartialEq for Delayed {
+    fn eq(&self, other: &Self) -> bool {
+        (self.due, self.seq) == (other.due, other.seq)
+    }
+}
+
+impl Eq for Delayed {}
+
+impl PartialOrd for Delayed {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Delayed {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.due, self.seq).cmp(&(other.due, other.seq))
+    }
+}
+
+// Packets in flight one way, soonest due first
+#[derive(Default)]
+struct DelayQueue {
+    packets: BinaryHeap<Reverse<Delayed>>,
+    next_seq: u64,
+}
+
+impl DelayQueue {
+    // Put a packet in flight, unless the network loses it
+    fn schedule(&mut self, conditions: &NetworkConditions, packet: &[u8], addr: SocketAddr) {
+        let mut rng = rand::rng();
+        if rng.random::<f32>() < conditions.loss {
+            return;
+        }
+
+        let copies = if rng.random::<f32>() < conditions.duplication {
+            2
+        } else {
+            1
+        };
+        for _ in 0..copies {
+            self.next_seq += 1;
+            self.packets.push(Reverse(Delayed {
+                due: conditions.arrival(&mut rng),
+                seq: self.next_seq,
+                packet: packet.to_vec(),
+                addr,
+            }));
+        }
+    }
+
+    fn next_due(&self) -> Option<Instant> {
+        self.packets.peek().map(|Reverse(next)| next.due)
+    }
+
+    fn pop_due(&mut self) -> Option<Delayed> {
+        if self.next_due()? > Instant::now() {
+            return None;
+        }
+        self.packets.pop().map(|Reverse(delayed)| delayed)
+    }
+}
+
+// Wraps another transport and makes its link as bad as the conditions say.
+// Delayed packets move on whenever the transport is used or waited on.
+pub struct SimulatedTransport<T> {
+    inner: T,
+    conditions: NetworkConditions,
+    outgoing: Mutex<DelayQueue>,
+    incoming: Mutex<DelayQueue>,
+}
+
+impl<T: Transport> SimulatedTransport<T> {
+    pub fn new(inner: T, conditions: NetworkConditions) -> Self {
+        Self {
+            inner,
+            conditions,
+            outgoing: Mutex::new(DelayQueue::default()),
+            incoming: Mutex::new(DelayQueue::default()),
+        }
+    }
+
+    // Send every outgoing packet whose delay is up
+    fn flush_outgoing(&self, outgoing: &mut DelayQueue) -> io::Result<()> {
+        while let Some(delayed) = outgoing.pop_due() {
+            self.inner.send_to(&delayed.packet, delayed.addr)?;
+        }
+        Ok(())
+    }
+
+    // Everything that really arrived starts its simulated delay now
+    fn receive_inner(&self, incoming: &mut DelayQueue) -> io::Result<()> {
+        while let Some((packet, addr)) = self.inner.try_recv_from()? {
+            incoming.schedule(&self.conditions, &packet, addr);
+        }
+        Ok(())
+    }
+}
+
+impl<T: Transport> Transport for SimulatedTransport<T> {
+    fn send_to(&self, packet: &[u8], addr: SocketAddr) -> io::Result<usize> {
+        let mut outgoing = self.outgoing.lock().unwrap();
+        outgoing.schedule(&self.conditions, packet, addr);
+        self.flush_outgoing(&mut outgoing)?;
+        Ok(packet.len())
+    }
+
+    fn try_recv_from(&self) -> io::Result<Option<(Vec<u8>, SocketAddr)>> {
+        self.flush_outgoing(&mut self.outgoing.lock().unwrap())?;
+
+        let mut incoming = self.incoming.lock().unwrap();
+        self.receive_inner(&mut incoming)?;
+        Ok(incoming
+            .pop_due()
+            .map(|delayed| (delayed.packet, delayed.addr)))
+    }
+
+    fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.inner.local_addr()
+    }
+
+    fn readable(&self) -> Readable<'_> {
+        Box::pin(async move {
+            loop {
+                // Sleep until the next packet is due either way, or more really arrives
+                let next_due = {
+                    let mut outgoing = self.outgoing.lock().unwrap();
+                    self.flush_outgoing(&mut outgoing)?;
+                    let mut incoming = self.incoming.lock().unwrap();
+                    self.receive_inner(&mut incoming)?;
+                    if incoming.next_due().is_some_and(|due| due <= Instant::now()) {
+                        return Ok(());
+                    }
+                    incoming
+                        .next_due()
+                        .into_iter()
+                        .chain(outgoing.next_due())
+                        .min()
+                };
+                match next_due {
+                    Some(due) => tokio::select! {
+                        ready = self.inner.readable() => ready?,
+                        _ = tokio::time::sleep_until(due.into()) => {}
+                    },
+                    None => self.inner.readable().await?,
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MemoryNetwork;
+
+    const LATENCY: Duration = Duration::from_millis(20);
+
+    fn addr() -> SocketAddr {
+        SocketAddr::from(([127, 0, 0, 1], 1))
+    }
+
+    fn delayed_by(latency: Duration) -> NetworkConditions {
+        NetworkConditions {
+            latency,
+            ..NetworkConditions::default()
+        }
+    }
+
+    fn drain(queue: &mut DelayQueue) -> Vec<Vec<u8>> {
+        std::iter::from_fn(|| queue.pop_due())
+            .map(|delayed| delayed.packet)
+            .collect()
+    }
+
+    #[test]
+    fn lost_packets_never_arrive() {
+        let conditions = NetworkConditions {
+            loss: 1.0,
+            ..NetworkConditions::default()
+        };
+        let mut queue = DelayQueue::default();
+        for i in 0..10 {
+            queue.schedule(&conditions, &[i], addr());
+        }
+        assert!(queue.next_due().is_none());
+    }
+
+    #[test]
+    fn duplicated_packets_arrive_twice() {
+        let conditions = NetworkConditions {
+            duplication: 1.0,
+            ..NetworkConditions::default()
+        };
+        let mut queue = DelayQueue::default();
+        queue.schedule(&conditions, &[1], addr());
+        assert_eq!(drain(&mut queue), vec![vec![1], vec![1]]);
+    }
+
+    #[test]
+    fn delayed_packets_wait_out_the_latency_in_order() {
+        let mut queue = DelayQueue::default();
+        for i in 0..3 {
+            queue.schedule(&delayed_by(LATENCY), &[i], addr());
+        }
+        assert!(drain(&mut queue).is_empty());
+
+        std::thread::sleep(LATENCY);
+        assert_eq!(drain(&mut queue), vec![vec![0], vec![1], vec![2]]);
+    }
+
+    #[test]
+    fn reordered_packets_are_overtaken() {
+        let reordered = NetworkConditions {
+            reordering: 1.0,
+            ..delayed_by(LATENCY)
+        };
+        let mut queue = DelayQueue::default();
+        queue.schedule(&reordered, &[0], addr());
+        queue.schedule(&delayed_by(LATENCY), &[1], addr());
+
+        std::thread::sleep(LATENCY);
+        assert_eq!(drain(&mut queue), vec![vec![1]]);
+
+        std::thread::sleep(LATENCY + REORDER_DELAY);
+        assert_eq!(drain(&mut queue), vec![vec![0]]);
+    }
+
+    #[tokio::test]
+    async fn simulated_transport_wakes_when_a_delayed_packet_is_due() {
+        let network = MemoryNetwork::new();
+        let sender = network.endpoint();
+        let receiver = SimulatedTransport::new(network.endpoint(), delayed_by(LATENCY));
+
+        let sent_at = Instant::now();
+        sender
+            .send_to(&[7], receiver.local_addr().unwrap())
+            .unwrap();
+        assert!(receiver.try_recv_from().unwrap().is_none());
+
+        tokio::time::timeout(Duration::from_secs(1), receiver.readable())
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(sent_at.elapsed() >= LATENCY);
+        let (packet, from) = receiver.try_recv_from().unwrap().unwrap();
+        assert_eq!(packet, vec![7]);
+        assert_eq!(from, sender.local_addr().unwrap());
+    }
+
+    #[tokio::test]
+    async fn simulated_transport_flushes_delayed_sends_while_waiting() {
+        let network = MemoryNetwork::new();
+        let sender = SimulatedTransport::new(network.endpoint(), delayed_by(LATENCY));
+        let receiver = network.endpoint();
+
+        sender
+            .send_to(&[7], receiver.local_addr().unwrap())
+            .unwrap();
+        assert!(receiver.try_recv_from().unwrap().is_none());
+
+        // The sender has nothing to read, but waiting on it still sends on time
+        let waiting = tokio::time::timeout(LATENCY * 3, sender.readable()).await;
+        assert!(waiting.is_err());
+        assert_eq!(receiver.try_recv_from().unwrap().unwrap().0, vec![7]);
+    }
+
+    #[tokio::test]
+    async fn lossy_transport_never_wakes() {
+        let network = MemoryNetwork::new();
+        let sender = network.endpoint();
+        let lossy = NetworkConditions {
+            loss: 1.0,
+            ..NetworkConditions::default()
+        };
+        let receiver = SimulatedTransport::new(network.endpoint(), lossy);
+
+        for i in 0..5 {
+            sender
+                .send_to(&[i], receiver.local_addr().unwrap())
+                .unwrap();
+        }
+        let waiting = tokio::time::timeout(LATENCY, receiver.readable()).await;
+        assert!(waiting.is_err());
+        assert!(receiver.try_recv_from().unwrap().is_none());
+    }
+}
@@ -0,0 +1,89 @@
+use crate::player::Team;
+use bevy::math::{Vec2, Vec3};
+use serde::{Deserialize, Serialize};
+
+// How close a player has to get to touch a flag or base
+pub const FLAG_TOUCH_RADIUS: f32 = 2.5;
+// How long a dropped flag lies around before it returns home
+pub const FLAG_RETURN_SECS: f32 = 20.0;
+// Radius of the King of the Hill control zone on the floor
+pub const ZONE_RADIUS: f32 = 6.0;
+// Uncontested time inside the zone needed to take control of it
+pub const ZONE_CAPTURE_SECS: f32 = 3.0;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum FlagStatus {
+    AtBase,
+    Carried { carrier_id: String },
+    Dropped { return_in: f32 }, // Seconds until it returns to base
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum FlagEvent {
+    PickedUp,
+    Dropped,
+    Returned,
+    Captured,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FlagState {
+    pub team: Team,
+    pub status: FlagStatus,
+    pub position: Vec3,
+    pub base: Vec3,
+}
+
+impl FlagState {
+    pub fn at_base(team: Team, base: Vec3) -> Self {
+        Self {
+            team,
+            status: FlagStatus::AtBase,
+            position: base,
+            base,
+        }
+    }
+
+    pub fn carrier_id(&self) -> Option<&str> {
+        match &self.status {
+            FlagStatus::Carried { carrier_id } => Some(carrier_id),
+            _ => None,
+        }
+    }
+}
+
+// Who a control zone belongs to: a lone player, or a team in team modes
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ZoneOwner {
+    Player(String),
+    Team(Team),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ControlZone {
+    pub center: Vec3,
+    pub radius: f32,
+    pub owner: Option<ZoneOwner>, // Scores while standing in the zone alone
+    pub capturing: Option<ZoneOwner>, // Taking the zone away from the owner
+    pub progress: f32,            // Capture progress, 0.0 to 1.0
+    pub contested: bool,          // More than one side inside
+    pub moves_in: Option<f32>,    // Seconds until the zone relocates
+}
+
+impl ControlZone {
+    pub fn new(center: Vec3) -> Self {
+        Self {
+            center,
+            radius: ZONE_RADIUS,
+            owner: None,
+            capturing: None,
+            progress: 0.0,
+            contested: false,
+            moves_in: None,
+        }
+    }
+
+    pub fn contains(&self, position: Vec3) -> bool {
+        Vec2::new(position.x - self.center.x, position.z - self.center.z).length() <= self.radius
+    }
+}
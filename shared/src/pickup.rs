@@ -0,0 +1,137 @@
+use bevy::math::Vec3;
+use serde::{Deserialize, Serialize};
+
+use crate::Player;
+
+// How close (horizontally) a player has to get to grab a pickup
+pub const PICKUP_RADIUS: f32 = 2.0;
+pub const MAX_ARMOR: f32 = 100.0;
+pub const ARMOR_ABSORPTION: f32 = 0.66; // Share of incoming damage armor soaks up
+pub const SPEED_BOOST: f32 = 1.5;
+pub const DAMAGE_BOOST: f32 = 1.5;
+
+const HEALTH_PACK_AMOUNT: f32 = 50.0;
+const ARMOR_AMOUNT: f32 = 50.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PickupKind {
+    HealthPack,
+    Armor,
+    SpeedBoost,
+    DamageBoost,
+    Invisibility,
+}
+
+impl PickupKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            PickupKind::HealthPack => "Health Pack",
+            PickupKind::Armor => "Armor",
+            PickupKind::SpeedBoost => "Speed Boost",
+            PickupKind::DamageBoost => "Damage Boost",
+            PickupKind::Invisibility => "Invisibility",
+        }
+    }
+
+    pub fn color(&self) -> [f32; 3] {
+        match self {
+            PickupKind::HealthPack => [0.2, 1.0, 0.3],
+            PickupKind::Armor => [0.3, 0.6, 1.0],
+            PickupKind::SpeedBoost => [1.0, 0.9, 0.2],
+            PickupKind::DamageBoost => [1.0, 0.25, 0.2],
+            PickupKind::Invisibility => [0.8, 0.4, 1.0],
+        }
+    }
+
+    // Seconds before a collected pickup reappears
+    pub fn respawn_secs(&self) -> f32 {
+        match self {
+            PickupKind::HealthPack => 20.0,
+            PickupKind::Armor => 30.0,
+            PickupKind::SpeedBoost | PickupKind::DamageBoost => 45.0,
+            PickupKind::Invisibility => 60.0,
+        }
+    }
+
+    pub fn power_up(&self) -> Option<PowerUp> {
+        match self {
+            PickupKind::SpeedBoost => Some(PowerUp::Speed),
+            PickupKind::DamageBoost => Some(PowerUp::Damage),
+            PickupKind::Invisibility => Some(PowerUp::Invisibility),
+            PickupKind::HealthPack | PickupKind::Armor => None,
+        }
+    }
+
+    // Give the pickup's effect to a player, false if they don't need it
+    pub fn apply(&self, player: &mut Player) -> bool {
+        match self {
+            PickupKind::HealthPack if player.health < player.max_health => {
+                player.health = (player.health + HEALTH_PACK_AMOUNT).min(player.max_health);
+                true
+            }
+            PickupKind::Armor if player.armor < MAX_ARMOR => {
+                player.armor = (player.armor + ARMOR_AMOUNT).min(MAX_ARMOR);
+                true
+            }
+            PickupKind::HealthPack | PickupKind::Armor => false,
+            _ => {
+                if let Some(power_up) = self.power_up() {
+                    player.add_power_up(power_up);
+                }
+                true
+            }
+        }
+    }
+}
+
+// Timed effects granted by power-up pickups
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PowerUp {
+    Speed,
+    Damage,
+    Invisibility,
+}
+
+impl PowerUp {
+    pub fn duration_secs(&self) -> f32 {
+        match self {
+            PowerUp::Speed => 10.0,
+            PowerUp::Damage => 15.0,
+            PowerUp::Invisibility => 12.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ActivePowerUp {
+    pub kind: PowerUp,
+    pub remaining: f32, // Seconds left, counted down by the server and locally on clients
+}
+
+// Where the maze generator placed a pickup spawner
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PickupSpawn {
+    pub kind: PickupKind,
+    pub position: Vec3,
+}
+
+// A pickup spawner and whether its pickup is currently there to collect
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PickupState {
+    pub id: u32,
+    pub kind: PickupKind,
+    pub position: Vec3,
+    pub available: bool,
+}
+
+impl PickupState {
+    pub fn in_reach(&self, position: Vec3) -> bool {
+        Vec3::new(
+            position.x - self.position.x,
+            0.0,
+            position.z - self.position.z,
+        )
+        .length()
+            <= PICKUP_RADIUS
+    }
+}
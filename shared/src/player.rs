@@ -2,6 +2,82 @@ use bevy::math::{Quat, Vec3};
 use rand::Rng;
 use serde::{Deserialize, Serialize};
 
+use crate::{
+    ARMOR_ABSORPTION, ASSIST_WINDOW_SECS, ActivePowerUp, Ammo, DAMAGE_BOOST, DamageEvent, PowerUp,
+    SPEED_BOOST, WeaponKind, weapons,
+};
+use std::collections::HashMap;
+
+// Radius of the player sphere, used for hit detection
+pub const PLAYER_HIT_RADIUS: f32 = 1.5;
+
+// Middle of the maze, which is centred on the origin, until a spawn point is assigned
+const DEFAULT_POSITION: Vec3 = Vec3::new(0.0, 1.0, 0.0);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Team {
+    Red,
+    Blue,
+    Survivors, // Infection only
+    Infected,  // Infection only
+}
+
+impl Team {
+    // The two sides of the Red vs Blue modes
+    pub const ALL: [Team; 2] = [Team::Red, Team::Blue];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Team::Red => "Red",
+            Team::Blue => "Blue",
+            Team::Survivors => "Survivors",
+            Team::Infected => "Infected",
+        }
+    }
+
+    // RGB color shared by every member of the team
+    pub fn color(&self) -> [f32; 3] {
+        match self {
+            Team::Red => [0.95, 0.25, 0.2],
+            Team::Blue => [0.2, 0.45, 0.95],
+            Team::Survivors => [0.9, 0.9, 0.85],
+            Team::Infected => [0.45, 0.9, 0.1],
+        }
+    }
+}
+
+// Per-player multipliers on top of the base movement and weapon stats
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Loadout {
+    pub speed: f32,
+    pub damage: f32,
+    pub range: f32,
+    pub fire_rate: f32,
+}
+
+impl Loadout {
+    pub const STANDARD: Loadout = Loadout {
+        speed: 1.0,
+        damage: 1.0,
+        range: 1.0,
+        fire_rate: 1.0,
+    };
+
+    // Fast, hard-hitting melee range claws
+    pub const INFECTED: Loadout = Loadout {
+        speed: 1.3,
+        damage: 2.0,
+        range: 0.08,
+        fire_rate: 0.5,
+    };
+}
+
+impl Default for Loadout {
+    fn default() -> Self {
+        Self::STANDARD
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Player {
     pub id: String,
@@ -10,56 +86,194 @@ pub struct Player {
     pub rotation: Quat,
     pub health: f32,
     pub max_health: f32,
+    pub armor: f32, // Soaks up part of incoming damage until used up
     pub kills: u32,
     pub deaths: u32,
+    pub assists: u32,
+    pub streak: u32, // Kills since last death
+    #[serde(skip)]
+    pub multi_kill: (u32, f64), // Kills in the current multi-kill chain and when the last one was
+    pub score: u32,  // Objective points, e.g. time spent holding the hill
     pub is_alive: bool,
     pub last_shot_time: f64,
     pub death_time: Option<f64>,
     pub last_damage_time: Option<f64>,
-    pub last_damage_by: Option<u32>,
-    pub color: [f32; 3], // RGB color values (0.0 to 1.0)
+    pub last_damage_by: Option<String>,
+    #[serde(skip)]
+    pub recent_attackers: HashMap<String, f64>, // Who hurt us and when, for assists
+    pub color: [f32; 3],    // RGB color values (0.0 to 1.0)
+    pub team: Option<Team>, // None in free-for-all modes
+    pub loadout: Loadout,
+    pub weapon: WeaponKind,
+    pub ammo: HashMap<WeaponKind, Ammo>,
+    pub reload_done_at: Option<f64>, // When the current reload finishes
+    pub power_ups: Vec<ActivePowerUp>,
+    pub ping_ms: Option<u32>, // Round trip to the server, None for bots and until measured
 }
 
 impl Player {
     pub fn new(id: String, name: String) -> Self {
         let mut rng = rand::rng();
-        Self {
+        let mut player = Self {
             id,
             name,
-            position: Vec3::new(48.0, 2.5, 48.0), // Default center position (server will assign proper spawn)
+            position: DEFAULT_POSITION,
             rotation: Quat::IDENTITY,
             health: 100.0,
             max_health: 100.0,
+            armor: 0.0,
             kills: 0,
             deaths: 0,
+            assists: 0,
+            streak: 0,
+            multi_kill: (0, 0.0),
+            score: 0,
             is_alive: true,
             last_shot_time: 0.0,
             death_time: None,
             last_damage_time: None,
             last_damage_by: None,
+            recent_attackers: HashMap::new(),
             color: [
                 rng.random_range(0.3..1.0), // Red component (avoid too dark)
                 rng.random_range(0.3..1.0), // Green component
                 rng.random_range(0.3..1.0), // Blue component
             ],
+            team: None,
+            loadout: Loadout::STANDARD,
+            weapon: WeaponKind::default(),
+            ammo: HashMap::new(),
+            reload_done_at: None,
+            power_ups: Vec::new(),
+            ping_ms: None,
+        };
+        player.refill_ammo();
+        player
+    }
+
+    // Joining a team replaces the random color with the team color
+    pub fn join_team(&mut self, team: Team) {
+        self.team = Some(team);
+        self.color = team.color();
+    }
+
+    // Full magazines and reserves for every weapon, e.g. on spawn
+    pub fn refill_ammo(&mut self) {
+        self.ammo = weapons().iter().map(|w| (w.kind, Ammo::full(w))).collect();
+        self.reload_done_at = None;
+    }
+
+    // Ammo of the weapon currently in hand
+    pub fn current_ammo(&self) -> Ammo {
+        self.ammo
+            .get(&self.weapon)
+            .copied()
+            .unwrap_or_else(|| Ammo::full(weapons().get(self.weapon)))
+    }
+
+    // Switch to the infected team with its tougher body and stats
+    pub fn infect(&mut self) {
+        self.join_team(Team::Infected);
+        self.loadout = Loadout::INFECTED;
+        self.max_health = 150.0;
+    }
+
+    pub fn cure(&mut self) {
+        self.join_team(Team::Survivors);
+        self.loadout = Loadout::STANDARD;
+        self.max_health = 100.0;
+        self.health = self.health.min(self.max_health);
+    }
+
+    pub fn is_infected(&self) -> bool {
+        self.team == Some(Team::Infected)
+    }
+
+    // Start a power-up, or restart its timer if it's already running
+    pub fn add_power_up(&mut self, kind: PowerUp) {
+        self.power_ups.retain(|p| p.kind != kind);
+        self.power_ups.push(ActivePowerUp {
+            kind,
+            remaining: kind.duration_secs(),
+        });
+    }
+
+    pub fn has_power_up(&self, kind: PowerUp) -> bool {
+        self.power_ups.iter().any(|p| p.kind == kind)
+    }
+
+    // Count power-ups down, returning true if any ran out
+    pub fn tick_power_ups(&mut self, dt: f32) -> bool {
+        let before = self.power_ups.len();
+        for power_up in self.power_ups.iter_mut() {
+            power_up.remaining -= dt;
         }
+        self.power_ups.retain(|p| p.remaining > 0.0);
+        self.power_ups.len() != before
+    }
+
+    pub fn speed_multiplier(&self) -> f32 {
+        let boost = if self.has_power_up(PowerUp::Speed) {
+            SPEED_BOOST
+        } else {
+            1.0
+        };
+        self.loadout.speed * boost
+    }
+
+    pub fn damage_multiplier(&self) -> f32 {
+        let boost = if self.has_power_up(PowerUp::Damage) {
+            DAMAGE_BOOST
+        } else {
+            1.0
+        };
+        self.loadout.damage * boost
+    }
+
+    pub fn is_teammate(&self, other: &Player) -> bool {
+        self.team.is_some() && self.team == other.team
     }
 
     pub fn respawn(&mut self) {
         self.health = self.max_health;
         self.is_alive = true;
-        self.position = Vec3::new(96.0, 2.5, 96.0); // Reset to spawn
+        self.position = DEFAULT_POSITION; // The server moves us to a spawn point
+        self.refill_ammo();
         self.death_time = None;
         self.last_damage_time = None;
         self.last_damage_by = None;
+        self.recent_attackers.clear();
+        self.armor = 0.0;
+        self.power_ups.clear();
+    }
+
+    // Everyone but the killer who damaged us within the assist window
+    pub fn assisters(&self, killer_id: Option<&str>, now: f64) -> Vec<String> {
+        self.recent_attackers
+            .iter()
+            .filter(|(id, at)| Some(id.as_str()) != killer_id && now - **at <= ASSIST_WINDOW_SECS)
+            .map(|(id, _)| id.clone())
+            .collect()
     }
 
-    pub fn take_damage(&mut self, damage: f32) -> bool {
+    // Apply a hit at time now (unix seconds), returning true if it killed us
+    pub fn take_damage(&mut self, event: &DamageEvent, now: f64) -> bool {
         if !self.is_alive {
             return false;
         }
 
-        self.health -= damage;
+        self.last_damage_time = Some(now);
+        self.last_damage_by = event.source_id.clone();
+        if let Some(source_id) = &event.source_id
+            && *source_id != self.id
+        {
+            self.recent_attackers.insert(source_id.clone(), now);
+        }
+
+        // Armor takes its share of the hit first
+        let absorbed = (event.amount * ARMOR_ABSORPTION).min(self.armor);
+        self.armor -= absorbed;
+        self.health -= event.amount - absorbed;
         if self.health <= 0.0 {
             self.health = 0.0;
             self.is_alive = false;
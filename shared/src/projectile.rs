@@ -0,0 +1,119 @@
+use bevy::math::Vec3;
+use serde::{Deserialize, Serialize};
+
+use crate::{MazeGrid, WeaponKind, is_wall_at};
+
+const MAX_STEP_DISTANCE: f32 = 0.5; // Longest move between wall checks
+const GROUND_FRICTION: f32 = 0.8; // Horizontal speed kept when bouncing off the floor
+const MIN_BOUNCE_SPEED: f32 = 1.0; // Slower floor hits stop bouncing
+
+// How a projectile weapon's shots fly, loaded with the rest of the weapon table
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectileDef {
+    pub speed: f32,
+    pub gravity: f32,
+    pub radius: f32,
+    pub splash_radius: f32,
+    pub bounciness: f32, // Speed kept after hitting a wall; 0 explodes on impact
+    pub fuse_secs: f32,  // Explodes on its own after this long
+    pub color: [f32; 3],
+}
+
+// A projectile in flight, as simulated by the server and replicated to clients
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectileState {
+    pub id: u32,
+    pub owner_id: String,
+    pub weapon: WeaponKind,
+    pub position: Vec3,
+    pub velocity: Vec3,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProjectileStep {
+    Flying,
+    Bounced,
+    Impact, // Hit a wall or the floor and should explode where it stopped
+}
+
+impl ProjectileState {
+    // Move through the maze for dt seconds, bouncing off or stopping at walls and the floor
+    pub fn advance(&mut self, grid: &MazeGrid, def: &ProjectileDef, dt: f32) -> ProjectileStep {
+        let steps = ((self.velocity.length() * dt) / MAX_STEP_DISTANCE)
+            .ceil()
+            .max(1.0);
+        let step_dt = dt / steps;
+
+        let mut result = ProjectileStep::Flying;
+        for _ in 0..steps as u32 {
+            match self.step(grid, def, step_dt) {
+                ProjectileStep::Impact => return ProjectileStep::Impact,
+                ProjectileStep::Bounced => result = ProjectileStep::Bounced,
+                ProjectileStep::Flying => {}
+            }
+        }
+        result
+    }
+
+    fn step(&mut self, grid: &MazeGrid, def: &ProjectileDef, dt: f32) -> ProjectileStep {
+        self.velocity.y -= def.gravity * dt;
+        let mut next = self.position + self.velocity * dt;
+        let mut bounced = false;
+
+        if next.y < def.radius {
+            if def.bounciness <= 0.0 {
+                self.position = Vec3::new(next.x, def.radius, next.z);
+                return ProjectileStep::Impact;
+            }
+            // Slow landings settle into rolling along the floor
+            next.y = def.radius;
+            bounced = -self.velocity.y * def.bounciness > MIN_BOUNCE_SPEED;
+            self.velocity.y = if bounced {
+                -self.velocity.y * def.bounciness
+            } else {
+                0.0
+            };
+            self.velocity.x *= GROUND_FRICTION;
+            self.velocity.z *= GROUND_FRICTION;
+        }
+
+        if touches_wall(grid, next, def.radius) {
+            if def.bounciness <= 0.0 {
+                return ProjectileStep::Impact;
+            }
+
+            // Reflect whichever horizontal components carried it into the wall
+            let moved_x = Vec3::new(next.x, self.position.y, self.position.z);
+            let moved_z = Vec3::new(self.position.x, self.position.y, next.z);
+            let hit_x = touches_wall(grid, moved_x, def.radius);
+            let hit_z = touches_wall(grid, moved_z, def.radius);
+            if hit_x || !hit_z {
+                self.velocity.x = -self.velocity.x * def.bounciness;
+            }
+            if hit_z || !hit_x {
+                self.velocity.z = -self.velocity.z * def.bounciness;
+            }
+            next.x = self.position.x;
+            next.z = self.position.z;
+            bounced = true;
+        }
+
+        self.position = next;
+        if bounced {
+            ProjectileStep::Bounced
+        } else {
+            ProjectileStep::Flying
+        }
+    }
+}
+
+fn touches_wall(grid: &MazeGrid, position: Vec3, radius: f32) -> bool {
+    [
+        Vec3::new(radius, 0.0, 0.0),
+        Vec3::new(-radius, 0.0, 0.0),
+        Vec3::new(0.0, 0.0, radius),
+        Vec3::new(0.0, 0.0, -radius),
+    ]
+    .iter()
+    .any(|offset| is_wall_at(grid, position + *offset))
+}
@@ -0,0 +1,136 @@
+use bevy::math::{Quat, Vec3};
+use serde::{Deserialize, Serialize};
+use std::io::{self, Read, Write};
+
+use crate::{GameMode, MazeConfig, Player, ServerMessage};
+
+// Replay files start with these bytes, then a header, then entries until the end
+pub const REPLAY_MAGIC: [u8; 4] = *b"SWRP";
+// Bumped whenever the layout of anything written to a replay changes
+pub const REPLAY_VERSION: u16 = 4;
+pub const REPLAY_EXTENSION: &str = "swr";
+
+// What a replay was recorded on, enough to rebuild the maze it was played in
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayHeader {
+    pub version: u16,
+    pub recorded_at: f64, // Unix time the recording started
+    pub mode: GameMode,
+    pub maze: MazeConfig,
+    pub tick_rate: u32, // Server ticks per second
+}
+
+// Where a player was and how they were doing at the end of a tick
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlayerSnapshot {
+    pub player_id: String,
+    pub position: Vec3,
+    pub rotation: Quat,
+    pub health: f32,
+    pub armor: f32,
+    pub is_alive: bool,
+}
+
+impl PlayerSnapshot {
+    pub fn of(player: &Player) -> Self {
+        Self {
+            player_id: player.id.clone(),
+            position: player.position,
+            rotation: player.rotation,
+            health: player.health,
+            armor: player.armor,
+            is_alive: player.is_alive,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ReplayRecord {
+    // Only the players that changed since the previous snapshot
+    Snapshot { players: Vec<PlayerSnapshot> },
+    // Something the server told everyone, or a hit only the shooter hears about
+    Event(Box<ServerMessage>),
+    // Everything a client joining at this moment would be told, so playback can
+    // seek here without replaying the match from the start
+    Keyframe { messages: Vec<ServerMessage> },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayEntry {
+    pub time_ms: u64, // Since the recording started
+    pub record: ReplayRecord,
+}
+
+pub struct ReplayWriter<W: Write> {
+    writer: W,
+}
+
+impl<W: Write> ReplayWriter<W> {
+    pub fn new(mut writer: W, header: &ReplayHeader) -> io::Result<Self> {
+        writer.write_all(&REPLAY_MAGIC)?;
+        bincode::serde::encode_into_std_write(header, &mut writer, bincode::config::standard())
+            .map_err(io::Error::other)?;
+        Ok(Self { writer })
+    }
+
+    pub fn write(&mut self, entry: &ReplayEntry) -> io::Result<()> {
+        bincode::serde::encode_into_std_write(entry, &mut self.writer, bincode::config::standard())
+            .map_err(io::Error::other)?;
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+pub struct ReplayReader<R: Read> {
+    reader: R,
+    header: ReplayHeader,
+}
+
+impl<R: Read> ReplayReader<R> {
+    pub fn new(mut reader: R) -> io::Result<Self> {
+        let mut magic = [0; REPLAY_MAGIC.len()];
+        reader.read_exact(&mut magic)?;
+        if magic != REPLAY_MAGIC {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "Not a Sphere Wars replay",
+            ));
+        }
+
+        let header: ReplayHeader =
+            bincode::serde::decode_from_std_read(&mut reader, bincode::config::standard())
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        if header.version != REPLAY_VERSION {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "Replay format version {} is not supported, expected {}",
+                    header.version, REPLAY_VERSION
+                ),
+            ));
+        }
+
+        Ok(Self { reader, header })
+    }
+
+    pub fn header(&self) -> &ReplayHeader {
+        &self.header
+    }
+
+    // Next entry, or None at the end. A recording cut short by a crash just ends
+    // at its last complete entry.
+    pub fn next_entry(&mut self) -> io::Result<Option<ReplayEntry>> {
+        match bincode::serde::decode_from_std_read(&mut self.reader, bincode::config::standard()) {
+            Ok(entry) => Ok(Some(entry)),
+            Err(bincode::error::DecodeError::Io { inner, .. })
+                if inner.kind() == io::ErrorKind::UnexpectedEof =>
+            {
+                Ok(None)
+            }
+            Err(e) => Err(io::Error::new(io::ErrorKind::InvalidData, e)),
+        }
+    }
+}
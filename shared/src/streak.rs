@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+
+// Kills this close together chain into a multi-kill
+pub const MULTI_KILL_WINDOW_SECS: f64 = 4.0;
+// Streaks shorter than this aren't worth announcing
+pub const MIN_ANNOUNCED_STREAK: u32 = 3;
+// Every this many kills in a row earns a radar pulse
+pub const RADAR_PULSE_STREAK: u32 = 5;
+pub const RADAR_PULSE_SECS: f32 = 8.0;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum StreakKind {
+    KillStreak, // Kills in a row without dying
+    MultiKill,  // Kills in quick succession
+    Ended { ended_by: Option<String> },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum StreakReward {
+    RadarPulse { secs: f32 }, // Enemies show on the minimap
+}
+
+pub fn kill_streak_name(count: u32) -> Option<&'static str> {
+    match count {
+        3 => Some("KILLING SPREE"),
+        5 => Some("RAMPAGE"),
+        7 => Some("DOMINATING"),
+        10 => Some("UNSTOPPABLE"),
+        15.. if count.is_multiple_of(5) => Some("GODLIKE"),
+        _ => None,
+    }
+}
+
+pub fn multi_kill_name(count: u32) -> &'static str {
+    match count {
+        0..=2 => "DOUBLE KILL",
+        3 => "TRIPLE KILL",
+        4 => "MULTI KILL",
+        _ => "MONSTER KILL",
+    }
+}
+
+pub fn streak_reward(count: u32) -> Option<StreakReward> {
+    (count > 0 && count.is_multiple_of(RADAR_PULSE_STREAK)).then_some(StreakReward::RadarPulse {
+        secs: RADAR_PULSE_SECS,
+    })
+}
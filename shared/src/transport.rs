@@ -0,0 +1,194 @@
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::io;
+use std::net::{Ipv4Addr, SocketAddr, ToSocketAddrs, UdpSocket};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, OnceLock};
+use tokio::sync::Notify;
+
+const MAX_PACKET_SIZE: usize = 65536;
+
+pub type Readable<'a> = Pin<Box<dyn Future<Output = io::Result<()>> + Send + 'a>>;
+
+// How packets get between clients and the server. Receiving never blocks: it
+// returns straight away when there is nothing to read. Frame-driven callers poll,
+// async ones wait on `readable` and then read until nothing is left.
+pub trait Transport: Send + Sync {
+    fn send_to(&self, packet: &[u8], addr: SocketAddr) -> io::Result<usize>;
+    // Next packet that has arrived and who sent it, if any
+    fn try_recv_from(&self) -> io::Result<Option<(Vec<u8>, SocketAddr)>>;
+    fn local_addr(&self) -> io::Result<SocketAddr>;
+    // Resolves once a packet may be waiting. It can wake with nothing to read,
+    // but never sleeps through a packet that arrived after the last empty read.
+    fn readable(&self) -> Readable<'_>;
+}
+
+// Real networking over a non-blocking UDP socket
+pub struct UdpTransport {
+    socket: UdpSocket,
+    // Registered with tokio the first time someone waits, so polling callers
+    // don't need a runtime
+    registered: OnceLock<tokio::net::UdpSocket>,
+    buf: Mutex<Vec<u8>>, // Reused for every receive
+}
+
+impl UdpTransport {
+    pub fn bind(addr: impl ToSocketAddrs) -> io::Result<Self> {
+        let socket = UdpSocket::bind(addr)?;
+        socket.set_nonblocking(true)?;
+        Ok(Self {
+            socket,
+            registered: OnceLock::new(),
+            buf: Mutex::new(vec![0; MAX_PACKET_SIZE]),
+        })
+    }
+
+    fn registered(&self) -> io::Result<&tokio::net::UdpSocket> {
+        if let Some(socket) = self.registered.get() {
+            return Ok(socket);
+        }
+        let socket = tokio::net::UdpSocket::from_std(self.socket.try_clone()?)?;
+        Ok(self.registered.get_or_init(|| socket))
+    }
+}
+
+impl Transport for UdpTransport {
+    fn send_to(&self, packet: &[u8], addr: SocketAddr) -> io::Result<usize> {
+        self.socket.send_to(packet, addr)
+    }
+
+    fn try_recv_from(&self) -> io::Result<Option<(Vec<u8>, SocketAddr)>> {
+        let mut buf = self.buf.lock().unwrap();
+        // Once tokio is watching, read through it so it knows when we've run dry
+        let received = match self.registered.get() {
+            Some(socket) => socket.try_recv_from(&mut buf),
+            None => self.socket.recv_from(&mut buf),
+        };
+        match received {
+            Ok((len, addr)) => Ok(Some((buf[..len].to_vec(), addr))),
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.socket.local_addr()
+    }
+
+    fn readable(&self) -> Readable<'_> {
+        Box::pin(async move { self.registered()?.readable().await })
+    }
+}
+
+struct Inbox {
+    packets: VecDeque<(Vec<u8>, SocketAddr)>,
+    arrived: Arc<Notify>, // Wakes the endpoint's reader on every delivery
+}
+
+#[derive(Default)]
+struct MemoryNetworkState {
+    inboxes: HashMap<SocketAddr, Inbox>,
+    next_port: u16,
+}
+
+// A network that only exists in memory, for running clients and a server in one
+// process. Delivery is instant and in order; packets to unknown addresses vanish.
+#[derive(Clone, Default)]
+pub struct MemoryNetwork {
+    state: Arc<Mutex<MemoryNetworkState>>,
+}
+
+impl MemoryNetwork {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Plug a new endpoint into the network under an address of its own
+    pub fn endpoint(&self) -> MemoryTransport {
+        let mut state = self.state.lock().unwrap();
+        state.next_port += 1;
+        let addr = SocketAddr::from((Ipv4Addr::LOCALHOST, state.next_port));
+        let arrived = Arc::new(Notify::new());
+        state.inboxes.insert(
+            addr,
+            Inbox {
+                packets: VecDeque::new(),
+                arrived: arrived.clone(),
+            },
+        );
+
+        MemoryTransport {
+            network: self.clone(),
+            addr,
+            arrived,
+        }
+    }
+}
+
+pub struct MemoryTransport {
+    network: MemoryNetwork,
+    addr: SocketAddr,
+    arrived: Arc<Notify>,
+}
+
+impl Transport for MemoryTransport {
+    fn send_to(&self, packet: &[u8], addr: SocketAddr) -> io::Result<usize> {
+        let mut state = self.network.state.lock().unwrap();
+        if let Some(inbox) = state.inboxes.get_mut(&addr) {
+            inbox.packets.push_back((packet.to_vec(), self.addr));
+            inbox.arrived.notify_one();
+        }
+        Ok(packet.len())
+    }
+
+    fn try_recv_from(&self) -> io::Result<Option<(Vec<u8>, SocketAddr)>> {
+        let mut state = self.network.state.lock().unwrap();
+        Ok(state
+            .inboxes
+            .get_mut(&self.addr)
+            .and_then(|inbox| inbox.packets.pop_front()))
+    }
+
+    fn local_addr(&self) -> io::Result<SocketAddr> {
+        Ok(self.addr)
+    }
+
+    fn readable(&self) -> Readable<'_> {
+        // A delivery while nobody waits leaves a permit, so none is missed
+        Box::pin(async move {
+            self.arrived.notified().await;
+            Ok(())
+        })
+    }
+}
+
+impl Drop for MemoryTransport {
+    fn drop(&mut self) {
+        if let Ok(mut state) = self.network.state.lock() {
+            state.inboxes.remove(&self.addr);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn udp_wakes_for_every_packet_after_running_dry() {
+        let sender = UdpTransport::bind("127.0.0.1:0").unwrap();
+        let receiver = UdpTransport::bind("127.0.0.1:0").unwrap();
+        let to = receiver.local_addr().unwrap();
+
+        for i in 0..2u8 {
+            sender.send_to(&[i], to).unwrap();
+            tokio::time::timeout(Duration::from_secs(1), receiver.readable())
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(receiver.try_recv_from().unwrap().unwrap().0, vec![i]);
+            assert!(receiver.try_recv_from().unwrap().is_none());
+        }
+    }
+}
@@ -0,0 +1,307 @@
+use bevy::math::Vec3;
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+
+use crate::ProjectileDef;
+
+// Weapon table compiled into both the client and server so they always agree
+const BUILTIN_WEAPONS: &str = include_str!("../data/weapons.ron");
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum WeaponKind {
+    #[default]
+    Pistol,
+    Shotgun,
+    Rifle,
+    Sniper,
+    PlasmaRifle,
+    GrenadeLauncher,
+}
+
+impl WeaponKind {
+    // Weapon slots in number key order
+    pub const ALL: [WeaponKind; 6] = [
+        WeaponKind::Pistol,
+        WeaponKind::Shotgun,
+        WeaponKind::Rifle,
+        WeaponKind::Sniper,
+        WeaponKind::PlasmaRifle,
+        WeaponKind::GrenadeLauncher,
+    ];
+}
+
+// Shape of the damage drop-off between full and minimum damage
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum Falloff {
+    None,
+    #[default]
+    Linear,
+    Quadratic, // Drops quickly at first, e.g. shotgun pellets
+    Smooth,    // Holds up, then eases off towards the end
+}
+
+impl Falloff {
+    // Fraction of the way from full to minimum damage, given progress t in [0, 1]
+    pub fn apply(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Falloff::None => 0.0,
+            Falloff::Linear => t,
+            Falloff::Quadratic => 1.0 - (1.0 - t) * (1.0 - t),
+            Falloff::Smooth => t * t * (3.0 - 2.0 * t),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WeaponDef {
+    pub kind: WeaponKind,
+    pub name: String,
+    pub damage: f32, // Per pellet
+    pub range: f32,
+    pub fire_rate: f32, // Shots per second
+    pub pellets: u32,
+    pub spread: f32, // Cone half-angle in degrees
+    pub falloff_start: f32,
+    pub min_damage_scale: f32, // Fraction of damage left at max range
+    #[serde(default)]
+    pub falloff: Falloff,
+    pub magazine_size: u32,
+    pub reserve_ammo: u32, // Spare rounds carried on spawn
+    pub reload_secs: f32,
+    #[serde(default)]
+    pub projectile: Option<ProjectileDef>, // Fires travelling shots instead of hitscan
+}
+
+impl WeaponDef {
+    // Direct hit damage, falling off from falloff_start down to min_damage_scale at max range
+    pub fn damage_at(&self, distance: f32) -> f32 {
+        if distance <= self.falloff_start || self.range <= self.falloff_start {
+            return self.damage;
+        }
+
+        let t = (distance - self.falloff_start) / (self.range - self.falloff_start);
+        self.damage * (1.0 - self.falloff.apply(t) * (1.0 - self.min_damage_scale))
+    }
+
+    // Explosion damage at a distance from the blast, down to min_damage_scale at its edge
+    pub fn splash_damage_at(&self, distance: f32) -> f32 {
+        let Some(projectile) = &self.projectile else {
+            return 0.0;
+        };
+        if distance > projectile.splash_radius {
+            return 0.0;
+        }
+
+        let t = distance / projectile.splash_radius.max(f32::EPSILON);
+        self.damage * (1.0 - self.falloff.apply(t) * (1.0 - self.min_damage_scale))
+    }
+
+    pub fn cooldown(&self) -> f32 {
+        1.0 / self.fire_rate.max(f32::EPSILON)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Ammo {
+    pub magazine: u32,
+    pub reserve: u32,
+}
+
+impl Ammo {
+    pub fn full(weapon: &WeaponDef) -> Self {
+        Self {
+            magazine: weapon.magazine_size,
+            reserve: weapon.reserve_ammo,
+        }
+    }
+
+    pub fn can_reload(&self, weapon: &WeaponDef) -> bool {
+        self.magazine < weapon.magazine_size && self.reserve > 0
+    }
+
+    // Move rounds from the reserve into the magazine
+    pub fn reload(&mut self, weapon: &WeaponDef) {
+        let loaded =
+            (weapon.magazine_size - self.magazine.min(weapon.magazine_size)).min(self.reserve);
+        self.magazine += loaded;
+        self.reserve -= loaded;
+    }
+}
+
+// Ammo of the weapon a player is holding, as sent to that player
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct AmmoStatus {
+    pub weapon: WeaponKind,
+    pub ammo: Ammo,
+    pub reload_remaining: Option<f32>, // Seconds until the reload finishes
+}
+
+#[derive(Debug, Clone)]
+pub struct WeaponRegistry {
+    weapons: Vec<WeaponDef>,
+}
+
+impl WeaponRegistry {
+    pub fn from_ron(text: &str) -> Result<Self, String> {
+        let weapons: Vec<WeaponDef> =
+            ron::from_str(text).map_err(|e| format!("Invalid weapon data: {}", e))?;
+
+        if let Some(missing) = WeaponKind::ALL
+            .iter()
+            .find(|kind| !weapons.iter().any(|w| w.kind == **kind))
+        {
+            return Err(format!("Weapon data is missing {:?}", missing));
+        }
+
+        Ok(Self { weapons })
+    }
+
+    pub fn get(&self, kind: WeaponKind) -> &WeaponDef {
+        self.weapons
+            .iter()
+            .find(|w| w.kind == kind)
+            .unwrap_or(&self.weapons[0])
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &WeaponDef> {
+        self.weapons.iter()
+    }
+}
+
+// The weapon table from data/weapons.ron
+pub fn weapons() -> &'static WeaponRegistry {
+    static REGISTRY: OnceLock<WeaponRegistry> = OnceLock::new();
+    REGISTRY.get_or_init(|| {
+        WeaponRegistry::from_ron(BUILTIN_WEAPONS).expect("built-in weapon data should be valid")
+    })
+}
+
+// Tilt a direction by a random amount inside a cone, given two uniform samples in [0, 1)
+pub fn spread_direction(direction: Vec3, spread_degrees: f32, u: f32, v: f32) -> Vec3 {
+    if spread_degrees <= 0.0 {
+        return direction;
+    }
+
+    let direction = direction.normalize_or_zero();
+    let angle = spread_degrees.to_radians() * u.sqrt();
+    let around = v * std::f32::consts::TAU;
+
+    let side = direction.any_orthonormal_vector();
+    let up = direction.cross(side);
+    (direction * angle.cos() + (side * around.cos() + up * around.sin()) * angle.sin()).normalize()
+}
+
+// Distance along a ray to where it enters a sphere, if it hits at all
+pub fn ray_sphere_distance(
+    origin: Vec3,
+    direction: Vec3,
+    center: Vec3,
+    radius: f32,
+) -> Option<f32> {
+    let to_center = center - origin;
+    let along = to_center.dot(direction);
+    let closest_sq = to_center.length_squared() - along * along;
+    let radius_sq = radius * radius;
+    if closest_sq > radius_sq {
+        return None;
+    }
+
+    let half_chord = (radius_sq - closest_sq).sqrt();
+    let entry = along - half_chord;
+    if entry >= 0.0 {
+        Some(entry)
+    } else if along + half_chord >= 0.0 {
+        Some(0.0) // Starting inside the sphere
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_weapon() -> WeaponDef {
+        WeaponDef {
+            kind: WeaponKind::Pistol,
+            name: "Test".to_string(),
+            damage: 20.0,
+            range: 50.0,
+            fire_rate: 2.0,
+            pellets: 1,
+            spread: 0.0,
+            falloff_start: 10.0,
+            min_damage_scale: 0.5,
+            falloff: Falloff::Linear,
+            magazine_size: 10,
+            reserve_ammo: 15,
+            reload_secs: 1.0,
+            projectile: None,
+        }
+    }
+
+    #[test]
+    fn falloff_curves_run_from_none_to_full() {
+        for falloff in [Falloff::Linear, Falloff::Quadratic, Falloff::Smooth] {
+            assert_eq!(falloff.apply(0.0), 0.0);
+            assert_eq!(falloff.apply(1.0), 1.0);
+            // Out of range progress is clamped
+            assert_eq!(falloff.apply(-1.0), 0.0);
+            assert_eq!(falloff.apply(2.0), 1.0);
+        }
+        assert_eq!(Falloff::None.apply(0.5), 0.0);
+        assert_eq!(Falloff::None.apply(1.0), 0.0);
+
+        // Halfway: quadratic has mostly dropped already, smooth is still even
+        assert_eq!(Falloff::Linear.apply(0.5), 0.5);
+        assert_eq!(Falloff::Quadratic.apply(0.5), 0.75);
+        assert_eq!(Falloff::Smooth.apply(0.5), 0.5);
+        assert!(Falloff::Smooth.apply(0.25) < Falloff::Linear.apply(0.25));
+    }
+
+    #[test]
+    fn damage_falls_off_past_its_start() {
+        let weapon = test_weapon();
+        assert_eq!(weapon.damage_at(0.0), 20.0);
+        assert_eq!(weapon.damage_at(10.0), 20.0);
+        assert_eq!(weapon.damage_at(30.0), 15.0);
+        assert_eq!(weapon.damage_at(50.0), 10.0);
+        // Never below the minimum, however far
+        assert_eq!(weapon.damage_at(500.0), 10.0);
+    }
+
+    #[test]
+    fn reload_tops_up_the_magazine_from_the_reserve() {
+        let weapon = test_weapon();
+        let mut ammo = Ammo {
+            magazine: 4,
+            reserve: 15,
+        };
+        assert!(ammo.can_reload(&weapon));
+        ammo.reload(&weapon);
+        assert_eq!(
+            ammo,
+            Ammo {
+                magazine: 10,
+                reserve: 9
+            }
+        );
+        assert!(!ammo.can_reload(&weapon));
+
+        // A short reserve only partly fills it
+        let mut ammo = Ammo {
+            magazine: 0,
+            reserve: 3,
+        };
+        ammo.reload(&weapon);
+        assert_eq!(
+            ammo,
+            Ammo {
+                magazine: 3,
+                reserve: 0
+            }
+        );
+        assert!(!ammo.can_reload(&weapon));
+    }
+}